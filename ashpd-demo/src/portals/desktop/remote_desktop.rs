@@ -1,8 +1,8 @@
 use crate::widgets::{NotificationKind, PortalPage, PortalPageExt, PortalPageImpl};
 use ashpd::{
     desktop::{
-        remote_desktop::{DeviceType, RemoteDesktopProxy},
-        screencast::{CursorMode, ScreenCastProxy, SourceType, Stream},
+        remote_desktop::{DeviceType, RemoteDesktopProxy, SelectDevicesOptions},
+        screencast::{CursorMode, ScreenCastProxy, SelectSourcesOptions, SourceType, Stream},
         SessionProxy,
     },
     enumflags2::BitFlags,
@@ -236,14 +236,26 @@ impl RemoteDesktopPage {
         if is_screencast {
             let screencast_proxy = ScreenCastProxy::new(&connection).await?;
             screencast_proxy
-                .select_sources(&session, cursor_mode, sources, multiple_sources)
+                .select_sources(
+                    &session,
+                    SelectSourcesOptions::default()
+                        .cursor_mode(cursor_mode)
+                        .types(sources)
+                        .multiple(multiple_sources),
+                )
                 .await?;
         }
-        proxy.select_devices(&session, devices).await?;
+        proxy
+            .select_devices(&session, SelectDevicesOptions::default().types(devices))
+            .await?;
 
         self.send_notification("Starting a remote desktop session", NotificationKind::Info);
-        let (devices, streams) = proxy.start(&session, &identifier).await?;
-        Ok((devices, streams, session))
+        let response = proxy.start(&session, &identifier).await?;
+        Ok((
+            response.devices(),
+            response.streams().unwrap_or_default().to_vec(),
+            session,
+        ))
     }
 }
 
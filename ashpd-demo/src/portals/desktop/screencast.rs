@@ -3,7 +3,7 @@ use crate::widgets::{
 };
 use ashpd::{
     desktop::{
-        screencast::{CursorMode, ScreenCastProxy, SourceType, Stream},
+        screencast::{CursorMode, ScreenCastProxy, SelectSourcesOptions, SourceType, Stream},
         SessionProxy,
     },
     enumflags2::BitFlags,
@@ -225,10 +225,16 @@ impl ScreenCastPage {
         let session = proxy.create_session().await?;
 
         proxy
-            .select_sources(&session, cursor_mode, sources, multiple)
+            .select_sources(
+                &session,
+                SelectSourcesOptions::default()
+                    .cursor_mode(cursor_mode)
+                    .types(sources)
+                    .multiple(multiple),
+            )
             .await?;
         self.send_notification("Starting a screen cast session", NotificationKind::Info);
-        let streams = proxy.start(&session, &identifier).await?.to_vec();
+        let streams = proxy.start(&session, &identifier).await?.streams().to_vec();
 
         let fd = proxy.open_pipe_wire_remote(&session).await?;
         Ok((streams, fd, session))
@@ -0,0 +1,141 @@
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use zbus::dbus_interface;
+use zvariant::OwnedObjectPath;
+use zvariant_derive::{DeserializeDict, SerializeDict, TypeDict};
+
+use super::{Options, Response, WeakServer};
+use crate::{desktop::file_chooser::Choice, PortalError};
+
+#[derive(DeserializeDict, TypeDict, Debug)]
+/// The options of an [`AccessImpl::access_dialog`] request.
+pub struct AccessDialogOptions {
+    modal: Option<bool>,
+    deny_label: Option<String>,
+    grant_label: Option<String>,
+    icon: Option<String>,
+    choices: Option<Vec<Choice>>,
+}
+
+impl AccessDialogOptions {
+    /// Whether to make the dialog modal.
+    pub fn modal(&self) -> Option<bool> {
+        self.modal
+    }
+
+    /// Label for the Deny button.
+    pub fn deny_label(&self) -> Option<&str> {
+        self.deny_label.as_deref()
+    }
+
+    /// Label for the Grant button.
+    pub fn grant_label(&self) -> Option<&str> {
+        self.grant_label.as_deref()
+    }
+
+    /// Icon name for an icon to show in the dialog.
+    pub fn icon(&self) -> Option<&str> {
+        self.icon.as_deref()
+    }
+
+    /// The combo boxes to add to the dialog.
+    pub fn choices(&self) -> &[Choice] {
+        self.choices.as_deref().unwrap_or_default()
+    }
+}
+
+#[derive(SerializeDict, DeserializeDict, TypeDict, Debug, Default)]
+/// A response to an [`AccessImpl::access_dialog`] request.
+pub struct AccessDialogResponse {
+    choices: Option<Vec<(String, String)>>,
+}
+
+impl AccessDialogResponse {
+    /// Sets the selected value of a choice.
+    pub fn choice(mut self, key: &str, value: &str) -> Self {
+        self.choices
+            .get_or_insert_with(Vec::new)
+            .push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// The selected value of each choice as a tuple of (key, value)
+    pub fn choices(&self) -> &[(String, String)] {
+        self.choices.as_deref().unwrap_or_default()
+    }
+}
+
+/// The Access portal lets other portals present a dialog asking the user to
+/// grant or deny access to a resource.
+///
+/// Implementation of the DBus interface: [`org.freedesktop.impl.portal.Access`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.impl.portal.Access).
+#[doc(alias = "org.freedesktop.impl.portal.Access")]
+pub trait AccessImpl: Send + Sync {
+    /// Presents a "deny/grant" question to the user.
+    ///
+    /// # Arguments
+    ///
+    /// * `app_id` - App id of the application.
+    /// * `parent_window` - Identifier for the application window.
+    /// * `title` - Title for the dialog.
+    /// * `subtitle` - Subtitle for the dialog.
+    /// * `body` - Body text, may be "".
+    /// * `options` - The options of the dialog.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`AccessDialog`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-impl-portal-Access.AccessDialog).
+    #[doc(alias = "AccessDialog")]
+    fn access_dialog(
+        &self,
+        app_id: String,
+        parent_window: String,
+        title: String,
+        subtitle: String,
+        body: String,
+        options: AccessDialogOptions,
+    ) -> BoxFuture<'_, Response<AccessDialogResponse>>;
+}
+
+/// The `org.freedesktop.impl.portal.Access` object.
+pub(crate) struct AccessInterface {
+    imp: Arc<dyn AccessImpl>,
+    server: WeakServer,
+}
+
+impl AccessInterface {
+    pub(crate) fn new(imp: Arc<dyn AccessImpl>, server: WeakServer) -> Self {
+        Self { imp, server }
+    }
+}
+
+#[dbus_interface(name = "org.freedesktop.impl.portal.Access")]
+impl AccessInterface {
+    #[allow(clippy::too_many_arguments)]
+    async fn access_dialog(
+        &self,
+        handle: OwnedObjectPath,
+        app_id: String,
+        parent_window: String,
+        title: String,
+        subtitle: String,
+        body: String,
+        options: Options,
+    ) -> Result<Response<AccessDialogResponse>, PortalError> {
+        let future = self.imp.access_dialog(
+            app_id,
+            parent_window,
+            title,
+            subtitle,
+            body,
+            super::options(options)?,
+        );
+        Ok(self.server.upgrade()?.request(&handle, future).await)
+    }
+
+    #[dbus_interface(property, name = "version")]
+    fn version(&self) -> u32 {
+        1
+    }
+}
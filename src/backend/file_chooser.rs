@@ -0,0 +1,143 @@
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use zbus::dbus_interface;
+use zvariant::OwnedObjectPath;
+
+use super::{Options, Response, WeakServer};
+use crate::{
+    desktop::file_chooser::{OpenFileOptions, SaveFileOptions, SaveFilesOptions, SelectedFiles},
+    PortalError,
+};
+
+/// The backend side of the
+/// [`FileChooserProxy`](crate::desktop::file_chooser::FileChooserProxy).
+///
+/// Implementation of the DBus interface: [`org.freedesktop.impl.portal.FileChooser`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.impl.portal.FileChooser).
+#[doc(alias = "org.freedesktop.impl.portal.FileChooser")]
+pub trait FileChooserImpl: Send + Sync {
+    /// Presents a file chooser dialog to the user to open one or more files.
+    ///
+    /// # Arguments
+    ///
+    /// * `app_id` - App id of the application.
+    /// * `parent_window` - Identifier for the application window.
+    /// * `title` - Title for the file chooser dialog.
+    /// * `options` - The options requested by the application.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`OpenFile`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-impl-portal-FileChooser.OpenFile).
+    #[doc(alias = "OpenFile")]
+    fn open_file(
+        &self,
+        app_id: String,
+        parent_window: String,
+        title: String,
+        options: OpenFileOptions,
+    ) -> BoxFuture<'_, Response<SelectedFiles>>;
+
+    /// Presents a file chooser dialog to the user to save a file.
+    ///
+    /// # Arguments
+    ///
+    /// * `app_id` - App id of the application.
+    /// * `parent_window` - Identifier for the application window.
+    /// * `title` - Title for the file chooser dialog.
+    /// * `options` - The options requested by the application.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`SaveFile`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-impl-portal-FileChooser.SaveFile).
+    #[doc(alias = "SaveFile")]
+    fn save_file(
+        &self,
+        app_id: String,
+        parent_window: String,
+        title: String,
+        options: SaveFileOptions,
+    ) -> BoxFuture<'_, Response<SelectedFiles>>;
+
+    /// Asks for a folder as a location to save one or more files.
+    ///
+    /// # Arguments
+    ///
+    /// * `app_id` - App id of the application.
+    /// * `parent_window` - Identifier for the application window.
+    /// * `title` - Title for the file chooser dialog.
+    /// * `options` - The options requested by the application.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`SaveFiles`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-impl-portal-FileChooser.SaveFiles).
+    #[doc(alias = "SaveFiles")]
+    fn save_files(
+        &self,
+        app_id: String,
+        parent_window: String,
+        title: String,
+        options: SaveFilesOptions,
+    ) -> BoxFuture<'_, Response<SelectedFiles>>;
+}
+
+/// The `org.freedesktop.impl.portal.FileChooser` object.
+pub(crate) struct FileChooserInterface {
+    imp: Arc<dyn FileChooserImpl>,
+    server: WeakServer,
+}
+
+impl FileChooserInterface {
+    pub(crate) fn new(imp: Arc<dyn FileChooserImpl>, server: WeakServer) -> Self {
+        Self { imp, server }
+    }
+}
+
+#[dbus_interface(name = "org.freedesktop.impl.portal.FileChooser")]
+impl FileChooserInterface {
+    async fn open_file(
+        &self,
+        handle: OwnedObjectPath,
+        app_id: String,
+        parent_window: String,
+        title: String,
+        options: Options,
+    ) -> Result<Response<SelectedFiles>, PortalError> {
+        let future = self
+            .imp
+            .open_file(app_id, parent_window, title, super::options(options)?);
+        Ok(self.server.upgrade()?.request(&handle, future).await)
+    }
+
+    async fn save_file(
+        &self,
+        handle: OwnedObjectPath,
+        app_id: String,
+        parent_window: String,
+        title: String,
+        options: Options,
+    ) -> Result<Response<SelectedFiles>, PortalError> {
+        let future = self
+            .imp
+            .save_file(app_id, parent_window, title, super::options(options)?);
+        Ok(self.server.upgrade()?.request(&handle, future).await)
+    }
+
+    async fn save_files(
+        &self,
+        handle: OwnedObjectPath,
+        app_id: String,
+        parent_window: String,
+        title: String,
+        options: Options,
+    ) -> Result<Response<SelectedFiles>, PortalError> {
+        let future = self
+            .imp
+            .save_files(app_id, parent_window, title, super::options(options)?);
+        Ok(self.server.upgrade()?.request(&handle, future).await)
+    }
+
+    #[dbus_interface(property, name = "version")]
+    fn version(&self) -> u32 {
+        1
+    }
+}
@@ -0,0 +1,196 @@
+use std::sync::Arc;
+
+use enumflags2::BitFlags;
+use futures::future::BoxFuture;
+use zbus::dbus_interface;
+use zvariant::{ObjectPath, OwnedObjectPath};
+
+use super::{Options, Server, SessionKind, WeakServer, PATH};
+use crate::{
+    desktop::{
+        inhibit::{InhibitFlags, SessionState, State},
+        ResponseError,
+    },
+    Error, PortalError,
+};
+
+pub(crate) const INTERFACE: &str = "org.freedesktop.impl.portal.Inhibit";
+
+/// The backend side of the
+/// [`InhibitProxy`](crate::desktop::inhibit::InhibitProxy).
+///
+/// Implementation of the DBus interface: [`org.freedesktop.impl.portal.Inhibit`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.impl.portal.Inhibit).
+#[doc(alias = "org.freedesktop.impl.portal.Inhibit")]
+pub trait InhibitImpl: Send + Sync {
+    /// Inhibits session status changes.
+    ///
+    /// The inhibition lasts until the frontend closes the request at `handle`,
+    /// which calls [`release()`][`InhibitImpl::release`].
+    ///
+    /// # Arguments
+    ///
+    /// * `handle` - Object path of the request.
+    /// * `app_id` - App id of the application.
+    /// * `window` - Identifier for the window.
+    /// * `flags` - The changes to inhibit.
+    /// * `reason` - User-visible reason for the inhibition.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`Inhibit`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-impl-portal-Inhibit.Inhibit).
+    #[doc(alias = "Inhibit")]
+    fn inhibit(
+        &self,
+        handle: OwnedObjectPath,
+        app_id: String,
+        window: String,
+        flags: BitFlags<InhibitFlags>,
+        reason: Option<String>,
+    ) -> BoxFuture<'_, ()>;
+
+    /// Releases an inhibition made with [`inhibit()`][`InhibitImpl::inhibit`].
+    ///
+    /// # Arguments
+    ///
+    /// * `handle` - Object path of the inhibition request.
+    fn release(&self, handle: OwnedObjectPath) -> BoxFuture<'_, ()>;
+
+    /// Creates a monitoring session.
+    ///
+    /// The session is tracked by the [`Server`] once the creation is
+    /// successful, use [`Server::state_changed`] to notify it.
+    ///
+    /// # Arguments
+    ///
+    /// * `session_handle` - Object path for the created session.
+    /// * `app_id` - App id of the application.
+    /// * `window` - Identifier for the window.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`CreateMonitor`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-impl-portal-Inhibit.CreateMonitor).
+    #[doc(alias = "CreateMonitor")]
+    fn create_monitor(
+        &self,
+        session_handle: OwnedObjectPath,
+        app_id: String,
+        window: String,
+    ) -> BoxFuture<'_, Result<(), ResponseError>>;
+
+    /// Acknowledges that the application received a
+    /// [`SessionState::QueryEnd`] state.
+    ///
+    /// # Arguments
+    ///
+    /// * `session_handle` - Object path of the monitoring session.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`QueryEndResponse`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-impl-portal-Inhibit.QueryEndResponse).
+    #[doc(alias = "QueryEndResponse")]
+    fn query_end_response(&self, session_handle: OwnedObjectPath) -> BoxFuture<'_, ()>;
+
+    /// Called once a session created by
+    /// [`create_monitor()`][`InhibitImpl::create_monitor`] is closed,
+    /// either by the frontend or with [`Server::close_session`].
+    fn close_session(&self, _session_handle: OwnedObjectPath) -> BoxFuture<'_, ()> {
+        Box::pin(async {})
+    }
+}
+
+impl Server {
+    /// Notify a monitoring session that the session state changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `session_handle` - Object path of the monitoring session.
+    /// * `screensaver_active` - Whether the screensaver is active.
+    /// * `session_state` - The new session state.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`StateChanged`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-signal-org-freedesktop-impl-portal-Inhibit.StateChanged).
+    #[doc(alias = "StateChanged")]
+    pub async fn state_changed(
+        &self,
+        session_handle: &ObjectPath<'_>,
+        screensaver_active: bool,
+        session_state: SessionState,
+    ) -> Result<(), Error> {
+        let state = State {
+            screensaver_active,
+            session_state,
+        };
+        self.emit_signal(PATH, INTERFACE, "StateChanged", &(session_handle, state))
+            .await
+    }
+}
+
+/// The `org.freedesktop.impl.portal.Inhibit` object.
+pub(crate) struct InhibitInterface {
+    imp: Arc<dyn InhibitImpl>,
+    server: WeakServer,
+}
+
+impl InhibitInterface {
+    pub(crate) fn new(imp: Arc<dyn InhibitImpl>, server: WeakServer) -> Self {
+        Self { imp, server }
+    }
+}
+
+#[dbus_interface(name = "org.freedesktop.impl.portal.Inhibit")]
+impl InhibitInterface {
+    async fn inhibit(
+        &self,
+        handle: OwnedObjectPath,
+        app_id: String,
+        window: String,
+        flags: BitFlags<InhibitFlags>,
+        options: Options,
+    ) -> Result<(), PortalError> {
+        let server = self.server.upgrade()?;
+        let reason = options
+            .get("reason")
+            .and_then(|reason| reason.downcast_ref::<str>())
+            .map(ToString::to_string);
+        self.imp
+            .inhibit(handle.clone(), app_id, window, flags, reason)
+            .await;
+        server.add_inhibition(&handle).await;
+        Ok(())
+    }
+
+    async fn create_monitor(
+        &self,
+        _handle: OwnedObjectPath,
+        session_handle: OwnedObjectPath,
+        app_id: String,
+        window: String,
+    ) -> Result<u32, PortalError> {
+        let server = self.server.upgrade()?;
+        let response = self
+            .imp
+            .create_monitor(session_handle.clone(), app_id, window)
+            .await;
+        let code = match response {
+            Ok(()) => {
+                server
+                    .add_session(&session_handle, SessionKind::Inhibit)
+                    .await;
+                0
+            }
+            Err(ResponseError::Cancelled) => 1,
+            Err(ResponseError::Other(_)) => 2,
+        };
+        Ok(code)
+    }
+
+    async fn query_end_response(&self, session_handle: OwnedObjectPath) {
+        self.imp.query_end_response(session_handle).await;
+    }
+
+    #[dbus_interface(property, name = "version")]
+    fn version(&self) -> u32 {
+        3
+    }
+}
@@ -0,0 +1,536 @@
+//! # Examples
+//!
+//! A backend implementing the screenshot portal.
+//!
+//! ```rust,no_run
+//! use ashpd::backend::{screenshot::ScreenshotImpl, Response, Server};
+//! use ashpd::desktop::screenshot::{Color, PickColorOptions, Screenshot, ScreenshotOptions};
+//! use futures::future::BoxFuture;
+//!
+//! struct Screenshooter;
+//!
+//! impl ScreenshotImpl for Screenshooter {
+//!     fn screenshot(
+//!         &self,
+//!         _app_id: String,
+//!         _parent_window: String,
+//!         _options: ScreenshotOptions,
+//!     ) -> BoxFuture<'_, Response<Screenshot>> {
+//!         Box::pin(async { Response::Ok(Screenshot::new("file:///tmp/screenshot.png")) })
+//!     }
+//!
+//!     fn pick_color(
+//!         &self,
+//!         _app_id: String,
+//!         _parent_window: String,
+//!         _options: PickColorOptions,
+//!     ) -> BoxFuture<'_, Response<Color>> {
+//!         Box::pin(async { Response::Ok(Color::new(1.0, 1.0, 1.0)) })
+//!     }
+//! }
+//!
+//! async fn run() -> ashpd::Result<()> {
+//!     // The connection is expected to own the bus name declared in the
+//!     // backend's `.portal` file.
+//!     let connection = zbus::azync::Connection::session().await?;
+//!     let server = Server::builder(&connection)
+//!         .screenshot(Screenshooter)
+//!         .build();
+//!     server.run().await
+//! }
+//! ```
+
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+    fmt::{self, Debug},
+    future::Future,
+    sync::{Arc, Mutex, Weak},
+};
+
+use futures::{
+    future::{AbortHandle, Abortable},
+    StreamExt,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use zbus::{dbus_interface, Interface, Message};
+use zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Value};
+
+pub use crate::desktop::request::Response;
+use crate::{
+    desktop::{HandleToken, ResponseError},
+    Error, PortalError,
+};
+
+pub(crate) const PATH: &str = "/org/freedesktop/portal/desktop";
+
+/// A vardict of options or results.
+pub type Options = HashMap<String, OwnedValue>;
+
+/// Request access to resources.
+pub mod access;
+/// Open/save file(s) chooser.
+pub mod file_chooser;
+/// Inhibit the session from ending or idling.
+pub mod inhibit;
+/// Display notifications.
+pub mod notification;
+/// Let applications remote control the desktop.
+pub mod remote_desktop;
+/// Screen cast sessions backed by PipeWire streams.
+pub mod screencast;
+/// Take a screenshot or pick a color.
+pub mod screenshot;
+/// Expose the desktop settings.
+pub mod settings;
+
+use self::{
+    access::AccessImpl, file_chooser::FileChooserImpl, inhibit::InhibitImpl,
+    notification::NotificationImpl, remote_desktop::RemoteDesktopImpl, screencast::ScreenCastImpl,
+    screenshot::ScreenshotImpl, settings::SettingsImpl,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// The interface that created a session.
+pub(crate) enum SessionKind {
+    Inhibit,
+    RemoteDesktop,
+    ScreenCast,
+}
+
+#[derive(Default)]
+struct Interfaces {
+    access: Option<Arc<dyn AccessImpl>>,
+    file_chooser: Option<Arc<dyn FileChooserImpl>>,
+    inhibit: Option<Arc<dyn InhibitImpl>>,
+    notification: Option<Arc<dyn NotificationImpl>>,
+    remote_desktop: Option<Arc<dyn RemoteDesktopImpl>>,
+    screencast: Option<Arc<dyn ScreenCastImpl>>,
+    screenshot: Option<Arc<dyn ScreenshotImpl>>,
+    settings: Option<Arc<dyn SettingsImpl>>,
+}
+
+struct ServerInner {
+    connection: zbus::azync::Connection,
+    interfaces: Interfaces,
+    /// The pending requests, by handle.
+    requests: Mutex<HashMap<String, AbortHandle>>,
+    /// The active inhibitions, by request handle.
+    inhibitions: Mutex<HashSet<String>>,
+    /// The open sessions, by session handle.
+    sessions: Mutex<HashMap<String, SessionKind>>,
+}
+
+/// Builds a [`Server`] out of the implemented interfaces.
+pub struct ServerBuilder {
+    connection: zbus::azync::Connection,
+    interfaces: Interfaces,
+}
+
+impl ServerBuilder {
+    /// Serves `org.freedesktop.impl.portal.Access`.
+    pub fn access<I: AccessImpl + 'static>(mut self, imp: I) -> Self {
+        self.interfaces.access = Some(Arc::new(imp));
+        self
+    }
+
+    /// Serves `org.freedesktop.impl.portal.FileChooser`.
+    pub fn file_chooser<I: FileChooserImpl + 'static>(mut self, imp: I) -> Self {
+        self.interfaces.file_chooser = Some(Arc::new(imp));
+        self
+    }
+
+    /// Serves `org.freedesktop.impl.portal.Inhibit`.
+    pub fn inhibit<I: InhibitImpl + 'static>(mut self, imp: I) -> Self {
+        self.interfaces.inhibit = Some(Arc::new(imp));
+        self
+    }
+
+    /// Serves `org.freedesktop.impl.portal.Notification`.
+    pub fn notification<I: NotificationImpl + 'static>(mut self, imp: I) -> Self {
+        self.interfaces.notification = Some(Arc::new(imp));
+        self
+    }
+
+    /// Serves `org.freedesktop.impl.portal.RemoteDesktop`.
+    pub fn remote_desktop<I: RemoteDesktopImpl + 'static>(mut self, imp: I) -> Self {
+        self.interfaces.remote_desktop = Some(Arc::new(imp));
+        self
+    }
+
+    /// Serves `org.freedesktop.impl.portal.ScreenCast`.
+    pub fn screencast<I: ScreenCastImpl + 'static>(mut self, imp: I) -> Self {
+        self.interfaces.screencast = Some(Arc::new(imp));
+        self
+    }
+
+    /// Serves `org.freedesktop.impl.portal.Screenshot`.
+    pub fn screenshot<I: ScreenshotImpl + 'static>(mut self, imp: I) -> Self {
+        self.interfaces.screenshot = Some(Arc::new(imp));
+        self
+    }
+
+    /// Serves `org.freedesktop.impl.portal.Settings`.
+    pub fn settings<I: SettingsImpl + 'static>(mut self, imp: I) -> Self {
+        self.interfaces.settings = Some(Arc::new(imp));
+        self
+    }
+
+    /// Create the [`Server`].
+    pub fn build(self) -> Server {
+        Server(Arc::new(ServerInner {
+            connection: self.connection,
+            interfaces: self.interfaces,
+            requests: Default::default(),
+            inhibitions: Default::default(),
+            sessions: Default::default(),
+        }))
+    }
+}
+
+impl Debug for ServerBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ServerBuilder").finish()
+    }
+}
+
+/// Serves the implemented `org.freedesktop.impl.portal.*` interfaces on
+/// `/org/freedesktop/portal/desktop` with the [`zbus::ObjectServer`] of the
+/// connection.
+///
+/// The server keeps track of the Request and Session objects the frontend
+/// refers to: closing a request drops the future of the matching pending
+/// call, and closing a session notifies the interface that created it.
+///
+/// Method calls are processed concurrently, so a long running interaction
+/// doesn't block the other ones.
+#[derive(Clone)]
+pub struct Server(Arc<ServerInner>);
+
+impl Server {
+    /// Create a [`ServerBuilder`] serving on `connection`.
+    pub fn builder(connection: &zbus::azync::Connection) -> ServerBuilder {
+        ServerBuilder {
+            connection: connection.clone(),
+            interfaces: Default::default(),
+        }
+    }
+
+    /// Get a reference to the underlying connection.
+    pub fn connection(&self) -> &zbus::azync::Connection {
+        &self.0.connection
+    }
+
+    /// Register the implemented interfaces on the object server of the
+    /// connection and serve them until the connection is closed.
+    ///
+    /// The calls are answered by the object server: the interfaces that
+    /// aren't implemented reply with an `UnknownInterface` error and a call
+    /// that fails to be handled, for example because of a malformed body, is
+    /// answered with a DBus error while the server keeps serving.
+    pub async fn run(&self) -> Result<(), Error> {
+        self.serve().await?;
+        let mut messages = self.0.connection.clone();
+        while let Some(message) = messages.next().await {
+            if let Err(err) = message {
+                tracing::warn!("Backend failed to receive a message: {}", err);
+            }
+        }
+        Ok(())
+    }
+
+    async fn serve(&self) -> Result<(), Error> {
+        let object_server = self.0.connection.object_server();
+        let interfaces = &self.0.interfaces;
+        if let Some(imp) = &interfaces.access {
+            let iface = access::AccessInterface::new(imp.clone(), self.downgrade());
+            object_server.at(PATH, iface).await?;
+        }
+        if let Some(imp) = &interfaces.file_chooser {
+            let iface = file_chooser::FileChooserInterface::new(imp.clone(), self.downgrade());
+            object_server.at(PATH, iface).await?;
+        }
+        if let Some(imp) = &interfaces.inhibit {
+            let iface = inhibit::InhibitInterface::new(imp.clone(), self.downgrade());
+            object_server.at(PATH, iface).await?;
+        }
+        if let Some(imp) = &interfaces.notification {
+            let iface = notification::NotificationInterface::new(imp.clone());
+            object_server.at(PATH, iface).await?;
+        }
+        if let Some(imp) = &interfaces.remote_desktop {
+            let iface = remote_desktop::RemoteDesktopInterface::new(imp.clone(), self.downgrade());
+            object_server.at(PATH, iface).await?;
+        }
+        if let Some(imp) = &interfaces.screencast {
+            let iface = screencast::ScreenCastInterface::new(imp.clone(), self.downgrade());
+            object_server.at(PATH, iface).await?;
+        }
+        if let Some(imp) = &interfaces.screenshot {
+            let iface = screenshot::ScreenshotInterface::new(imp.clone(), self.downgrade());
+            object_server.at(PATH, iface).await?;
+        }
+        if let Some(imp) = &interfaces.settings {
+            let iface = settings::SettingsInterface::new(imp.clone());
+            object_server.at(PATH, iface).await?;
+        }
+        Ok(())
+    }
+
+    /// Close a session from the backend side.
+    ///
+    /// The interface that created the session is notified and the `Closed`
+    /// signal is emitted on the session object.
+    ///
+    /// # Arguments
+    ///
+    /// * `session_handle` - The session object path.
+    pub async fn close_session(&self, session_handle: &ObjectPath<'_>) -> Result<(), Error> {
+        self.session_closed(session_handle.as_str()).await;
+        self.emit_signal(
+            session_handle.as_str(),
+            "org.freedesktop.impl.portal.Session",
+            "Closed",
+            &(),
+        )
+        .await
+    }
+
+    fn downgrade(&self) -> WeakServer {
+        WeakServer(Arc::downgrade(&self.0))
+    }
+
+    /// Run `future` as the pending call of the Request object at `handle`.
+    ///
+    /// If the frontend closes the request before `future` completes, it is
+    /// dropped and the call is answered as cancelled.
+    pub(crate) async fn request<T, F>(&self, handle: &ObjectPath<'_>, future: F) -> Response<T>
+    where
+        F: Future<Output = Response<T>>,
+        T: for<'de> serde::Deserialize<'de> + zvariant::Type,
+    {
+        let (abort_handle, registration) = AbortHandle::new_pair();
+        self.0
+            .requests
+            .lock()
+            .unwrap()
+            .insert(handle.to_string(), abort_handle);
+        self.serve_object(handle, RequestInterface::new(handle, self.downgrade()))
+            .await;
+        let response = Abortable::new(future, registration)
+            .await
+            .unwrap_or(Response::Err(ResponseError::Cancelled));
+        self.0.requests.lock().unwrap().remove(handle.as_str());
+        self.remove_object::<RequestInterface>(handle.as_str())
+            .await;
+        response
+    }
+
+    pub(crate) async fn add_inhibition(&self, handle: &ObjectPath<'_>) {
+        self.0
+            .inhibitions
+            .lock()
+            .unwrap()
+            .insert(handle.to_string());
+        self.serve_object(handle, RequestInterface::new(handle, self.downgrade()))
+            .await;
+    }
+
+    pub(crate) async fn add_session(&self, session_handle: &ObjectPath<'_>, kind: SessionKind) {
+        self.0
+            .sessions
+            .lock()
+            .unwrap()
+            .insert(session_handle.to_string(), kind);
+        self.serve_object(
+            session_handle,
+            SessionInterface::new(session_handle, self.downgrade()),
+        )
+        .await;
+    }
+
+    async fn close_request(&self, handle: &str) {
+        let pending = self.0.requests.lock().unwrap().remove(handle);
+        if let Some(abort_handle) = pending {
+            tracing::info!("Request {} was closed", handle);
+            // The Request object is removed once the pending call is dropped.
+            abort_handle.abort();
+            return;
+        }
+        let inhibition = self.0.inhibitions.lock().unwrap().remove(handle);
+        if inhibition {
+            self.remove_object::<RequestInterface>(handle).await;
+            if let (Some(imp), Ok(handle)) = (
+                self.0.interfaces.inhibit.as_deref(),
+                OwnedObjectPath::try_from(handle),
+            ) {
+                imp.release(handle).await;
+            }
+        }
+    }
+
+    async fn session_closed(&self, session_handle: &str) {
+        let kind = self.0.sessions.lock().unwrap().remove(session_handle);
+        let session_handle = match (kind, OwnedObjectPath::try_from(session_handle)) {
+            (Some(_), Ok(session_handle)) => session_handle,
+            _ => return,
+        };
+        tracing::info!("Session {} was closed", session_handle.as_str());
+        self.remove_object::<SessionInterface>(session_handle.as_str())
+            .await;
+        let interfaces = &self.0.interfaces;
+        match kind {
+            Some(SessionKind::Inhibit) => {
+                if let Some(imp) = interfaces.inhibit.as_deref() {
+                    imp.close_session(session_handle).await;
+                }
+            }
+            Some(SessionKind::RemoteDesktop) => {
+                if let Some(imp) = interfaces.remote_desktop.as_deref() {
+                    imp.close_session(session_handle).await;
+                }
+            }
+            Some(SessionKind::ScreenCast) => {
+                if let Some(imp) = interfaces.screencast.as_deref() {
+                    imp.close_session(session_handle).await;
+                }
+            }
+            None => (),
+        }
+    }
+
+    /// Serve `iface` at `path`, the failure is only logged as the call
+    /// referring to the object is still answered.
+    async fn serve_object<I: Interface>(&self, path: &ObjectPath<'_>, iface: I) {
+        let object_server = self.0.connection.object_server();
+        if let Err(err) = object_server.at(path.clone(), iface).await {
+            tracing::warn!("Backend failed to serve {}: {}", path.as_str(), err);
+        }
+    }
+
+    async fn remove_object<I: Interface>(&self, path: &str) {
+        let object_server = self.0.connection.object_server();
+        if let Err(err) = object_server.remove::<I, _>(path).await {
+            tracing::warn!("Backend failed to remove {}: {}", path, err);
+        }
+    }
+
+    pub(crate) async fn emit_signal<B>(
+        &self,
+        path: &str,
+        interface: &str,
+        signal_name: &str,
+        body: &B,
+    ) -> Result<(), Error>
+    where
+        B: Serialize + zvariant::Type,
+    {
+        let message = Message::signal(
+            None::<&str>,
+            None::<&str>,
+            path,
+            interface,
+            signal_name,
+            body,
+        )?;
+        tracing::info!("Backend emitting signal '{}.{}'", interface, signal_name);
+        self.0.connection.send_message(message).await?;
+        Ok(())
+    }
+}
+
+impl Debug for Server {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Server")
+            .field("requests", &self.0.requests.lock().unwrap().len())
+            .field("sessions", &self.0.sessions.lock().unwrap())
+            .finish()
+    }
+}
+
+/// A reference to the [`Server`] held by the served objects, which are owned
+/// by the connection the server holds.
+#[derive(Clone)]
+pub(crate) struct WeakServer(Weak<ServerInner>);
+
+impl WeakServer {
+    pub(crate) fn upgrade(&self) -> Result<Server, PortalError> {
+        self.0
+            .upgrade()
+            .map(Server)
+            .ok_or_else(|| PortalError::Failed("The backend server was dropped".to_string()))
+    }
+}
+
+/// The `org.freedesktop.impl.portal.Request` object of a pending call or of
+/// an inhibition.
+struct RequestInterface {
+    handle: OwnedObjectPath,
+    server: WeakServer,
+}
+
+impl RequestInterface {
+    fn new(handle: &ObjectPath<'_>, server: WeakServer) -> Self {
+        Self {
+            handle: handle.clone().into(),
+            server,
+        }
+    }
+}
+
+#[dbus_interface(name = "org.freedesktop.impl.portal.Request")]
+impl RequestInterface {
+    /// Closes the request, the pending call is answered as cancelled.
+    async fn close(&self) -> Result<(), PortalError> {
+        self.server
+            .upgrade()?
+            .close_request(self.handle.as_str())
+            .await;
+        Ok(())
+    }
+}
+
+/// The `org.freedesktop.impl.portal.Session` object of an open session.
+struct SessionInterface {
+    handle: OwnedObjectPath,
+    server: WeakServer,
+}
+
+impl SessionInterface {
+    fn new(handle: &ObjectPath<'_>, server: WeakServer) -> Self {
+        Self {
+            handle: handle.clone().into(),
+            server,
+        }
+    }
+}
+
+#[dbus_interface(name = "org.freedesktop.impl.portal.Session")]
+impl SessionInterface {
+    /// Closes the session, the interface that created it is notified.
+    async fn close(&self) -> Result<(), PortalError> {
+        self.server
+            .upgrade()?
+            .session_closed(self.handle.as_str())
+            .await;
+        Ok(())
+    }
+}
+
+/// Convert the options forwarded by the frontend into one of the client-side
+/// option types.
+///
+/// The frontend consumes the `handle_token` before forwarding the options, a
+/// placeholder is put back so that the types can be deserialized.
+pub(crate) fn options<T>(mut options: Options) -> Result<T, PortalError>
+where
+    T: DeserializeOwned + zvariant::Type,
+{
+    options
+        .entry("handle_token".to_string())
+        .or_insert_with(|| Value::from(HandleToken::default().to_string()).into());
+    let ctxt = zvariant::EncodingContext::<byteorder::LE>::new_dbus(0);
+    zvariant::to_bytes(ctxt, &options)
+        .and_then(|bytes| zvariant::from_slice(&bytes, ctxt))
+        .map_err(|err| PortalError::InvalidArgument(err.to_string()))
+}
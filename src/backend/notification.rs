@@ -0,0 +1,111 @@
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use zbus::dbus_interface;
+use zvariant::OwnedValue;
+
+use super::{Server, PATH};
+use crate::{desktop::notification::Notification, Error};
+
+pub(crate) const INTERFACE: &str = "org.freedesktop.impl.portal.Notification";
+
+/// The backend side of the
+/// [`NotificationProxy`](crate::desktop::notification::NotificationProxy).
+///
+/// Implementation of the DBus interface: [`org.freedesktop.impl.portal.Notification`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.impl.portal.Notification).
+#[doc(alias = "org.freedesktop.impl.portal.Notification")]
+pub trait NotificationImpl: Send + Sync {
+    /// Sends a notification.
+    ///
+    /// If a notification with the same `id` was sent by the application, it
+    /// must be replaced.
+    ///
+    /// # Arguments
+    ///
+    /// * `app_id` - App id of the application.
+    /// * `id` - Application-provided ID for this notification.
+    /// * `notification` - The notification.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`AddNotification`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-impl-portal-Notification.AddNotification).
+    #[doc(alias = "AddNotification")]
+    fn add_notification(
+        &self,
+        app_id: String,
+        id: String,
+        notification: Notification,
+    ) -> BoxFuture<'_, ()>;
+
+    /// Withdraws a notification.
+    ///
+    /// # Arguments
+    ///
+    /// * `app_id` - App id of the application.
+    /// * `id` - Application-provided ID for this notification.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`RemoveNotification`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-impl-portal-Notification.RemoveNotification).
+    #[doc(alias = "RemoveNotification")]
+    fn remove_notification(&self, app_id: String, id: String) -> BoxFuture<'_, ()>;
+}
+
+impl Server {
+    /// Notify the frontend that the user activated an action of a
+    /// notification.
+    ///
+    /// # Arguments
+    ///
+    /// * `app_id` - App id of the application.
+    /// * `id` - Application-provided ID for the notification.
+    /// * `action` - The name of the activated action.
+    /// * `parameter` - The target parameter of the action.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`ActionInvoked`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-signal-org-freedesktop-impl-portal-Notification.ActionInvoked).
+    #[doc(alias = "ActionInvoked")]
+    pub async fn action_invoked(
+        &self,
+        app_id: &str,
+        id: &str,
+        action: &str,
+        parameter: Vec<OwnedValue>,
+    ) -> Result<(), Error> {
+        self.emit_signal(
+            PATH,
+            INTERFACE,
+            "ActionInvoked",
+            &(app_id, id, action, parameter),
+        )
+        .await
+    }
+}
+
+/// The `org.freedesktop.impl.portal.Notification` object.
+pub(crate) struct NotificationInterface {
+    imp: Arc<dyn NotificationImpl>,
+}
+
+impl NotificationInterface {
+    pub(crate) fn new(imp: Arc<dyn NotificationImpl>) -> Self {
+        Self { imp }
+    }
+}
+
+#[dbus_interface(name = "org.freedesktop.impl.portal.Notification")]
+impl NotificationInterface {
+    async fn add_notification(&self, app_id: String, id: String, notification: Notification) {
+        self.imp.add_notification(app_id, id, notification).await;
+    }
+
+    async fn remove_notification(&self, app_id: String, id: String) {
+        self.imp.remove_notification(app_id, id).await;
+    }
+
+    #[dbus_interface(property, name = "version")]
+    fn version(&self) -> u32 {
+        1
+    }
+}
@@ -0,0 +1,390 @@
+use std::sync::Arc;
+
+use enumflags2::BitFlags;
+use futures::future::BoxFuture;
+use zbus::dbus_interface;
+use zvariant::OwnedObjectPath;
+
+use super::{Options, Response, SessionKind, WeakServer};
+use crate::{
+    desktop::remote_desktop::{
+        Axis, DeviceType, KeyState, SelectDevicesOptions, SelectedDevices, StartRemoteOptions,
+    },
+    PortalError,
+};
+
+#[derive(Debug)]
+/// An input event an application asked to send to a remote desktop session.
+pub enum RemoteDesktopEvent {
+    /// The pointer moved by a relative motion.
+    PointerMotion {
+        /// Relative movement on the x axis.
+        dx: f64,
+        /// Relative movement on the y axis.
+        dy: f64,
+    },
+    /// The pointer moved to an absolute position in a stream.
+    PointerMotionAbsolute {
+        /// The PipeWire stream node the coordinate is relative to.
+        stream: u32,
+        /// Pointer motion x coordinate.
+        x: f64,
+        /// Pointer motion y coordinate.
+        y: f64,
+    },
+    /// A pointer button was pressed or released.
+    PointerButton {
+        /// The pointer button, as a evdev button code.
+        button: i32,
+        /// The new state of the button.
+        state: KeyState,
+    },
+    /// The pointer scrolled smoothly.
+    PointerAxis {
+        /// Relative axis movement on the x axis.
+        dx: f64,
+        /// Relative axis movement on the y axis.
+        dy: f64,
+        /// Whether this is the last event of a scroll sequence.
+        finish: bool,
+    },
+    /// The pointer scrolled by discrete steps.
+    PointerAxisDiscrete {
+        /// The axis that was scrolled.
+        axis: Axis,
+        /// The number of steps scrolled.
+        steps: i32,
+    },
+    /// A keyboard key was pressed or released.
+    KeyboardKeycode {
+        /// The keyboard code, as a evdev key code.
+        keycode: i32,
+        /// The new state of the key.
+        state: KeyState,
+    },
+    /// A keyboard symbol was pressed or released.
+    KeyboardKeysym {
+        /// The keyboard symbol.
+        keysym: i32,
+        /// The new state of the key.
+        state: KeyState,
+    },
+    /// A new touch point went down.
+    TouchDown {
+        /// The PipeWire stream node the coordinate is relative to.
+        stream: u32,
+        /// The touch slot.
+        slot: u32,
+        /// Touch down x coordinate.
+        x: f64,
+        /// Touch down y coordinate.
+        y: f64,
+    },
+    /// A touch point moved.
+    TouchMotion {
+        /// The PipeWire stream node the coordinate is relative to.
+        stream: u32,
+        /// The touch slot.
+        slot: u32,
+        /// Touch motion x coordinate.
+        x: f64,
+        /// Touch motion y coordinate.
+        y: f64,
+    },
+    /// A touch point went up.
+    TouchUp {
+        /// The touch slot.
+        slot: u32,
+    },
+}
+
+/// The backend side of the
+/// [`RemoteDesktopProxy`](crate::desktop::remote_desktop::RemoteDesktopProxy).
+///
+/// Implementation of the DBus interface: [`org.freedesktop.impl.portal.RemoteDesktop`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.impl.portal.RemoteDesktop).
+#[doc(alias = "org.freedesktop.impl.portal.RemoteDesktop")]
+pub trait RemoteDesktopImpl: Send + Sync {
+    /// Available source types.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`AvailableDeviceTypes`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-property-org-freedesktop-impl-portal-RemoteDesktop.AvailableDeviceTypes).
+    #[doc(alias = "AvailableDeviceTypes")]
+    fn available_device_types(&self) -> BitFlags<DeviceType>;
+
+    /// Create a remote desktop session.
+    ///
+    /// The session is tracked by the [`Server`](super::Server) once the
+    /// response is successful.
+    ///
+    /// # Arguments
+    ///
+    /// * `session_handle` - Object path for the created session.
+    /// * `app_id` - App id of the application.
+    /// * `options` - Vardict with optional further information.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`CreateSession`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-impl-portal-RemoteDesktop.CreateSession).
+    #[doc(alias = "CreateSession")]
+    fn create_session(
+        &self,
+        session_handle: OwnedObjectPath,
+        app_id: String,
+        options: Options,
+    ) -> BoxFuture<'_, Response<Options>>;
+
+    /// Select input devices to remote control.
+    ///
+    /// # Arguments
+    ///
+    /// * `session_handle` - Object path of the session.
+    /// * `app_id` - App id of the application.
+    /// * `options` - The options requested by the application.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`SelectDevices`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-impl-portal-RemoteDesktop.SelectDevices).
+    #[doc(alias = "SelectDevices")]
+    fn select_devices(
+        &self,
+        session_handle: OwnedObjectPath,
+        app_id: String,
+        options: SelectDevicesOptions,
+    ) -> BoxFuture<'_, Response<Options>>;
+
+    /// Start the remote desktop session.
+    ///
+    /// # Arguments
+    ///
+    /// * `session_handle` - Object path of the session.
+    /// * `app_id` - App id of the application.
+    /// * `parent_window` - Identifier for the application window.
+    /// * `options` - The options requested by the application.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`Start`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-impl-portal-RemoteDesktop.Start).
+    #[doc(alias = "Start")]
+    fn start(
+        &self,
+        session_handle: OwnedObjectPath,
+        app_id: String,
+        parent_window: String,
+        options: StartRemoteOptions,
+    ) -> BoxFuture<'_, Response<SelectedDevices>>;
+
+    /// Handle one of the `Notify*` input events of a started session.
+    ///
+    /// # Arguments
+    ///
+    /// * `session_handle` - Object path of the session.
+    /// * `event` - The input event.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`NotifyPointerMotion`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-impl-portal-RemoteDesktop.NotifyPointerMotion)
+    /// and the following methods.
+    fn notify(
+        &self,
+        session_handle: OwnedObjectPath,
+        event: RemoteDesktopEvent,
+    ) -> BoxFuture<'_, ()>;
+
+    /// Called once a session created by
+    /// [`create_session()`][`RemoteDesktopImpl::create_session`] is closed,
+    /// either by the frontend or with
+    /// [`Server::close_session`](super::Server::close_session).
+    fn close_session(&self, _session_handle: OwnedObjectPath) -> BoxFuture<'_, ()> {
+        Box::pin(async {})
+    }
+}
+
+/// The `org.freedesktop.impl.portal.RemoteDesktop` object.
+pub(crate) struct RemoteDesktopInterface {
+    imp: Arc<dyn RemoteDesktopImpl>,
+    server: WeakServer,
+}
+
+impl RemoteDesktopInterface {
+    pub(crate) fn new(imp: Arc<dyn RemoteDesktopImpl>, server: WeakServer) -> Self {
+        Self { imp, server }
+    }
+}
+
+#[dbus_interface(name = "org.freedesktop.impl.portal.RemoteDesktop")]
+impl RemoteDesktopInterface {
+    async fn create_session(
+        &self,
+        handle: OwnedObjectPath,
+        session_handle: OwnedObjectPath,
+        app_id: String,
+        options: Options,
+    ) -> Result<Response<Options>, PortalError> {
+        let server = self.server.upgrade()?;
+        let future = self
+            .imp
+            .create_session(session_handle.clone(), app_id, options);
+        let response = server.request(&handle, future).await;
+        if let Response::Ok(_) = response {
+            server
+                .add_session(&session_handle, SessionKind::RemoteDesktop)
+                .await;
+        }
+        Ok(response)
+    }
+
+    async fn select_devices(
+        &self,
+        handle: OwnedObjectPath,
+        session_handle: OwnedObjectPath,
+        app_id: String,
+        options: Options,
+    ) -> Result<Response<Options>, PortalError> {
+        let future = self
+            .imp
+            .select_devices(session_handle, app_id, super::options(options)?);
+        Ok(self.server.upgrade()?.request(&handle, future).await)
+    }
+
+    async fn start(
+        &self,
+        handle: OwnedObjectPath,
+        session_handle: OwnedObjectPath,
+        app_id: String,
+        parent_window: String,
+        options: Options,
+    ) -> Result<Response<SelectedDevices>, PortalError> {
+        let future = self.imp.start(
+            session_handle,
+            app_id,
+            parent_window,
+            super::options(options)?,
+        );
+        Ok(self.server.upgrade()?.request(&handle, future).await)
+    }
+
+    async fn notify_pointer_motion(
+        &self,
+        session_handle: OwnedObjectPath,
+        _options: Options,
+        dx: f64,
+        dy: f64,
+    ) {
+        let event = RemoteDesktopEvent::PointerMotion { dx, dy };
+        self.imp.notify(session_handle, event).await;
+    }
+
+    async fn notify_pointer_motion_absolute(
+        &self,
+        session_handle: OwnedObjectPath,
+        _options: Options,
+        stream: u32,
+        x: f64,
+        y: f64,
+    ) {
+        let event = RemoteDesktopEvent::PointerMotionAbsolute { stream, x, y };
+        self.imp.notify(session_handle, event).await;
+    }
+
+    async fn notify_pointer_button(
+        &self,
+        session_handle: OwnedObjectPath,
+        _options: Options,
+        button: i32,
+        state: KeyState,
+    ) {
+        let event = RemoteDesktopEvent::PointerButton { button, state };
+        self.imp.notify(session_handle, event).await;
+    }
+
+    async fn notify_pointer_axis(
+        &self,
+        session_handle: OwnedObjectPath,
+        options: Options,
+        dx: f64,
+        dy: f64,
+    ) {
+        let finish = options
+            .get("finish")
+            .and_then(|finish| finish.downcast_ref::<bool>())
+            .copied()
+            .unwrap_or(false);
+        let event = RemoteDesktopEvent::PointerAxis { dx, dy, finish };
+        self.imp.notify(session_handle, event).await;
+    }
+
+    async fn notify_pointer_axis_discrete(
+        &self,
+        session_handle: OwnedObjectPath,
+        _options: Options,
+        axis: Axis,
+        steps: i32,
+    ) {
+        let event = RemoteDesktopEvent::PointerAxisDiscrete { axis, steps };
+        self.imp.notify(session_handle, event).await;
+    }
+
+    async fn notify_keyboard_keycode(
+        &self,
+        session_handle: OwnedObjectPath,
+        _options: Options,
+        keycode: i32,
+        state: KeyState,
+    ) {
+        let event = RemoteDesktopEvent::KeyboardKeycode { keycode, state };
+        self.imp.notify(session_handle, event).await;
+    }
+
+    async fn notify_keyboard_keysym(
+        &self,
+        session_handle: OwnedObjectPath,
+        _options: Options,
+        keysym: i32,
+        state: KeyState,
+    ) {
+        let event = RemoteDesktopEvent::KeyboardKeysym { keysym, state };
+        self.imp.notify(session_handle, event).await;
+    }
+
+    async fn notify_touch_down(
+        &self,
+        session_handle: OwnedObjectPath,
+        _options: Options,
+        stream: u32,
+        slot: u32,
+        x: f64,
+        y: f64,
+    ) {
+        let event = RemoteDesktopEvent::TouchDown { stream, slot, x, y };
+        self.imp.notify(session_handle, event).await;
+    }
+
+    async fn notify_touch_motion(
+        &self,
+        session_handle: OwnedObjectPath,
+        _options: Options,
+        stream: u32,
+        slot: u32,
+        x: f64,
+        y: f64,
+    ) {
+        let event = RemoteDesktopEvent::TouchMotion { stream, slot, x, y };
+        self.imp.notify(session_handle, event).await;
+    }
+
+    async fn notify_touch_up(&self, session_handle: OwnedObjectPath, _options: Options, slot: u32) {
+        let event = RemoteDesktopEvent::TouchUp { slot };
+        self.imp.notify(session_handle, event).await;
+    }
+
+    #[dbus_interface(property)]
+    fn available_device_types(&self) -> u32 {
+        self.imp.available_device_types().bits()
+    }
+
+    #[dbus_interface(property, name = "version")]
+    fn version(&self) -> u32 {
+        2
+    }
+}
@@ -0,0 +1,187 @@
+use std::sync::Arc;
+
+use enumflags2::BitFlags;
+use futures::future::BoxFuture;
+use zbus::dbus_interface;
+use zvariant::OwnedObjectPath;
+
+use super::{Options, Response, SessionKind, WeakServer};
+use crate::{
+    desktop::screencast::{
+        CursorMode, SelectSourcesOptions, SourceType, StartCastOptions, Streams,
+    },
+    PortalError,
+};
+
+/// The backend side of the
+/// [`ScreenCastProxy`](crate::desktop::screencast::ScreenCastProxy).
+///
+/// Implementation of the DBus interface: [`org.freedesktop.impl.portal.ScreenCast`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.impl.portal.ScreenCast).
+#[doc(alias = "org.freedesktop.impl.portal.ScreenCast")]
+pub trait ScreenCastImpl: Send + Sync {
+    /// Available source types.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`AvailableSourceTypes`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-property-org-freedesktop-impl-portal-ScreenCast.AvailableSourceTypes).
+    #[doc(alias = "AvailableSourceTypes")]
+    fn available_source_types(&self) -> BitFlags<SourceType>;
+
+    /// Available cursor mode.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`AvailableCursorModes`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-property-org-freedesktop-impl-portal-ScreenCast.AvailableCursorModes).
+    #[doc(alias = "AvailableCursorModes")]
+    fn available_cursor_modes(&self) -> BitFlags<CursorMode>;
+
+    /// Create a screen cast session.
+    ///
+    /// The session is tracked by the [`Server`](super::Server) once the
+    /// response is successful.
+    ///
+    /// # Arguments
+    ///
+    /// * `session_handle` - Object path for the created session.
+    /// * `app_id` - App id of the application.
+    /// * `options` - Vardict with optional further information.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`CreateSession`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-impl-portal-ScreenCast.CreateSession).
+    #[doc(alias = "CreateSession")]
+    fn create_session(
+        &self,
+        session_handle: OwnedObjectPath,
+        app_id: String,
+        options: Options,
+    ) -> BoxFuture<'_, Response<Options>>;
+
+    /// Configure what the screen cast session should record.
+    ///
+    /// # Arguments
+    ///
+    /// * `session_handle` - Object path of the session.
+    /// * `app_id` - App id of the application.
+    /// * `options` - The options requested by the application.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`SelectSources`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-impl-portal-ScreenCast.SelectSources).
+    #[doc(alias = "SelectSources")]
+    fn select_sources(
+        &self,
+        session_handle: OwnedObjectPath,
+        app_id: String,
+        options: SelectSourcesOptions,
+    ) -> BoxFuture<'_, Response<Options>>;
+
+    /// Start the screen cast session.
+    ///
+    /// # Arguments
+    ///
+    /// * `session_handle` - Object path of the session.
+    /// * `app_id` - App id of the application.
+    /// * `parent_window` - Identifier for the application window.
+    /// * `options` - The options requested by the application.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`Start`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-impl-portal-ScreenCast.Start).
+    #[doc(alias = "Start")]
+    fn start(
+        &self,
+        session_handle: OwnedObjectPath,
+        app_id: String,
+        parent_window: String,
+        options: StartCastOptions,
+    ) -> BoxFuture<'_, Response<Streams>>;
+
+    /// Called once a session created by
+    /// [`create_session()`][`ScreenCastImpl::create_session`] is closed,
+    /// either by the frontend or with
+    /// [`Server::close_session`](super::Server::close_session).
+    fn close_session(&self, _session_handle: OwnedObjectPath) -> BoxFuture<'_, ()> {
+        Box::pin(async {})
+    }
+}
+
+/// The `org.freedesktop.impl.portal.ScreenCast` object.
+pub(crate) struct ScreenCastInterface {
+    imp: Arc<dyn ScreenCastImpl>,
+    server: WeakServer,
+}
+
+impl ScreenCastInterface {
+    pub(crate) fn new(imp: Arc<dyn ScreenCastImpl>, server: WeakServer) -> Self {
+        Self { imp, server }
+    }
+}
+
+#[dbus_interface(name = "org.freedesktop.impl.portal.ScreenCast")]
+impl ScreenCastInterface {
+    async fn create_session(
+        &self,
+        handle: OwnedObjectPath,
+        session_handle: OwnedObjectPath,
+        app_id: String,
+        options: Options,
+    ) -> Result<Response<Options>, PortalError> {
+        let server = self.server.upgrade()?;
+        let future = self
+            .imp
+            .create_session(session_handle.clone(), app_id, options);
+        let response = server.request(&handle, future).await;
+        if let Response::Ok(_) = response {
+            server
+                .add_session(&session_handle, SessionKind::ScreenCast)
+                .await;
+        }
+        Ok(response)
+    }
+
+    async fn select_sources(
+        &self,
+        handle: OwnedObjectPath,
+        session_handle: OwnedObjectPath,
+        app_id: String,
+        options: Options,
+    ) -> Result<Response<Options>, PortalError> {
+        let future = self
+            .imp
+            .select_sources(session_handle, app_id, super::options(options)?);
+        Ok(self.server.upgrade()?.request(&handle, future).await)
+    }
+
+    async fn start(
+        &self,
+        handle: OwnedObjectPath,
+        session_handle: OwnedObjectPath,
+        app_id: String,
+        parent_window: String,
+        options: Options,
+    ) -> Result<Response<Streams>, PortalError> {
+        let future = self.imp.start(
+            session_handle,
+            app_id,
+            parent_window,
+            super::options(options)?,
+        );
+        Ok(self.server.upgrade()?.request(&handle, future).await)
+    }
+
+    #[dbus_interface(property)]
+    fn available_source_types(&self) -> u32 {
+        self.imp.available_source_types().bits()
+    }
+
+    #[dbus_interface(property)]
+    fn available_cursor_modes(&self) -> u32 {
+        self.imp.available_cursor_modes().bits()
+    }
+
+    #[dbus_interface(property, name = "version")]
+    fn version(&self) -> u32 {
+        4
+    }
+}
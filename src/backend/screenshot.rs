@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
+use zbus::dbus_interface;
+use zvariant::OwnedObjectPath;
+
+use super::{Options, Response, WeakServer};
+use crate::{
+    desktop::screenshot::{Color, PickColorOptions, Screenshot, ScreenshotOptions},
+    PortalError,
+};
+
+/// The backend side of the
+/// [`ScreenshotProxy`](crate::desktop::screenshot::ScreenshotProxy).
+///
+/// Implementation of the DBus interface: [`org.freedesktop.impl.portal.Screenshot`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.impl.portal.Screenshot).
+#[doc(alias = "org.freedesktop.impl.portal.Screenshot")]
+pub trait ScreenshotImpl: Send + Sync {
+    /// Takes a screenshot.
+    ///
+    /// # Arguments
+    ///
+    /// * `app_id` - App id of the application.
+    /// * `parent_window` - Identifier for the application window.
+    /// * `options` - The options requested by the application.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`Screenshot`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-impl-portal-Screenshot.Screenshot).
+    #[doc(alias = "Screenshot")]
+    fn screenshot(
+        &self,
+        app_id: String,
+        parent_window: String,
+        options: ScreenshotOptions,
+    ) -> BoxFuture<'_, Response<Screenshot>>;
+
+    /// Obtains the color of a single pixel.
+    ///
+    /// # Arguments
+    ///
+    /// * `app_id` - App id of the application.
+    /// * `parent_window` - Identifier for the application window.
+    /// * `options` - The options requested by the application.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`PickColor`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-impl-portal-Screenshot.PickColor).
+    #[doc(alias = "PickColor")]
+    fn pick_color(
+        &self,
+        app_id: String,
+        parent_window: String,
+        options: PickColorOptions,
+    ) -> BoxFuture<'_, Response<Color>>;
+}
+
+/// The `org.freedesktop.impl.portal.Screenshot` object.
+pub(crate) struct ScreenshotInterface {
+    imp: Arc<dyn ScreenshotImpl>,
+    server: WeakServer,
+}
+
+impl ScreenshotInterface {
+    pub(crate) fn new(imp: Arc<dyn ScreenshotImpl>, server: WeakServer) -> Self {
+        Self { imp, server }
+    }
+}
+
+#[dbus_interface(name = "org.freedesktop.impl.portal.Screenshot")]
+impl ScreenshotInterface {
+    async fn screenshot(
+        &self,
+        handle: OwnedObjectPath,
+        app_id: String,
+        parent_window: String,
+        options: Options,
+    ) -> Result<Response<Screenshot>, PortalError> {
+        let future = self
+            .imp
+            .screenshot(app_id, parent_window, super::options(options)?);
+        Ok(self.server.upgrade()?.request(&handle, future).await)
+    }
+
+    async fn pick_color(
+        &self,
+        handle: OwnedObjectPath,
+        app_id: String,
+        parent_window: String,
+        options: Options,
+    ) -> Result<Response<Color>, PortalError> {
+        let future = self
+            .imp
+            .pick_color(app_id, parent_window, super::options(options)?);
+        Ok(self.server.upgrade()?.request(&handle, future).await)
+    }
+
+    #[dbus_interface(property, name = "version")]
+    fn version(&self) -> u32 {
+        1
+    }
+}
@@ -0,0 +1,98 @@
+use std::{collections::HashMap, sync::Arc};
+
+use futures::future::BoxFuture;
+use zbus::dbus_interface;
+use zvariant::OwnedValue;
+
+use super::{Server, PATH};
+use crate::{desktop::settings::Setting, Error, PortalError};
+
+pub(crate) const INTERFACE: &str = "org.freedesktop.impl.portal.Settings";
+
+/// The backend side of the
+/// [`SettingsProxy`](crate::desktop::settings::SettingsProxy).
+///
+/// Implementation of the DBus interface: [`org.freedesktop.impl.portal.Settings`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.impl.portal.Settings).
+#[doc(alias = "org.freedesktop.impl.portal.Settings")]
+pub trait SettingsImpl: Send + Sync {
+    /// Reads a number of settings.
+    ///
+    /// # Arguments
+    ///
+    /// * `namespaces` - List of namespaces to filter results by, supports
+    ///   a trailing `*` glob. An empty list returns every namespace.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`ReadAll`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-impl-portal-Settings.ReadAll).
+    #[doc(alias = "ReadAll")]
+    fn read_all(
+        &self,
+        namespaces: Vec<String>,
+    ) -> BoxFuture<'_, HashMap<String, HashMap<String, OwnedValue>>>;
+
+    /// Reads a single value.
+    ///
+    /// Returning `None` replies with a `NotFound` error.
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - Namespace to look up key in.
+    /// * `key` - The key to get.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`Read`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-impl-portal-Settings.Read).
+    #[doc(alias = "Read")]
+    fn read(&self, namespace: String, key: String) -> BoxFuture<'_, Option<OwnedValue>>;
+}
+
+impl Server {
+    /// Notify the frontend that a setting changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `setting` - The changed setting along with its new value.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`SettingChanged`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-signal-org-freedesktop-impl-portal-Settings.SettingChanged).
+    #[doc(alias = "SettingChanged")]
+    pub async fn setting_changed(&self, setting: Setting) -> Result<(), Error> {
+        self.emit_signal(PATH, INTERFACE, "SettingChanged", &setting)
+            .await
+    }
+}
+
+/// The `org.freedesktop.impl.portal.Settings` object.
+pub(crate) struct SettingsInterface {
+    imp: Arc<dyn SettingsImpl>,
+}
+
+impl SettingsInterface {
+    pub(crate) fn new(imp: Arc<dyn SettingsImpl>) -> Self {
+        Self { imp }
+    }
+}
+
+#[dbus_interface(name = "org.freedesktop.impl.portal.Settings")]
+impl SettingsInterface {
+    async fn read_all(
+        &self,
+        namespaces: Vec<String>,
+    ) -> HashMap<String, HashMap<String, OwnedValue>> {
+        self.imp.read_all(namespaces).await
+    }
+
+    async fn read(&self, namespace: String, key: String) -> Result<OwnedValue, PortalError> {
+        self.imp
+            .read(namespace, key)
+            .await
+            .ok_or_else(|| PortalError::NotFound("Requested setting not found".to_string()))
+    }
+
+    #[dbus_interface(property, name = "version")]
+    fn version(&self) -> u32 {
+        1
+    }
+}
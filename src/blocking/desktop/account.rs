@@ -0,0 +1,11 @@
+use crate::{desktop::account::AccountProxy as AsyncAccountProxy, WindowIdentifier};
+
+pub use crate::desktop::account::UserInfo;
+
+blocking_proxy! {
+    /// Blocking variant of the
+    /// [`AccountProxy`](crate::desktop::account::AccountProxy).
+    #[doc(alias = "org.freedesktop.portal.Account")]
+    AccountProxy => AsyncAccountProxy;
+    fn user_information(&self, identifier: &WindowIdentifier, reason: &str) -> UserInfo;
+}
@@ -0,0 +1,36 @@
+use serde::Serialize;
+
+use crate::{
+    blocking::block_on, desktop::background::BackgroundProxy as AsyncBackgroundProxy, Error,
+    WindowIdentifier,
+};
+
+pub use crate::desktop::background::Background;
+
+blocking_proxy! {
+    /// Blocking variant of the
+    /// [`BackgroundProxy`](crate::desktop::background::BackgroundProxy).
+    #[doc(alias = "org.freedesktop.portal.Background")]
+    BackgroundProxy => AsyncBackgroundProxy;
+}
+
+impl<'a> BackgroundProxy<'a> {
+    /// Blocking variant of
+    /// [`request_background()`](AsyncBackgroundProxy::request_background).
+    pub fn request_background<S: AsRef<str> + zvariant::Type + Serialize>(
+        &self,
+        identifier: &WindowIdentifier,
+        reason: &str,
+        auto_start: bool,
+        command_line: Option<&[S]>,
+        dbus_activatable: bool,
+    ) -> Result<Background, Error> {
+        block_on(self.0.request_background(
+            identifier,
+            reason,
+            auto_start,
+            command_line,
+            dbus_activatable,
+        ))
+    }
+}
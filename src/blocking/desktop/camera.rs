@@ -0,0 +1,13 @@
+use std::os::unix::prelude::RawFd;
+
+use crate::desktop::camera::CameraProxy as AsyncCameraProxy;
+
+blocking_proxy! {
+    /// Blocking variant of the
+    /// [`CameraProxy`](crate::desktop::camera::CameraProxy).
+    #[doc(alias = "org.freedesktop.portal.Camera")]
+    CameraProxy => AsyncCameraProxy;
+    fn access_camera(&self) -> ();
+    fn open_pipe_wire_remote(&self) -> RawFd;
+    fn is_camera_present(&self) -> bool;
+}
@@ -0,0 +1,11 @@
+use crate::desktop::device::DeviceProxy as AsyncDeviceProxy;
+
+pub use crate::desktop::device::Device;
+
+blocking_proxy! {
+    /// Blocking variant of the
+    /// [`DeviceProxy`](crate::desktop::device::DeviceProxy).
+    #[doc(alias = "org.freedesktop.portal.Device")]
+    DeviceProxy => AsyncDeviceProxy;
+    fn access_device(&self, pid: u32, devices: &[Device]) -> ();
+}
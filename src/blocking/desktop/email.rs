@@ -0,0 +1,11 @@
+use crate::{desktop::email::EmailProxy as AsyncEmailProxy, WindowIdentifier};
+
+pub use crate::desktop::email::Email;
+
+blocking_proxy! {
+    /// Blocking variant of the
+    /// [`EmailProxy`](crate::desktop::email::EmailProxy).
+    #[doc(alias = "org.freedesktop.portal.Email")]
+    EmailProxy => AsyncEmailProxy;
+    fn compose_email(&self, identifier: &WindowIdentifier, email: Email) -> ();
+}
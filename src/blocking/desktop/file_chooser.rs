@@ -0,0 +1,30 @@
+use crate::{desktop::file_chooser::FileChooserProxy as AsyncFileChooserProxy, WindowIdentifier};
+
+pub use crate::desktop::file_chooser::{
+    Choice, FileFilter, OpenFileOptions, SaveFileOptions, SaveFilesOptions, SelectedFiles,
+};
+
+blocking_proxy! {
+    /// Blocking variant of the
+    /// [`FileChooserProxy`](crate::desktop::file_chooser::FileChooserProxy).
+    #[doc(alias = "org.freedesktop.portal.FileChooser")]
+    FileChooserProxy => AsyncFileChooserProxy;
+    fn open_file(
+        &self,
+        identifier: &WindowIdentifier,
+        title: &str,
+        options: OpenFileOptions,
+    ) -> SelectedFiles;
+    fn save_file(
+        &self,
+        identifier: &WindowIdentifier,
+        title: &str,
+        options: SaveFileOptions,
+    ) -> SelectedFiles;
+    fn save_files(
+        &self,
+        identifier: &WindowIdentifier,
+        title: &str,
+        options: SaveFilesOptions,
+    ) -> SelectedFiles;
+}
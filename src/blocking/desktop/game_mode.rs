@@ -0,0 +1,50 @@
+use std::os::unix::prelude::AsRawFd;
+
+use crate::{blocking::block_on, desktop::game_mode::GameModeProxy as AsyncGameModeProxy, Error};
+
+pub use crate::desktop::game_mode::Status;
+
+blocking_proxy! {
+    /// Blocking variant of the
+    /// [`GameModeProxy`](crate::desktop::game_mode::GameModeProxy).
+    #[doc(alias = "org.freedesktop.portal.GameMode")]
+    GameModeProxy => AsyncGameModeProxy;
+    fn query_status(&self, pid: u32) -> Status;
+    fn query_status_by_pid(&self, target: u32, requester: u32) -> Status;
+    fn register_game(&self, pid: u32) -> ();
+    fn register_game_by_pid(&self, target: u32, requester: u32) -> ();
+    fn unregister_game(&self, pid: u32) -> ();
+    fn unregister_game_by_pid(&self, target: u32, requester: u32) -> ();
+}
+
+impl<'a> GameModeProxy<'a> {
+    /// Blocking variant of
+    /// [`query_status_by_pidfd()`](AsyncGameModeProxy::query_status_by_pidfd).
+    pub fn query_status_by_pidfd<F, R>(&self, target: &F, requester: &R) -> Result<Status, Error>
+    where
+        F: AsRawFd,
+        R: AsRawFd,
+    {
+        block_on(self.0.query_status_by_pidfd(target, requester))
+    }
+
+    /// Blocking variant of
+    /// [`register_game_by_pidfd()`](AsyncGameModeProxy::register_game_by_pidfd).
+    pub fn register_game_by_pidfd<F, R>(&self, target: &F, requester: &R) -> Result<(), Error>
+    where
+        F: AsRawFd,
+        R: AsRawFd,
+    {
+        block_on(self.0.register_game_by_pidfd(target, requester))
+    }
+
+    /// Blocking variant of
+    /// [`unregister_game_by_pidfd()`](AsyncGameModeProxy::unregister_game_by_pidfd).
+    pub fn unregister_game_by_pidfd<F, R>(&self, target: &F, requester: &R) -> Result<(), Error>
+    where
+        F: AsRawFd,
+        R: AsRawFd,
+    {
+        block_on(self.0.unregister_game_by_pidfd(target, requester))
+    }
+}
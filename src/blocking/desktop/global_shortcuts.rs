@@ -0,0 +1,51 @@
+use super::SessionProxy;
+use crate::{
+    blocking::block_on,
+    desktop::global_shortcuts::GlobalShortcutsProxy as AsyncGlobalShortcutsProxy, Error,
+    WindowIdentifier,
+};
+
+pub use crate::desktop::global_shortcuts::{
+    NewShortcut, Shortcut, ShortcutEvent, ShortcutsChanged,
+};
+
+blocking_proxy! {
+    /// Blocking variant of the
+    /// [`GlobalShortcutsProxy`](crate::desktop::global_shortcuts::GlobalShortcutsProxy).
+    #[doc(alias = "org.freedesktop.portal.GlobalShortcuts")]
+    GlobalShortcutsProxy => AsyncGlobalShortcutsProxy;
+    fn receive_activated(&self) -> ShortcutEvent;
+    fn receive_deactivated(&self) -> ShortcutEvent;
+    fn receive_shortcuts_changed(&self) -> ShortcutsChanged;
+    iter receive_activated_iter => receive_activated_stream: ShortcutEvent;
+    iter receive_deactivated_iter => receive_deactivated_stream: ShortcutEvent;
+    iter receive_shortcuts_changed_iter => receive_shortcuts_changed_stream: ShortcutsChanged;
+}
+
+impl<'a> GlobalShortcutsProxy<'a> {
+    /// Blocking variant of
+    /// [`create_session()`](AsyncGlobalShortcutsProxy::create_session).
+    pub fn create_session(&self) -> Result<SessionProxy<'a>, Error> {
+        block_on(self.0.create_session()).map(From::from)
+    }
+
+    /// Blocking variant of
+    /// [`bind_shortcuts()`](AsyncGlobalShortcutsProxy::bind_shortcuts).
+    pub fn bind_shortcuts(
+        &self,
+        session: &SessionProxy<'_>,
+        shortcuts: &[NewShortcut],
+        identifier: &WindowIdentifier,
+    ) -> Result<Vec<Shortcut>, Error> {
+        block_on(
+            self.0
+                .bind_shortcuts(session.inner(), shortcuts, identifier),
+        )
+    }
+
+    /// Blocking variant of
+    /// [`list_shortcuts()`](AsyncGlobalShortcutsProxy::list_shortcuts).
+    pub fn list_shortcuts(&self, session: &SessionProxy<'_>) -> Result<Vec<Shortcut>, Error> {
+        block_on(self.0.list_shortcuts(session.inner()))
+    }
+}
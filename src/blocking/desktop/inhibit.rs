@@ -0,0 +1,100 @@
+use std::fmt::Debug;
+
+use enumflags2::BitFlags;
+use zvariant::ObjectPath;
+
+use super::SessionProxy;
+use crate::{
+    blocking::block_on,
+    desktop::inhibit::{InhibitGuard as AsyncInhibitGuard, InhibitProxy as AsyncInhibitProxy},
+    Error, WindowIdentifier,
+};
+
+pub use crate::desktop::inhibit::{InhibitFlags, InhibitState, SessionState};
+
+/// Blocking variant of the
+/// [`InhibitGuard`](crate::desktop::inhibit::InhibitGuard).
+///
+/// The inhibition lasts until released with [`InhibitGuard::release`] or
+/// dropped, the release then runs in the background.
+pub struct InhibitGuard(AsyncInhibitGuard);
+
+impl InhibitGuard {
+    /// The object path of the request backing the inhibition.
+    pub fn path(&self) -> &ObjectPath<'_> {
+        self.0.path()
+    }
+
+    /// Blocking variant of [`release()`](AsyncInhibitGuard::release).
+    pub fn release(self) -> Result<(), Error> {
+        block_on(self.0.release())
+    }
+}
+
+impl From<AsyncInhibitGuard> for InhibitGuard {
+    fn from(guard: AsyncInhibitGuard) -> Self {
+        Self(guard)
+    }
+}
+
+impl Debug for InhibitGuard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("InhibitGuard")
+            .field(&self.path().as_str())
+            .finish()
+    }
+}
+
+blocking_proxy! {
+    /// Blocking variant of the
+    /// [`InhibitProxy`](crate::desktop::inhibit::InhibitProxy).
+    #[doc(alias = "org.freedesktop.portal.Inhibit")]
+    InhibitProxy => AsyncInhibitProxy;
+    fn receive_state_changed(&self) -> InhibitState;
+    iter receive_state_changed_iter => receive_state_changed_stream: InhibitState;
+}
+
+impl<'a> InhibitProxy<'a> {
+    /// Blocking variant of [`inhibit()`](AsyncInhibitProxy::inhibit).
+    pub fn inhibit(
+        &self,
+        identifier: &WindowIdentifier,
+        flags: BitFlags<InhibitFlags>,
+        reason: &str,
+    ) -> Result<InhibitGuard, Error> {
+        block_on(self.0.inhibit(identifier, flags, reason)).map(From::from)
+    }
+
+    /// Blocking variant of
+    /// [`inhibit_while()`](AsyncInhibitProxy::inhibit_while), inhibiting while
+    /// `f` runs.
+    ///
+    /// The output of `f` is returned even if releasing the inhibition fails,
+    /// the failure is logged instead.
+    pub fn inhibit_while<T>(
+        &self,
+        identifier: &WindowIdentifier,
+        flags: BitFlags<InhibitFlags>,
+        reason: &str,
+        f: impl FnOnce() -> T,
+    ) -> Result<T, Error> {
+        let inhibitor = self.inhibit(identifier, flags, reason)?;
+        let output = f();
+        if let Err(err) = inhibitor.release() {
+            tracing::warn!("Failed to release the inhibition: {}", err);
+        }
+        Ok(output)
+    }
+
+    /// Blocking variant of
+    /// [`create_monitor()`](AsyncInhibitProxy::create_monitor).
+    pub fn create_monitor(&self, identifier: &WindowIdentifier) -> Result<SessionProxy<'a>, Error> {
+        block_on(self.0.create_monitor(identifier)).map(From::from)
+    }
+
+    /// Blocking variant of
+    /// [`query_end_response()`](AsyncInhibitProxy::query_end_response).
+    pub fn query_end_response(&self, session: &SessionProxy<'_>) -> Result<(), Error> {
+        block_on(self.0.query_end_response(session.inner()))
+    }
+}
@@ -0,0 +1,42 @@
+use super::SessionProxy;
+use crate::{
+    blocking::block_on, desktop::location::LocationProxy as AsyncLocationProxy, Error,
+    WindowIdentifier,
+};
+
+pub use crate::desktop::location::{Accuracy, Location};
+
+blocking_proxy! {
+    /// Blocking variant of the
+    /// [`LocationProxy`](crate::desktop::location::LocationProxy).
+    #[doc(alias = "org.freedesktop.portal.Location")]
+    LocationProxy => AsyncLocationProxy;
+    fn receive_location_updated(&self) -> Location;
+    iter receive_location_updated_iter => receive_location_updated_stream: Location;
+}
+
+impl<'a> LocationProxy<'a> {
+    /// Blocking variant of
+    /// [`create_session()`](AsyncLocationProxy::create_session).
+    pub fn create_session(
+        &self,
+        distance_threshold: Option<u32>,
+        time_threshold: Option<u32>,
+        accuracy: Option<Accuracy>,
+    ) -> Result<SessionProxy<'a>, Error> {
+        block_on(
+            self.0
+                .create_session(distance_threshold, time_threshold, accuracy),
+        )
+        .map(From::from)
+    }
+
+    /// Blocking variant of [`start()`](AsyncLocationProxy::start).
+    pub fn start(
+        &self,
+        session: &SessionProxy<'_>,
+        identifier: &WindowIdentifier,
+    ) -> Result<(), Error> {
+        block_on(self.0.start(session.inner(), identifier))
+    }
+}
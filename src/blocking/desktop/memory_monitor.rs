@@ -0,0 +1,10 @@
+use crate::desktop::memory_monitor::MemoryMonitorProxy as AsyncMemoryMonitorProxy;
+
+blocking_proxy! {
+    /// Blocking variant of the
+    /// [`MemoryMonitorProxy`](crate::desktop::memory_monitor::MemoryMonitorProxy).
+    #[doc(alias = "org.freedesktop.portal.MemoryMonitor")]
+    MemoryMonitorProxy => AsyncMemoryMonitorProxy;
+    fn receive_low_memory_warning(&self) -> i32;
+    iter receive_low_memory_warning_iter => receive_low_memory_warning_stream: i32;
+}
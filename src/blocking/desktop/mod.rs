@@ -0,0 +1,148 @@
+use std::fmt::Debug;
+
+use crate::{desktop::SessionProxy as AsyncSessionProxy, Error};
+
+pub use crate::desktop::ResponseError;
+
+/// Blocking variant of the
+/// [`SessionProxy`](crate::desktop::SessionProxy).
+///
+/// A session is created by the portals supporting it, such as
+/// [`ScreenCastProxy::create_session`](screencast::ScreenCastProxy::create_session).
+pub struct SessionProxy<'a>(AsyncSessionProxy<'a>);
+
+impl<'a> SessionProxy<'a> {
+    /// Get a reference to the underlying async proxy.
+    pub fn inner(&self) -> &AsyncSessionProxy<'a> {
+        &self.0
+    }
+
+    /// Blocking variant of
+    /// [`receive_closed()`](AsyncSessionProxy::receive_closed).
+    #[doc(alias = "Closed")]
+    pub fn receive_closed(
+        &self,
+    ) -> Result<std::collections::HashMap<String, zvariant::OwnedValue>, Error> {
+        super::block_on(self.0.receive_closed())
+    }
+
+    /// Blocking variant of
+    /// [`receive_closed_stream()`](AsyncSessionProxy::receive_closed_stream),
+    /// each call to `next()` blocks until the signal is emitted.
+    #[doc(alias = "Closed")]
+    pub fn receive_closed_iter(
+        &self,
+    ) -> Result<
+        impl Iterator<Item = std::collections::HashMap<String, zvariant::OwnedValue>> + '_,
+        Error,
+    > {
+        let stream = super::block_on(self.0.receive_closed_stream())?;
+        Ok(super::iter(stream))
+    }
+
+    /// Blocking variant of [`close()`](AsyncSessionProxy::close).
+    #[doc(alias = "Close")]
+    pub fn close(&self) -> Result<(), Error> {
+        super::block_on(self.0.close())
+    }
+
+    /// Blocking variant of [`closed()`](AsyncSessionProxy::closed).
+    pub fn closed(&self) -> Result<std::collections::HashMap<String, zvariant::OwnedValue>, Error> {
+        super::block_on(self.0.closed())
+    }
+
+    /// Consume the proxy without closing the session, see
+    /// [`into_raw()`](AsyncSessionProxy::into_raw).
+    pub fn into_raw(self) -> zvariant::OwnedObjectPath {
+        self.0.into_raw()
+    }
+}
+
+impl<'a> From<AsyncSessionProxy<'a>> for SessionProxy<'a> {
+    fn from(proxy: AsyncSessionProxy<'a>) -> Self {
+        Self(proxy)
+    }
+}
+
+impl<'a> Debug for SessionProxy<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("SessionProxy")
+            .field(&self.0.inner().path().as_str())
+            .finish()
+    }
+}
+
+/// Request access to the current logged user information such as the id, name
+/// or their avatar uri.
+pub mod account;
+
+/// Request running an application in the background.
+pub mod background;
+
+/// Check if a camera is available, request access to it and open a PipeWire
+/// remote stream.
+pub mod camera;
+
+/// Request access to specific devices such as camera, speakers or microphone.
+pub mod device;
+
+/// Compose an email.
+pub mod email;
+
+/// Open/save file(s) chooser.
+pub mod file_chooser;
+
+/// Enable/disable/query the status of Game Mode.
+pub mod game_mode;
+
+/// Register global shortcuts.
+pub mod global_shortcuts;
+
+/// Inhibit the session from being restarted or the user from logging out.
+pub mod inhibit;
+
+/// Query the user's GPS location.
+pub mod location;
+
+/// Monitor memory level.
+pub mod memory_monitor;
+
+/// Check the status of the network on a user's machine.
+pub mod network_monitor;
+
+/// Send/withdraw notifications.
+pub mod notification;
+
+/// Open a file or a directory.
+pub mod open_uri;
+
+/// Print a document.
+pub mod print;
+
+/// Proxy information.
+pub mod proxy_resolver;
+
+/// Raise the scheduling priority of threads.
+pub mod realtime;
+
+/// Start a remote desktop session and interact with it.
+pub mod remote_desktop;
+
+/// Start a screencast session and get the PipeWire remote of it.
+pub mod screencast;
+
+/// Take a screenshot or pick a color.
+pub mod screenshot;
+
+/// Retrieve a per-application secret used to encrypt confidential data inside
+/// the sandbox.
+pub mod secret;
+
+/// Read & listen to system settings changes.
+pub mod settings;
+
+/// Move a file to the trash.
+pub mod trash;
+
+/// Set a wallpaper on lockscreen, background or both.
+pub mod wallpaper;
@@ -0,0 +1,17 @@
+use crate::desktop::network_monitor::NetworkMonitorProxy as AsyncNetworkMonitorProxy;
+
+pub use crate::desktop::network_monitor::{Connectivity, NetworkStatus};
+
+blocking_proxy! {
+    /// Blocking variant of the
+    /// [`NetworkMonitorProxy`](crate::desktop::network_monitor::NetworkMonitorProxy).
+    #[doc(alias = "org.freedesktop.portal.NetworkMonitor")]
+    NetworkMonitorProxy => AsyncNetworkMonitorProxy;
+    fn can_reach(&self, hostname: &str, port: u32) -> bool;
+    fn is_available(&self) -> bool;
+    fn connectivity(&self) -> Connectivity;
+    fn is_metered(&self) -> bool;
+    fn status(&self) -> NetworkStatus;
+    fn receive_changed(&self) -> ();
+    iter receive_changed_iter => receive_changed_stream: ();
+}
@@ -0,0 +1,14 @@
+use crate::desktop::notification::NotificationProxy as AsyncNotificationProxy;
+
+pub use crate::desktop::notification::{Action, Button, Notification, Priority};
+
+blocking_proxy! {
+    /// Blocking variant of the
+    /// [`NotificationProxy`](crate::desktop::notification::NotificationProxy).
+    #[doc(alias = "org.freedesktop.portal.Notification")]
+    NotificationProxy => AsyncNotificationProxy;
+    fn add_notification(&self, id: &str, notification: Notification) -> ();
+    fn remove_notification(&self, id: &str) -> ();
+    fn receive_action_invoked(&self) -> Action;
+    iter receive_action_invoked_iter => receive_action_invoked_stream: Action;
+}
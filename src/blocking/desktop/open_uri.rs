@@ -0,0 +1,49 @@
+use std::os::unix::prelude::AsRawFd;
+
+use crate::{
+    blocking::block_on, desktop::open_uri::OpenURIProxy as AsyncOpenURIProxy, Error,
+    WindowIdentifier,
+};
+
+blocking_proxy! {
+    /// Blocking variant of the
+    /// [`OpenURIProxy`](crate::desktop::open_uri::OpenURIProxy).
+    #[doc(alias = "org.freedesktop.portal.OpenURI")]
+    OpenURIProxy => AsyncOpenURIProxy;
+    fn open_uri(
+        &self,
+        identifier: &WindowIdentifier,
+        uri: &str,
+        writeable: bool,
+        ask: bool,
+    ) -> ();
+}
+
+impl<'a> OpenURIProxy<'a> {
+    /// Blocking variant of
+    /// [`open_directory()`](AsyncOpenURIProxy::open_directory).
+    pub fn open_directory<F>(
+        &self,
+        identifier: &WindowIdentifier,
+        directory: &F,
+    ) -> Result<(), Error>
+    where
+        F: AsRawFd,
+    {
+        block_on(self.0.open_directory(identifier, directory))
+    }
+
+    /// Blocking variant of [`open_file()`](AsyncOpenURIProxy::open_file).
+    pub fn open_file<F>(
+        &self,
+        identifier: &WindowIdentifier,
+        file: &F,
+        writeable: bool,
+        ask: bool,
+    ) -> Result<(), Error>
+    where
+        F: AsRawFd,
+    {
+        block_on(self.0.open_file(identifier, file, writeable, ask))
+    }
+}
@@ -0,0 +1,39 @@
+use std::os::unix::prelude::AsRawFd;
+
+use crate::{
+    blocking::block_on, desktop::print::PrintProxy as AsyncPrintProxy, Error, WindowIdentifier,
+};
+
+pub use crate::desktop::print::{Orientation, PageSetup, PreparePrint, Quality, Settings};
+
+blocking_proxy! {
+    /// Blocking variant of the
+    /// [`PrintProxy`](crate::desktop::print::PrintProxy).
+    #[doc(alias = "org.freedesktop.portal.Print")]
+    PrintProxy => AsyncPrintProxy;
+    fn prepare_print(
+        &self,
+        identifier: &WindowIdentifier,
+        title: &str,
+        settings: Settings,
+        page_setup: PageSetup,
+        modal: bool,
+    ) -> PreparePrint;
+}
+
+impl<'a> PrintProxy<'a> {
+    /// Blocking variant of [`print()`](AsyncPrintProxy::print).
+    pub fn print<F>(
+        &self,
+        identifier: &WindowIdentifier,
+        title: &str,
+        fd: &F,
+        token: Option<u32>,
+        modal: bool,
+    ) -> Result<(), Error>
+    where
+        F: AsRawFd,
+    {
+        block_on(self.0.print(identifier, title, fd, token, modal))
+    }
+}
@@ -0,0 +1,9 @@
+use crate::desktop::proxy_resolver::ProxyResolverProxy as AsyncProxyResolverProxy;
+
+blocking_proxy! {
+    /// Blocking variant of the
+    /// [`ProxyResolverProxy`](crate::desktop::proxy_resolver::ProxyResolverProxy).
+    #[doc(alias = "org.freedesktop.portal.ProxyResolver")]
+    ProxyResolverProxy => AsyncProxyResolverProxy;
+    fn lookup(&self, uri: &str) -> Vec<String>;
+}
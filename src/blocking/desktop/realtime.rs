@@ -0,0 +1,15 @@
+use crate::desktop::realtime::RealtimeProxy as AsyncRealtimeProxy;
+
+blocking_proxy! {
+    /// Blocking variant of the
+    /// [`RealtimeProxy`](crate::desktop::realtime::RealtimeProxy).
+    #[doc(alias = "org.freedesktop.portal.Realtime")]
+    RealtimeProxy => AsyncRealtimeProxy;
+    fn make_thread_realtime_with_pid(&self, process: u64, thread: u64, priority: u32) -> ();
+    fn make_thread_high_priority_with_pid(&self, process: u64, thread: u64, priority: i32) -> ();
+    fn make_current_thread_realtime(&self, priority: u32) -> ();
+    fn make_current_thread_high_priority(&self, priority: i32) -> ();
+    fn max_realtime_priority(&self) -> i32;
+    fn min_nice_level(&self) -> i32;
+    fn rt_time_usec_max(&self) -> i64;
+}
@@ -0,0 +1,258 @@
+use std::time::Duration;
+
+use enumflags2::BitFlags;
+use zvariant::OwnedFd;
+
+use super::SessionProxy;
+use crate::{
+    blocking::block_on,
+    desktop::remote_desktop::{
+        RemoteDesktopProxy as AsyncRemoteDesktopProxy, VirtualInput as AsyncVirtualInput,
+    },
+    Error, WindowIdentifier,
+};
+
+pub use crate::desktop::remote_desktop::{
+    Axis, DeviceType, KeyState, Keysym, PointerButton, SelectDevicesOptions, SelectedDevices,
+    StartRemoteOptions,
+};
+
+blocking_proxy! {
+    /// Blocking variant of the
+    /// [`RemoteDesktopProxy`](crate::desktop::remote_desktop::RemoteDesktopProxy).
+    #[doc(alias = "org.freedesktop.portal.RemoteDesktop")]
+    RemoteDesktopProxy => AsyncRemoteDesktopProxy;
+    fn available_device_types(&self) -> BitFlags<DeviceType>;
+}
+
+impl<'a> RemoteDesktopProxy<'a> {
+    /// Blocking variant of
+    /// [`create_session()`](AsyncRemoteDesktopProxy::create_session).
+    pub fn create_session(&self) -> Result<SessionProxy<'a>, Error> {
+        block_on(self.0.create_session()).map(From::from)
+    }
+
+    /// Blocking variant of
+    /// [`select_devices()`](AsyncRemoteDesktopProxy::select_devices).
+    pub fn select_devices(
+        &self,
+        session: &SessionProxy<'_>,
+        options: SelectDevicesOptions,
+    ) -> Result<(), Error> {
+        block_on(self.0.select_devices(session.inner(), options))
+    }
+
+    /// Blocking variant of [`start()`](AsyncRemoteDesktopProxy::start).
+    pub fn start(
+        &self,
+        session: &SessionProxy<'_>,
+        identifier: &WindowIdentifier,
+    ) -> Result<SelectedDevices, Error> {
+        block_on(self.0.start(session.inner(), identifier))
+    }
+
+    /// Blocking variant of
+    /// [`connect_to_eis()`](AsyncRemoteDesktopProxy::connect_to_eis).
+    pub fn connect_to_eis(&self, session: &SessionProxy<'_>) -> Result<OwnedFd, Error> {
+        block_on(self.0.connect_to_eis(session.inner()))
+    }
+
+    /// Blocking variant of
+    /// [`notify_keyboard_keycode()`](AsyncRemoteDesktopProxy::notify_keyboard_keycode).
+    pub fn notify_keyboard_keycode(
+        &self,
+        session: &SessionProxy<'_>,
+        keycode: i32,
+        state: KeyState,
+    ) -> Result<(), Error> {
+        block_on(
+            self.0
+                .notify_keyboard_keycode(session.inner(), keycode, state),
+        )
+    }
+
+    /// Blocking variant of
+    /// [`notify_keyboard_keysym()`](AsyncRemoteDesktopProxy::notify_keyboard_keysym).
+    pub fn notify_keyboard_keysym(
+        &self,
+        session: &SessionProxy<'_>,
+        keysym: i32,
+        state: KeyState,
+    ) -> Result<(), Error> {
+        block_on(
+            self.0
+                .notify_keyboard_keysym(session.inner(), keysym, state),
+        )
+    }
+
+    /// Blocking variant of
+    /// [`notify_touch_up()`](AsyncRemoteDesktopProxy::notify_touch_up).
+    pub fn notify_touch_up(&self, session: &SessionProxy<'_>, slot: u32) -> Result<(), Error> {
+        block_on(self.0.notify_touch_up(session.inner(), slot))
+    }
+
+    /// Blocking variant of
+    /// [`notify_touch_down()`](AsyncRemoteDesktopProxy::notify_touch_down).
+    pub fn notify_touch_down(
+        &self,
+        session: &SessionProxy<'_>,
+        stream: u32,
+        slot: u32,
+        x: f64,
+        y: f64,
+    ) -> Result<(), Error> {
+        block_on(
+            self.0
+                .notify_touch_down(session.inner(), stream, slot, x, y),
+        )
+    }
+
+    /// Blocking variant of
+    /// [`notify_touch_motion()`](AsyncRemoteDesktopProxy::notify_touch_motion).
+    pub fn notify_touch_motion(
+        &self,
+        session: &SessionProxy<'_>,
+        stream: u32,
+        slot: u32,
+        x: f64,
+        y: f64,
+    ) -> Result<(), Error> {
+        block_on(
+            self.0
+                .notify_touch_motion(session.inner(), stream, slot, x, y),
+        )
+    }
+
+    /// Blocking variant of
+    /// [`notify_pointer_motion_absolute()`](AsyncRemoteDesktopProxy::notify_pointer_motion_absolute).
+    pub fn notify_pointer_motion_absolute(
+        &self,
+        session: &SessionProxy<'_>,
+        stream: u32,
+        x: f64,
+        y: f64,
+    ) -> Result<(), Error> {
+        block_on(
+            self.0
+                .notify_pointer_motion_absolute(session.inner(), stream, x, y),
+        )
+    }
+
+    /// Blocking variant of
+    /// [`notify_pointer_motion()`](AsyncRemoteDesktopProxy::notify_pointer_motion).
+    pub fn notify_pointer_motion(
+        &self,
+        session: &SessionProxy<'_>,
+        dx: f64,
+        dy: f64,
+    ) -> Result<(), Error> {
+        block_on(self.0.notify_pointer_motion(session.inner(), dx, dy))
+    }
+
+    /// Blocking variant of
+    /// [`notify_pointer_button()`](AsyncRemoteDesktopProxy::notify_pointer_button).
+    pub fn notify_pointer_button(
+        &self,
+        session: &SessionProxy<'_>,
+        button: i32,
+        state: KeyState,
+    ) -> Result<(), Error> {
+        block_on(self.0.notify_pointer_button(session.inner(), button, state))
+    }
+
+    /// Blocking variant of
+    /// [`notify_pointer_axis_discrete()`](AsyncRemoteDesktopProxy::notify_pointer_axis_discrete).
+    pub fn notify_pointer_axis_discrete(
+        &self,
+        session: &SessionProxy<'_>,
+        axis: Axis,
+        steps: i32,
+    ) -> Result<(), Error> {
+        block_on(
+            self.0
+                .notify_pointer_axis_discrete(session.inner(), axis, steps),
+        )
+    }
+
+    /// Blocking variant of
+    /// [`notify_pointer_axis()`](AsyncRemoteDesktopProxy::notify_pointer_axis).
+    pub fn notify_pointer_axis(
+        &self,
+        session: &SessionProxy<'_>,
+        dx: f64,
+        dy: f64,
+    ) -> Result<(), Error> {
+        block_on(self.0.notify_pointer_axis(session.inner(), dx, dy))
+    }
+}
+
+/// Blocking variant of the
+/// [`VirtualInput`](crate::desktop::remote_desktop::VirtualInput) helper.
+#[derive(Debug)]
+pub struct VirtualInput<'a, 'p>(AsyncVirtualInput<'a, 'p>);
+
+impl<'a, 'p> VirtualInput<'a, 'p> {
+    /// Create a new helper sending the events to `session`.
+    ///
+    /// The delay between events defaults to 20 milliseconds.
+    pub fn new(proxy: &'a RemoteDesktopProxy<'p>, session: &'a SessionProxy<'p>) -> Self {
+        Self(AsyncVirtualInput::new(proxy.inner(), session.inner()))
+    }
+
+    /// Sets the delay to wait between two events.
+    pub fn delay(self, delay: Duration) -> Self {
+        Self(self.0.delay(delay))
+    }
+
+    /// Blocking variant of [`key()`](AsyncVirtualInput::key).
+    pub fn key(&self, keysym: Keysym) -> Result<(), Error> {
+        block_on(self.0.key(keysym))
+    }
+
+    /// Blocking variant of [`type_text()`](AsyncVirtualInput::type_text).
+    pub fn type_text(&self, text: &str) -> Result<(), Error> {
+        block_on(self.0.type_text(text))
+    }
+
+    /// Blocking variant of [`chord()`](AsyncVirtualInput::chord).
+    pub fn chord(&self, keys: &[Keysym]) -> Result<(), Error> {
+        block_on(self.0.chord(keys))
+    }
+
+    /// Blocking variant of [`click()`](AsyncVirtualInput::click).
+    pub fn click(&self, button: PointerButton) -> Result<(), Error> {
+        block_on(self.0.click(button))
+    }
+
+    /// Blocking variant of [`double_click()`](AsyncVirtualInput::double_click).
+    pub fn double_click(&self, button: PointerButton) -> Result<(), Error> {
+        block_on(self.0.double_click(button))
+    }
+
+    /// Blocking variant of [`drag()`](AsyncVirtualInput::drag).
+    pub fn drag(&self, button: PointerButton, dx: f64, dy: f64, steps: u32) -> Result<(), Error> {
+        block_on(self.0.drag(button, dx, dy, steps))
+    }
+
+    /// Blocking variant of [`drag_absolute()`](AsyncVirtualInput::drag_absolute).
+    pub fn drag_absolute(
+        &self,
+        button: PointerButton,
+        stream: u32,
+        from: (f64, f64),
+        to: (f64, f64),
+        steps: u32,
+    ) -> Result<(), Error> {
+        block_on(self.0.drag_absolute(button, stream, from, to, steps))
+    }
+
+    /// Blocking variant of [`scroll()`](AsyncVirtualInput::scroll).
+    pub fn scroll(&self, axis: Axis, steps: i32) -> Result<(), Error> {
+        block_on(self.0.scroll(axis, steps))
+    }
+
+    /// Blocking variant of [`smooth_scroll()`](AsyncVirtualInput::smooth_scroll).
+    pub fn smooth_scroll(&self, dx: f64, dy: f64, steps: u32) -> Result<(), Error> {
+        block_on(self.0.smooth_scroll(dx, dy, steps))
+    }
+}
@@ -0,0 +1,55 @@
+use std::os::unix::prelude::RawFd;
+
+use enumflags2::BitFlags;
+
+use super::SessionProxy;
+use crate::{
+    blocking::block_on, desktop::screencast::ScreenCastProxy as AsyncScreenCastProxy, Error,
+    WindowIdentifier,
+};
+
+pub use crate::desktop::screencast::{
+    CursorMode, PersistMode, SelectSourcesOptions, SourceType, StartCastOptions, Stream, Streams,
+};
+
+blocking_proxy! {
+    /// Blocking variant of the
+    /// [`ScreenCastProxy`](crate::desktop::screencast::ScreenCastProxy).
+    #[doc(alias = "org.freedesktop.portal.ScreenCast")]
+    ScreenCastProxy => AsyncScreenCastProxy;
+    fn available_cursor_modes(&self) -> BitFlags<CursorMode>;
+    fn available_source_types(&self) -> BitFlags<SourceType>;
+}
+
+impl<'a> ScreenCastProxy<'a> {
+    /// Blocking variant of
+    /// [`create_session()`](AsyncScreenCastProxy::create_session).
+    pub fn create_session(&self) -> Result<SessionProxy<'a>, Error> {
+        block_on(self.0.create_session()).map(From::from)
+    }
+
+    /// Blocking variant of
+    /// [`open_pipe_wire_remote()`](AsyncScreenCastProxy::open_pipe_wire_remote).
+    pub fn open_pipe_wire_remote(&self, session: &SessionProxy<'_>) -> Result<RawFd, Error> {
+        block_on(self.0.open_pipe_wire_remote(session.inner()))
+    }
+
+    /// Blocking variant of
+    /// [`select_sources()`](AsyncScreenCastProxy::select_sources).
+    pub fn select_sources(
+        &self,
+        session: &SessionProxy<'_>,
+        options: SelectSourcesOptions,
+    ) -> Result<(), Error> {
+        block_on(self.0.select_sources(session.inner(), options))
+    }
+
+    /// Blocking variant of [`start()`](AsyncScreenCastProxy::start).
+    pub fn start(
+        &self,
+        session: &SessionProxy<'_>,
+        identifier: &WindowIdentifier,
+    ) -> Result<Streams, Error> {
+        block_on(self.0.start(session.inner(), identifier))
+    }
+}
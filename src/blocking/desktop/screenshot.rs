@@ -0,0 +1,12 @@
+use crate::{desktop::screenshot::ScreenshotProxy as AsyncScreenshotProxy, WindowIdentifier};
+
+pub use crate::desktop::screenshot::{Color, PickColorOptions, Screenshot, ScreenshotOptions};
+
+blocking_proxy! {
+    /// Blocking variant of the
+    /// [`ScreenshotProxy`](crate::desktop::screenshot::ScreenshotProxy).
+    #[doc(alias = "org.freedesktop.portal.Screenshot")]
+    ScreenshotProxy => AsyncScreenshotProxy;
+    fn pick_color(&self, identifier: &WindowIdentifier) -> Color;
+    fn screenshot(&self, identifier: &WindowIdentifier, interactive: bool, modal: bool) -> String;
+}
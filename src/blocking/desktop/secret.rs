@@ -0,0 +1,22 @@
+use std::os::unix::prelude::AsRawFd;
+
+use crate::{blocking::block_on, desktop::secret::SecretProxy as AsyncSecretProxy, Error};
+
+blocking_proxy! {
+    /// Blocking variant of the
+    /// [`SecretProxy`](crate::desktop::secret::SecretProxy).
+    #[doc(alias = "org.freedesktop.portal.Secret")]
+    SecretProxy => AsyncSecretProxy;
+}
+
+impl<'a> SecretProxy<'a> {
+    /// Blocking variant of
+    /// [`retrieve_secret()`](AsyncSecretProxy::retrieve_secret).
+    pub fn retrieve_secret<F: AsRawFd>(
+        &self,
+        fd: &F,
+        token: Option<&str>,
+    ) -> Result<String, Error> {
+        block_on(self.0.retrieve_secret(fd, token))
+    }
+}
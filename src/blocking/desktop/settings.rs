@@ -0,0 +1,35 @@
+use std::{collections::HashMap, convert::TryFrom, fmt::Debug};
+
+use serde::{de::DeserializeOwned, Serialize};
+use zvariant::OwnedValue;
+
+use crate::{blocking::block_on, desktop::settings::SettingsProxy as AsyncSettingsProxy, Error};
+
+pub use crate::desktop::settings::{Namespace, Setting};
+
+blocking_proxy! {
+    /// Blocking variant of the
+    /// [`SettingsProxy`](crate::desktop::settings::SettingsProxy).
+    #[doc(alias = "org.freedesktop.portal.Settings")]
+    SettingsProxy => AsyncSettingsProxy;
+    fn receive_setting_changed(&self) -> Setting;
+    iter receive_setting_changed_iter => receive_setting_changed_stream: Setting;
+}
+
+impl<'a> SettingsProxy<'a> {
+    /// Blocking variant of [`read_all()`](AsyncSettingsProxy::read_all).
+    pub fn read_all<S: AsRef<str> + zvariant::Type + Serialize + Debug>(
+        &self,
+        namespaces: &[S],
+    ) -> Result<HashMap<String, Namespace>, Error> {
+        block_on(self.0.read_all(namespaces))
+    }
+
+    /// Blocking variant of [`read()`](AsyncSettingsProxy::read).
+    pub fn read<T>(&self, namespace: &str, key: &str) -> Result<T, Error>
+    where
+        T: TryFrom<OwnedValue> + DeserializeOwned + zvariant::Type,
+    {
+        block_on(self.0.read(namespace, key))
+    }
+}
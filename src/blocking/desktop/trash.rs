@@ -0,0 +1,20 @@
+use std::os::unix::prelude::AsRawFd;
+
+use crate::{blocking::block_on, desktop::trash::TrashProxy as AsyncTrashProxy, Error};
+
+blocking_proxy! {
+    /// Blocking variant of the
+    /// [`TrashProxy`](crate::desktop::trash::TrashProxy).
+    #[doc(alias = "org.freedesktop.portal.Trash")]
+    TrashProxy => AsyncTrashProxy;
+}
+
+impl<'a> TrashProxy<'a> {
+    /// Blocking variant of [`trash_file()`](AsyncTrashProxy::trash_file).
+    pub fn trash_file<T>(&self, fd: &T) -> Result<(), Error>
+    where
+        T: AsRawFd,
+    {
+        block_on(self.0.trash_file(fd))
+    }
+}
@@ -0,0 +1,42 @@
+use std::os::unix::prelude::AsRawFd;
+
+use crate::{
+    blocking::block_on, desktop::wallpaper::WallpaperProxy as AsyncWallpaperProxy, Error,
+    WindowIdentifier,
+};
+
+pub use crate::desktop::wallpaper::SetOn;
+
+blocking_proxy! {
+    /// Blocking variant of the
+    /// [`WallpaperProxy`](crate::desktop::wallpaper::WallpaperProxy).
+    #[doc(alias = "org.freedesktop.portal.Wallpaper")]
+    WallpaperProxy => AsyncWallpaperProxy;
+    fn set_wallpaper_uri(
+        &self,
+        identifier: &WindowIdentifier,
+        uri: &str,
+        show_preview: bool,
+        set_on: SetOn,
+    ) -> ();
+}
+
+impl<'a> WallpaperProxy<'a> {
+    /// Blocking variant of
+    /// [`set_wallpaper_file()`](AsyncWallpaperProxy::set_wallpaper_file).
+    pub fn set_wallpaper_file<F>(
+        &self,
+        identifier: &WindowIdentifier,
+        file: &F,
+        show_preview: bool,
+        set_on: SetOn,
+    ) -> Result<(), Error>
+    where
+        F: AsRawFd,
+    {
+        block_on(
+            self.0
+                .set_wallpaper_file(identifier, file, show_preview, set_on),
+        )
+    }
+}
@@ -0,0 +1,22 @@
+use std::os::unix::prelude::AsRawFd;
+
+use crate::{blocking::block_on, documents::FileTransferProxy as AsyncFileTransferProxy, Error};
+
+blocking_proxy! {
+    /// Blocking variant of the
+    /// [`FileTransferProxy`](crate::documents::FileTransferProxy).
+    #[doc(alias = "org.freedesktop.portal.FileTransfer")]
+    FileTransferProxy => AsyncFileTransferProxy;
+    fn retrieve_files(&self, key: &str) -> Vec<String>;
+    fn start_transfer(&self, writeable: bool, auto_stop: bool) -> String;
+    fn stop_transfer(&self, key: &str) -> ();
+    fn transfer_closed(&self) -> String;
+    iter receive_transfer_closed_iter => receive_transfer_closed_stream: String;
+}
+
+impl<'a> FileTransferProxy<'a> {
+    /// Blocking variant of [`add_files()`](AsyncFileTransferProxy::add_files).
+    pub fn add_files<F: AsRawFd>(&self, key: &str, fds: &[&F]) -> Result<(), Error> {
+        block_on(self.0.add_files(key, fds))
+    }
+}
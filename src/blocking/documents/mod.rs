@@ -0,0 +1,108 @@
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    os::unix::prelude::AsRawFd,
+    path::{Path, PathBuf},
+};
+
+use enumflags2::BitFlags;
+use serde::Serialize;
+
+use crate::{blocking::block_on, documents::DocumentsProxy as AsyncDocumentsProxy, Error};
+
+pub use crate::documents::{Flags, Permission, Permissions};
+
+blocking_proxy! {
+    /// Blocking variant of the
+    /// [`DocumentsProxy`](crate::documents::DocumentsProxy).
+    #[doc(alias = "org.freedesktop.portal.Documents")]
+    DocumentsProxy => AsyncDocumentsProxy;
+    fn delete(&self, doc_id: &str) -> ();
+    fn mount_point(&self) -> PathBuf;
+    fn grant_permissions(&self, doc_id: &str, app_id: &str, permissions: &[Permission]) -> ();
+    fn info(&self, doc_id: &str) -> (PathBuf, Permissions);
+    fn list(&self, app_id: &str) -> HashMap<String, PathBuf>;
+    fn revoke_permissions(&self, doc_id: &str, app_id: &str, permissions: &[Permission]) -> ();
+}
+
+impl<'a> DocumentsProxy<'a> {
+    /// Blocking variant of [`add()`](AsyncDocumentsProxy::add).
+    pub fn add<F>(
+        &self,
+        o_path_fd: &F,
+        reuse_existing: bool,
+        persistent: bool,
+    ) -> Result<String, Error>
+    where
+        F: AsRawFd + Debug,
+    {
+        block_on(self.0.add(o_path_fd, reuse_existing, persistent))
+    }
+
+    /// Blocking variant of [`add_full()`](AsyncDocumentsProxy::add_full).
+    pub fn add_full<F: AsRawFd>(
+        &self,
+        o_path_fds: &[&F],
+        flags: BitFlags<Flags>,
+        app_id: &str,
+        permissions: &[Permission],
+    ) -> Result<(Vec<String>, HashMap<String, zvariant::OwnedValue>), Error> {
+        block_on(self.0.add_full(o_path_fds, flags, app_id, permissions))
+    }
+
+    /// Blocking variant of [`add_named()`](AsyncDocumentsProxy::add_named).
+    pub fn add_named<F, P>(
+        &self,
+        o_path_parent_fd: &F,
+        filename: P,
+        reuse_existing: bool,
+        persistent: bool,
+    ) -> Result<String, Error>
+    where
+        F: AsRawFd + Debug,
+        P: AsRef<Path> + Serialize + zvariant::Type + Debug,
+    {
+        block_on(
+            self.0
+                .add_named(o_path_parent_fd, filename, reuse_existing, persistent),
+        )
+    }
+
+    /// Blocking variant of
+    /// [`add_named_full()`](AsyncDocumentsProxy::add_named_full).
+    pub fn add_named_full<F, P>(
+        &self,
+        o_path_fd: &F,
+        filename: P,
+        flags: BitFlags<Flags>,
+        app_id: &str,
+        permissions: &[Permission],
+    ) -> Result<(String, HashMap<String, zvariant::OwnedValue>), Error>
+    where
+        F: AsRawFd + Debug,
+        P: AsRef<Path> + Serialize + zvariant::Type + Debug,
+    {
+        block_on(
+            self.0
+                .add_named_full(o_path_fd, filename, flags, app_id, permissions),
+        )
+    }
+
+    /// Blocking variant of [`lookup()`](AsyncDocumentsProxy::lookup).
+    pub fn lookup<P: AsRef<Path> + Serialize + zvariant::Type + Debug>(
+        &self,
+        filename: P,
+    ) -> Result<Option<String>, Error> {
+        block_on(self.0.lookup(filename))
+    }
+}
+
+mod file_transfer;
+
+pub use file_transfer::FileTransferProxy;
+
+mod permission_store;
+
+pub use permission_store::{
+    AppPermissions, PermissionChange, PermissionStoreProxy, StorePermission, Table,
+};
@@ -0,0 +1,36 @@
+use zvariant::{OwnedValue, Value};
+
+use crate::documents::PermissionStoreProxy as AsyncPermissionStoreProxy;
+
+pub use crate::documents::{AppPermissions, PermissionChange, StorePermission, Table};
+
+blocking_proxy! {
+    /// Blocking variant of the
+    /// [`PermissionStoreProxy`](crate::documents::PermissionStoreProxy).
+    #[doc(alias = "org.freedesktop.impl.portal.PermissionStore")]
+    PermissionStoreProxy => AsyncPermissionStoreProxy;
+    fn receive_changed(&self) -> PermissionChange;
+    fn delete(&self, table: &Table, id: &str) -> ();
+    fn delete_permission(&self, table: &Table, id: &str, app: &str) -> ();
+    fn list(&self, table: &Table) -> Vec<String>;
+    fn lookup(&self, table: &Table, id: &str) -> (AppPermissions, OwnedValue);
+    fn permission(&self, table: &Table, id: &str, app: &str) -> Vec<StorePermission>;
+    fn set(
+        &self,
+        table: &Table,
+        create: bool,
+        id: &str,
+        app_permissions: &AppPermissions,
+        data: Value<'_>,
+    ) -> ();
+    fn set_permission(
+        &self,
+        table: &Table,
+        create: bool,
+        id: &str,
+        app: &str,
+        permissions: &[StorePermission],
+    ) -> ();
+    fn set_value(&self, table: &Table, create: bool, id: &str, data: Value<'_>) -> ();
+    iter receive_changed_iter => receive_changed_stream: PermissionChange;
+}
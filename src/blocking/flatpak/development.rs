@@ -0,0 +1,35 @@
+use std::{collections::HashMap, path::Path};
+
+use enumflags2::BitFlags;
+use zvariant::Fd;
+
+use super::SpawnedProcess;
+use crate::{
+    blocking::block_on, flatpak::development::DevelopmentProxy as AsyncDevelopmentProxy, Error,
+};
+
+pub use crate::flatpak::development::HostCommandFlags;
+
+blocking_proxy! {
+    /// Blocking variant of the
+    /// [`DevelopmentProxy`](crate::flatpak::development::DevelopmentProxy).
+    #[doc(alias = "org.freedesktop.Flatpak.Development")]
+    DevelopmentProxy => AsyncDevelopmentProxy;
+    fn receive_host_command_exited(&self) -> (u32, u32);
+    fn host_command_signal(&self, pid: u32, signal: u32, to_process_group: bool) -> ();
+    iter receive_host_command_exited_iter => receive_host_command_exited_stream: (u32, u32);
+}
+
+impl<'a> DevelopmentProxy<'a> {
+    /// Blocking variant of [`host_command()`](AsyncDevelopmentProxy::host_command).
+    pub fn host_command<C: AsRef<Path>, S: AsRef<Path>>(
+        &self,
+        cwd_path: C,
+        argv: &[S],
+        fds: HashMap<u32, Fd>,
+        envs: HashMap<&str, &str>,
+        flags: BitFlags<HostCommandFlags>,
+    ) -> Result<SpawnedProcess<'_>, Error> {
+        block_on(self.0.host_command(cwd_path, argv, fds, envs, flags)).map(From::from)
+    }
+}
@@ -0,0 +1,61 @@
+use std::{collections::HashMap, fmt::Debug, path::Path};
+
+use enumflags2::BitFlags;
+use serde::Serialize;
+use zvariant::Fd;
+
+use crate::{blocking::block_on, flatpak::FlatpakProxy as AsyncFlatpakProxy, Error};
+
+pub use crate::flatpak::{SandboxFlags, SpawnFlags, SpawnOptions, SupportsFlags};
+
+blocking_proxy! {
+    /// Blocking variant of the [`FlatpakProxy`](crate::flatpak::FlatpakProxy).
+    #[doc(alias = "org.freedesktop.portal.Flatpak")]
+    FlatpakProxy => AsyncFlatpakProxy;
+    fn receive_spawn_started(&self) -> (u32, u32);
+    fn receive_spawn_exited(&self) -> (u32, u32);
+    fn spawn_signal(&self, pid: u32, signal: u32, to_process_group: bool) -> ();
+    fn supports(&self) -> BitFlags<SupportsFlags>;
+    iter receive_spawn_started_iter => receive_spawn_started_stream: (u32, u32);
+    iter receive_spawn_exited_iter => receive_spawn_exited_stream: (u32, u32);
+}
+
+impl<'a> FlatpakProxy<'a> {
+    /// Blocking variant of
+    /// [`create_update_monitor()`](AsyncFlatpakProxy::create_update_monitor).
+    pub fn create_update_monitor(&self) -> Result<UpdateMonitorProxy<'a>, Error> {
+        block_on(self.0.create_update_monitor()).map(From::from)
+    }
+
+    /// Blocking variant of [`spawn()`](AsyncFlatpakProxy::spawn).
+    pub fn spawn<
+        C: AsRef<Path> + zvariant::Type + Serialize + Debug,
+        S: AsRef<Path> + zvariant::Type + Serialize + Debug,
+    >(
+        &self,
+        cwd_path: C,
+        argv: &[S],
+        fds: HashMap<u32, Fd>,
+        envs: HashMap<&str, &str>,
+        flags: BitFlags<SpawnFlags>,
+        options: SpawnOptions,
+    ) -> Result<SpawnedProcess<'_>, Error> {
+        block_on(self.0.spawn(cwd_path, argv, fds, envs, flags, options)).map(From::from)
+    }
+
+    /// Blocking variant of
+    /// [`receive_spawn_exited()`](AsyncFlatpakProxy::receive_spawn_exited).
+    #[deprecated(note = "Use `receive_spawn_exited()` instead")]
+    pub fn receive_spawn_existed(&self) -> Result<(u32, u32), Error> {
+        self.receive_spawn_exited()
+    }
+}
+
+/// Run commands on the host, outside of the sandbox.
+pub mod development;
+
+mod spawned_process;
+pub use spawned_process::{ExitStatus, SpawnedProcess};
+
+mod update_monitor;
+pub use update_monitor::{UpdateInfo, UpdateMonitorProxy, UpdateProgress, UpdateStatus};
@@ -0,0 +1,51 @@
+use crate::{blocking::block_on, flatpak::SpawnedProcess as AsyncSpawnedProcess, Error};
+
+pub use crate::flatpak::ExitStatus;
+
+/// Blocking variant of the
+/// [`SpawnedProcess`](crate::flatpak::SpawnedProcess).
+///
+/// Created with [`FlatpakProxy::spawn`](super::FlatpakProxy::spawn).
+#[derive(Debug)]
+pub struct SpawnedProcess<'a>(AsyncSpawnedProcess<'a>);
+
+impl<'a> SpawnedProcess<'a> {
+    /// Get a reference to the underlying async handle.
+    pub fn inner(&self) -> &AsyncSpawnedProcess<'a> {
+        &self.0
+    }
+
+    /// The PID of the process, as seen from the caller.
+    pub fn pid(&self) -> u32 {
+        self.0.pid()
+    }
+
+    /// Blocking variant of [`started()`](AsyncSpawnedProcess::started).
+    #[doc(alias = "SpawnStarted")]
+    pub fn started(&mut self) -> Result<Option<u32>, Error> {
+        block_on(self.0.started())
+    }
+
+    /// Blocking variant of [`wait()`](AsyncSpawnedProcess::wait).
+    #[doc(alias = "SpawnExited")]
+    pub fn wait(&mut self) -> Result<ExitStatus, Error> {
+        block_on(self.0.wait())
+    }
+
+    /// Blocking variant of [`signal()`](AsyncSpawnedProcess::signal).
+    #[doc(alias = "SpawnSignal")]
+    pub fn signal(&self, signal: u32, to_process_group: bool) -> Result<(), Error> {
+        block_on(self.0.signal(signal, to_process_group))
+    }
+
+    /// Blocking variant of [`kill()`](AsyncSpawnedProcess::kill).
+    pub fn kill(&self, to_process_group: bool) -> Result<(), Error> {
+        block_on(self.0.kill(to_process_group))
+    }
+}
+
+impl<'a> From<AsyncSpawnedProcess<'a>> for SpawnedProcess<'a> {
+    fn from(process: AsyncSpawnedProcess<'a>) -> Self {
+        Self(process)
+    }
+}
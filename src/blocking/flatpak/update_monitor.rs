@@ -0,0 +1,71 @@
+use crate::{
+    blocking::{block_on, iter},
+    flatpak::UpdateMonitorProxy as AsyncUpdateMonitorProxy,
+    Error, WindowIdentifier,
+};
+
+pub use crate::flatpak::{UpdateInfo, UpdateProgress, UpdateStatus};
+
+/// Blocking variant of the
+/// [`UpdateMonitorProxy`](crate::flatpak::UpdateMonitorProxy).
+///
+/// Created with
+/// [`FlatpakProxy::create_update_monitor`](super::FlatpakProxy::create_update_monitor).
+#[derive(Debug)]
+#[doc(alias = "org.freedesktop.portal.Flatpak.UpdateMonitor")]
+pub struct UpdateMonitorProxy<'a>(AsyncUpdateMonitorProxy<'a>);
+
+impl<'a> UpdateMonitorProxy<'a> {
+    /// Get a reference to the underlying async proxy.
+    pub fn inner(&self) -> &AsyncUpdateMonitorProxy<'a> {
+        &self.0
+    }
+
+    /// Blocking variant of
+    /// [`receive_progress()`](AsyncUpdateMonitorProxy::receive_progress).
+    pub fn receive_progress(&self) -> Result<UpdateProgress, Error> {
+        block_on(self.0.receive_progress())
+    }
+
+    /// Blocking variant of
+    /// [`receive_progress_stream()`](AsyncUpdateMonitorProxy::receive_progress_stream),
+    /// each call to `next()` blocks until the signal is emitted.
+    pub fn receive_progress_iter(
+        &self,
+    ) -> Result<impl Iterator<Item = UpdateProgress> + '_, Error> {
+        let stream = block_on(self.0.receive_progress_stream())?;
+        Ok(iter(stream))
+    }
+
+    /// Blocking variant of
+    /// [`receive_update_available()`](AsyncUpdateMonitorProxy::receive_update_available).
+    pub fn receive_update_available(&self) -> Result<UpdateInfo, Error> {
+        block_on(self.0.receive_update_available())
+    }
+
+    /// Blocking variant of
+    /// [`receive_update_available_stream()`](AsyncUpdateMonitorProxy::receive_update_available_stream),
+    /// each call to `next()` blocks until the signal is emitted.
+    pub fn receive_update_available_iter(
+        &self,
+    ) -> Result<impl Iterator<Item = UpdateInfo> + '_, Error> {
+        let stream = block_on(self.0.receive_update_available_stream())?;
+        Ok(iter(stream))
+    }
+
+    /// Blocking variant of [`update()`](AsyncUpdateMonitorProxy::update).
+    pub fn update(&self, identifier: &WindowIdentifier) -> Result<(), Error> {
+        block_on(self.0.update(identifier))
+    }
+
+    /// Blocking variant of [`close()`](AsyncUpdateMonitorProxy::close).
+    pub fn close(&self) -> Result<(), Error> {
+        block_on(self.0.close())
+    }
+}
+
+impl<'a> From<AsyncUpdateMonitorProxy<'a>> for UpdateMonitorProxy<'a> {
+    fn from(proxy: AsyncUpdateMonitorProxy<'a>) -> Self {
+        Self(proxy)
+    }
+}
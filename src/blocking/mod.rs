@@ -0,0 +1,128 @@
+//! Blocking variants of the portal proxies, for applications that don't run
+//! an async executor.
+//!
+//! The proxies are created from a [`zbus::Connection`] and mirror the async
+//! ones found in [`desktop`](crate::desktop),
+//! [`documents`](crate::documents) and [`flatpak`](crate::flatpak). They
+//! share the same option & response types, signals are received with
+//! iterators instead of streams.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use ashpd::blocking::desktop::file_chooser::{FileChooserProxy, OpenFileOptions};
+//! use ashpd::WindowIdentifier;
+//!
+//! fn run() -> ashpd::Result<()> {
+//!     let connection = zbus::Connection::session()?;
+//!     let proxy = FileChooserProxy::new(&connection)?;
+//!
+//!     let files = proxy.open_file(
+//!         &WindowIdentifier::default(),
+//!         "Open a file",
+//!         OpenFileOptions::default().multiple(true),
+//!     )?;
+//!
+//!     println!("{:#?}", files);
+//!     Ok(())
+//! }
+//! ```
+
+use futures::{Stream, StreamExt};
+
+/// Defines a blocking proxy wrapping the async proxy `$async`, forwarding
+/// each listed method & turning each listed signal stream into an iterator.
+macro_rules! blocking_proxy {
+    (
+        $(#[$attr:meta])*
+        $name:ident => $async:ident;
+        $(
+            fn $method:ident(&self $(, $arg:ident: $ty:ty)* $(,)?) -> $ret:ty;
+        )*
+        $(
+            iter $iter:ident => $stream:ident: $item:ty;
+        )*
+    ) => {
+        $(#[$attr])*
+        #[derive(Debug)]
+        pub struct $name<'a>($async<'a>);
+
+        impl<'a> $name<'a> {
+            #[doc = concat!("Create a new instance of [`", stringify!($name), "`].")]
+            pub fn new(connection: &zbus::Connection) -> Result<$name<'a>, $crate::Error> {
+                $crate::blocking::block_on($async::new(connection.inner())).map(Self)
+            }
+
+            /// Get a reference to the underlying async proxy.
+            pub fn inner(&self) -> &$async<'a> {
+                &self.0
+            }
+
+            #[doc = concat!(
+                "Blocking variant of [`version()`](", stringify!($async), "::version)."
+            )]
+            pub fn version(&self) -> Result<u32, $crate::Error> {
+                $crate::blocking::block_on(self.0.version())
+            }
+
+            #[doc = concat!(
+                "See [`set_timeout()`](", stringify!($async), "::set_timeout)."
+            )]
+            pub fn set_timeout(&mut self, timeout: Option<std::time::Duration>) {
+                self.0.set_timeout(timeout);
+            }
+
+            $(
+                #[doc = concat!(
+                    "Blocking variant of [`", stringify!($method), "()`](",
+                    stringify!($async), "::", stringify!($method), ")."
+                )]
+                pub fn $method(&self $(, $arg: $ty)*) -> Result<$ret, $crate::Error> {
+                    $crate::blocking::block_on(self.0.$method($($arg),*))
+                }
+            )*
+
+            $(
+                #[doc = concat!(
+                    "Blocking variant of [`", stringify!($stream), "()`](",
+                    stringify!($async), "::", stringify!($stream), "), ",
+                    "each call to `next()` blocks until the signal is emitted."
+                )]
+                pub fn $iter(&self) -> Result<impl Iterator<Item = $item> + '_, $crate::Error> {
+                    let stream = $crate::blocking::block_on(self.0.$stream())?;
+                    Ok($crate::blocking::iter(stream))
+                }
+            )*
+        }
+
+        impl<'a> From<$async<'a>> for $name<'a> {
+            fn from(proxy: $async<'a>) -> Self {
+                Self(proxy)
+            }
+        }
+    };
+}
+
+/// Interact with the user's desktop such as taking a screenshot, setting a
+/// background or querying the user's location.
+pub mod desktop;
+/// Interact with the documents store or transfer files across apps.
+pub mod documents;
+/// Spawn commands outside the sandbox or monitor if the running application has
+/// received an update & install it.
+pub mod flatpak;
+
+/// Run a future to completion on the current thread, driving the I/O of the
+/// connection like [`zbus::Connection`] does.
+pub(crate) fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    async_io::block_on(future)
+}
+
+/// Turn a signal stream into an iterator blocking on each item.
+pub(crate) fn iter<'a, S>(stream: S) -> impl Iterator<Item = S::Item> + 'a
+where
+    S: Stream + 'a,
+{
+    let mut stream = Box::pin(stream);
+    std::iter::from_fn(move || block_on(stream.next()))
+}
@@ -43,7 +43,7 @@
 
 use zvariant_derive::{DeserializeDict, SerializeDict, TypeDict};
 
-use super::{HandleToken, DESTINATION, PATH};
+use super::{HandleToken, Request, DESTINATION, PATH};
 use crate::{helpers::call_request_method, Error, WindowIdentifier};
 
 #[derive(SerializeDict, DeserializeDict, TypeDict, Clone, Debug, Default)]
@@ -100,7 +100,7 @@ impl UserInfo {
 /// Wrapper of the DBus interface: [`org.freedesktop.portal.Account`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.portal.Account).
 #[derive(Debug)]
 #[doc(alias = "org.freedesktop.portal.Account")]
-pub struct AccountProxy<'a>(zbus::azync::Proxy<'a>);
+pub struct AccountProxy<'a>(crate::helpers::Proxy<'a>);
 
 impl<'a> AccountProxy<'a> {
     /// Create a new instance of [`AccountProxy`].
@@ -111,7 +111,7 @@ impl<'a> AccountProxy<'a> {
             .destination(DESTINATION)?
             .build()
             .await?;
-        Ok(Self(proxy))
+        Ok(Self(proxy.into()))
     }
 
     /// Get a reference to the underlying Proxy.
@@ -119,6 +119,10 @@ impl<'a> AccountProxy<'a> {
         &self.0
     }
 
+    proxy_set_timeout!();
+
+    proxy_version!(Account);
+
     /// Gets information about the user.
     ///
     /// # Arguments
@@ -126,19 +130,22 @@ impl<'a> AccountProxy<'a> {
     /// * `identifier` - Identifier for the window.
     /// * `reason` - A user-visible reason for the request.
     #[doc(alias = "GetUserInformation")]
-    pub async fn user_information(
-        &self,
-        identifier: &WindowIdentifier,
+    pub fn user_information<'r>(
+        &'r self,
+        identifier: &'r WindowIdentifier,
         reason: &str,
-    ) -> Result<UserInfo, Error> {
+    ) -> Request<'r, UserInfo> {
         let options = UserInfoOptions::default().reason(reason);
-        call_request_method(
-            &self.0,
-            &options.handle_token,
-            "GetUserInformation",
-            &(&identifier, &options),
-        )
-        .await
+        Request::new(move |call| async move {
+            call_request_method(
+                &self.0,
+                &call,
+                &options.handle_token,
+                "GetUserInformation",
+                &(&identifier, &options),
+            )
+            .await
+        })
     }
 }
 
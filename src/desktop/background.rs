@@ -50,7 +50,7 @@
 use serde::Serialize;
 use zvariant_derive::{DeserializeDict, SerializeDict, TypeDict};
 
-use super::{HandleToken, DESTINATION, PATH};
+use super::{HandleToken, Request, DESTINATION, PATH};
 use crate::{helpers::call_request_method, Error, WindowIdentifier};
 
 #[derive(SerializeDict, DeserializeDict, TypeDict, Debug, Clone, Default)]
@@ -131,7 +131,7 @@ impl Background {
 /// Wrapper of the DBus interface: [`org.freedesktop.portal.Background`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.portal.Background).
 #[derive(Debug)]
 #[doc(alias = "org.freedesktop.portal.Background")]
-pub struct BackgroundProxy<'a>(zbus::azync::Proxy<'a>);
+pub struct BackgroundProxy<'a>(crate::helpers::Proxy<'a>);
 
 impl<'a> BackgroundProxy<'a> {
     /// Create a new instance of [`BackgroundProxy`].
@@ -142,7 +142,7 @@ impl<'a> BackgroundProxy<'a> {
             .destination(DESTINATION)?
             .build()
             .await?;
-        Ok(Self(proxy))
+        Ok(Self(proxy.into()))
     }
 
     /// Get a reference to the underlying Proxy.
@@ -150,6 +150,10 @@ impl<'a> BackgroundProxy<'a> {
         &self.0
     }
 
+    proxy_set_timeout!();
+
+    proxy_version!(Background);
+
     /// Requests that the application is allowed to run in the background.
     ///
     /// # Arguments
@@ -167,26 +171,29 @@ impl<'a> BackgroundProxy<'a> {
     ///
     /// See also [`RequestBackground`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-portal-Background.RequestBackground).
     #[doc(alias = "RequestBackground")]
-    pub async fn request_background<S: AsRef<str> + zvariant::Type + Serialize>(
-        &self,
-        identifier: &WindowIdentifier,
+    pub fn request_background<'r, S: AsRef<str> + zvariant::Type + Serialize>(
+        &'r self,
+        identifier: &'r WindowIdentifier,
         reason: &str,
         auto_start: bool,
         command_line: Option<&[S]>,
         dbus_activatable: bool,
-    ) -> Result<Background, Error> {
+    ) -> Request<'r, Background> {
         let options = BackgroundOptions::default()
             .reason(reason)
             .autostart(auto_start)
             .dbus_activatable(dbus_activatable)
             .command(command_line);
-        call_request_method(
-            &self.0,
-            &options.handle_token,
-            "RequestBackground",
-            &(&identifier, &options),
-        )
-        .await
+        Request::new(move |call| async move {
+            call_request_method(
+                &self.0,
+                &call,
+                &options.handle_token,
+                "RequestBackground",
+                &(&identifier, &options),
+            )
+            .await
+        })
     }
 }
 
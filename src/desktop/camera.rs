@@ -24,9 +24,9 @@ use std::{
 use zvariant::{Fd, Value};
 use zvariant_derive::{DeserializeDict, SerializeDict, TypeDict};
 
-use super::{HandleToken, DESTINATION, PATH};
+use super::{HandleToken, Request, DESTINATION, PATH};
 use crate::{
-    helpers::{call_basic_response_method, call_method},
+    helpers::{call_basic_response_method, call_method, get_property},
     Error,
 };
 
@@ -43,7 +43,7 @@ struct CameraAccessOptions {
 /// Wrapper of the DBus interface: [`org.freedesktop.portal.Camera`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.portal.Camera).
 #[derive(Debug)]
 #[doc(alias = "org.freedesktop.portal.Camera")]
-pub struct CameraProxy<'a>(zbus::azync::Proxy<'a>);
+pub struct CameraProxy<'a>(crate::helpers::Proxy<'a>);
 
 impl<'a> CameraProxy<'a> {
     /// Create a new instance of [`CameraProxy`].
@@ -54,7 +54,7 @@ impl<'a> CameraProxy<'a> {
             .destination(DESTINATION)?
             .build()
             .await?;
-        Ok(Self(proxy))
+        Ok(Self(proxy.into()))
     }
 
     /// Get a reference to the underlying Proxy.
@@ -62,16 +62,28 @@ impl<'a> CameraProxy<'a> {
         &self.0
     }
 
+    proxy_set_timeout!();
+
+    proxy_version!(Camera);
+
     /// Requests an access to the camera.
     ///
     /// # Specifications
     ///
     /// See also [`AccessCamera`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-portal-Camera.AccessCamera).
     #[doc(alias = "AccessCamera")]
-    pub async fn access_camera(&self) -> Result<(), Error> {
+    pub fn access_camera(&self) -> Request<'_, ()> {
         let options = CameraAccessOptions::default();
-        call_basic_response_method(&self.0, &options.handle_token, "AccessCamera", &(&options))
+        Request::new(move |call| async move {
+            call_basic_response_method(
+                &self.0,
+                &call,
+                &options.handle_token,
+                "AccessCamera",
+                &(&options),
+            )
             .await
+        })
     }
 
     /// Open a file descriptor to the PipeWire remote where the camera nodes are
@@ -100,10 +112,7 @@ impl<'a> CameraProxy<'a> {
     /// See also [`IsCameraPresent`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-property-org-freedesktop-portal-Camera.IsCameraPresent).
     #[doc(alias = "IsCameraPresent")]
     pub async fn is_camera_present(&self) -> Result<bool, Error> {
-        self.0
-            .get_property::<bool>("IsCameraPresent")
-            .await
-            .map_err(From::from)
+        get_property::<bool>(&self.0, "IsCameraPresent").await
     }
 }
 
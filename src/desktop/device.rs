@@ -19,7 +19,7 @@ use strum_macros::{AsRefStr, EnumString, IntoStaticStr, ToString};
 use zvariant::Signature;
 use zvariant_derive::{DeserializeDict, SerializeDict, TypeDict};
 
-use super::{HandleToken, DESTINATION, PATH};
+use super::{HandleToken, Request, DESTINATION, PATH};
 use crate::{helpers::call_basic_response_method, Error};
 
 #[derive(SerializeDict, DeserializeDict, TypeDict, Clone, Debug, Default)]
@@ -66,7 +66,7 @@ impl Serialize for Device {
 /// Wrapper of the DBus interface: [`org.freedesktop.portal.Device`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.portal.Device).
 #[derive(Debug)]
 #[doc(alias = "org.freedesktop.portal.Device")]
-pub struct DeviceProxy<'a>(zbus::azync::Proxy<'a>);
+pub struct DeviceProxy<'a>(crate::helpers::Proxy<'a>);
 
 impl<'a> DeviceProxy<'a> {
     /// Create a new instance of [`DeviceProxy`].
@@ -77,7 +77,7 @@ impl<'a> DeviceProxy<'a> {
             .destination(DESTINATION)?
             .build()
             .await?;
-        Ok(Self(proxy))
+        Ok(Self(proxy.into()))
     }
 
     /// Get a reference to the underlying Proxy.
@@ -85,6 +85,10 @@ impl<'a> DeviceProxy<'a> {
         &self.0
     }
 
+    proxy_set_timeout!();
+
+    proxy_version!(Device);
+
     /// Asks for access to a device.
     ///
     /// # Arguments
@@ -99,14 +103,17 @@ impl<'a> DeviceProxy<'a> {
     ///
     /// See also [`AccessDevice`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-portal-Device.AccessDevice).
     #[doc(alias = "AccessDevice")]
-    pub async fn access_device(&self, pid: u32, devices: &[Device]) -> Result<(), Error> {
+    pub fn access_device<'r>(&'r self, pid: u32, devices: &'r [Device]) -> Request<'r, ()> {
         let options = AccessDeviceOptions::default();
-        call_basic_response_method(
-            &self.0,
-            &options.handle_token,
-            "AccessDevice",
-            &(pid, devices, &options),
-        )
-        .await
+        Request::new(move |call| async move {
+            call_basic_response_method(
+                &self.0,
+                &call,
+                &options.handle_token,
+                "AccessDevice",
+                &(pid, devices, &options),
+            )
+            .await
+        })
     }
 }
@@ -54,7 +54,7 @@ use serde::Serialize;
 use zvariant::Fd;
 use zvariant_derive::{DeserializeDict, SerializeDict, TypeDict};
 
-use super::{HandleToken, DESTINATION, PATH};
+use super::{HandleToken, Request, DESTINATION, PATH};
 use crate::{helpers::call_basic_response_method, Error, WindowIdentifier};
 
 #[derive(SerializeDict, DeserializeDict, TypeDict, Debug, Default)]
@@ -171,7 +171,7 @@ impl Email {
 /// Wrapper of the DBus interface: [`org.freedesktop.portal.Email`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.portal.Email).
 #[derive(Debug)]
 #[doc(alias = "org.freedesktop.portal.Email")]
-pub struct EmailProxy<'a>(zbus::azync::Proxy<'a>);
+pub struct EmailProxy<'a>(crate::helpers::Proxy<'a>);
 
 impl<'a> EmailProxy<'a> {
     /// Create a new instance of [`EmailProxy`].
@@ -182,7 +182,7 @@ impl<'a> EmailProxy<'a> {
             .destination(DESTINATION)?
             .build()
             .await?;
-        Ok(Self(proxy))
+        Ok(Self(proxy.into()))
     }
 
     /// Get a reference to the underlying Proxy.
@@ -190,6 +190,10 @@ impl<'a> EmailProxy<'a> {
         &self.0
     }
 
+    proxy_set_timeout!();
+
+    proxy_version!(Email);
+
     /// Presents a window that lets the user compose an email.
     ///
     /// **Note** the default email client for the host will need to support
@@ -204,18 +208,21 @@ impl<'a> EmailProxy<'a> {
     ///
     /// See also [`ComposeEmail`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-portal-Email.ComposeEmail).
     #[doc(alias = "ComposeEmail")]
-    pub async fn compose_email(
-        &self,
-        identifier: &WindowIdentifier,
+    pub fn compose_email<'r>(
+        &'r self,
+        identifier: &'r WindowIdentifier,
         email: Email,
-    ) -> Result<(), Error> {
-        call_basic_response_method(
-            &self.0,
-            &email.handle_token,
-            "ComposeEmail",
-            &(&identifier, &email),
-        )
-        .await
+    ) -> Request<'r, ()> {
+        Request::new(move |call| async move {
+            call_basic_response_method(
+                &self.0,
+                &call,
+                &email.handle_token,
+                "ComposeEmail",
+                &(&identifier, &email),
+            )
+            .await
+        })
     }
 }
 
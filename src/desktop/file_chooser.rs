@@ -93,10 +93,13 @@
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use std::os::unix::ffi::OsStrExt;
-use std::{ffi::CString, path::Path};
+use std::{
+    ffi::{CString, OsStr},
+    path::Path,
+};
 use zvariant_derive::{DeserializeDict, SerializeDict, Type, TypeDict};
 
-use super::{HandleToken, DESTINATION, PATH};
+use super::{HandleToken, Request, DESTINATION, PATH};
 use crate::{helpers::call_request_method, Error, WindowIdentifier};
 
 #[derive(Serialize, Deserialize, Type, Clone, Debug)]
@@ -133,6 +136,29 @@ impl FileFilter {
         self.1.push((FilterType::GlobPattern, pattern.to_string()));
         self
     }
+
+    /// The user-visible name of the file filter.
+    pub fn label(&self) -> &str {
+        &self.0
+    }
+
+    /// The mime types of the file filter.
+    pub fn mimetypes(&self) -> Vec<&str> {
+        self.patterns_of(FilterType::MimeType)
+    }
+
+    /// The glob patterns of the file filter.
+    pub fn globs(&self) -> Vec<&str> {
+        self.patterns_of(FilterType::GlobPattern)
+    }
+
+    fn patterns_of(&self, type_: FilterType) -> Vec<&str> {
+        self.1
+            .iter()
+            .filter(|(t, _)| *t == type_)
+            .map(|(_, pattern)| pattern.as_str())
+            .collect()
+    }
 }
 
 #[derive(Serialize, Deserialize, Type, Clone, Debug)]
@@ -187,6 +213,13 @@ impl Choice {
     pub fn initial_selection(&self) -> &str {
         &self.3
     }
+
+    /// The possible values as a tuple of (key, label).
+    ///
+    /// Empty for a boolean choice.
+    pub fn pairs(&self) -> &[(String, String)] {
+        &self.2
+    }
 }
 
 #[derive(SerializeDict, DeserializeDict, TypeDict, Clone, Debug, Default)]
@@ -203,11 +236,11 @@ pub struct OpenFileOptions {
     /// Whether to select for folders instead of files.
     directory: Option<bool>,
     /// List of serialized file filters.
-    filters: Vec<FileFilter>,
+    filters: Option<Vec<FileFilter>>,
     /// Request that this filter be set by default at dialog creation.
     current_filter: Option<FileFilter>,
     /// List of serialized combo boxes to add to the file chooser
-    choices: Vec<Choice>,
+    choices: Option<Vec<Choice>>,
 }
 
 impl OpenFileOptions {
@@ -237,7 +270,7 @@ impl OpenFileOptions {
 
     /// Adds a files filter.
     pub fn add_filter(mut self, filter: FileFilter) -> Self {
-        self.filters.push(filter);
+        self.filters.get_or_insert_with(Vec::new).push(filter);
         self
     }
 
@@ -249,9 +282,44 @@ impl OpenFileOptions {
 
     /// Adds a choice.
     pub fn add_choice(mut self, choice: Choice) -> Self {
-        self.choices.push(choice);
+        self.choices.get_or_insert_with(Vec::new).push(choice);
         self
     }
+
+    /// The label of the accept button.
+    pub fn get_accept_label(&self) -> Option<&str> {
+        self.accept_label.as_deref()
+    }
+
+    /// Whether the dialog should be modal.
+    pub fn get_modal(&self) -> Option<bool> {
+        self.modal
+    }
+
+    /// Whether to allow multiple files selection.
+    pub fn get_multiple(&self) -> Option<bool> {
+        self.multiple
+    }
+
+    /// Whether to select directories.
+    pub fn get_directory(&self) -> Option<bool> {
+        self.directory
+    }
+
+    /// The files filters.
+    pub fn get_filters(&self) -> &[FileFilter] {
+        self.filters.as_deref().unwrap_or_default()
+    }
+
+    /// The default filter.
+    pub fn get_current_filter(&self) -> Option<&FileFilter> {
+        self.current_filter.as_ref()
+    }
+
+    /// The choices.
+    pub fn get_choices(&self) -> &[Choice] {
+        self.choices.as_deref().unwrap_or_default()
+    }
 }
 
 #[derive(SerializeDict, DeserializeDict, TypeDict, Debug, Default)]
@@ -270,11 +338,11 @@ pub struct SaveFileOptions {
     /// The current file (when saving an existing file).
     current_file: Option<Vec<u8>>,
     /// List of serialized file filters.
-    filters: Vec<FileFilter>,
+    filters: Option<Vec<FileFilter>>,
     /// Request that this filter be set by default at dialog creation.
     current_filter: Option<FileFilter>,
     /// List of serialized combo boxes to add to the file chooser
-    choices: Vec<Choice>,
+    choices: Option<Vec<Choice>>,
 }
 
 impl SaveFileOptions {
@@ -320,7 +388,7 @@ impl SaveFileOptions {
 
     /// Adds a files filter.
     pub fn add_filter(mut self, filter: FileFilter) -> Self {
-        self.filters.push(filter);
+        self.filters.get_or_insert_with(Vec::new).push(filter);
         self
     }
 
@@ -332,9 +400,49 @@ impl SaveFileOptions {
 
     /// Adds a choice.
     pub fn add_choice(mut self, choice: Choice) -> Self {
-        self.choices.push(choice);
+        self.choices.get_or_insert_with(Vec::new).push(choice);
         self
     }
+
+    /// The label of the accept button.
+    pub fn get_accept_label(&self) -> Option<&str> {
+        self.accept_label.as_deref()
+    }
+
+    /// Whether the dialog should be modal.
+    pub fn get_modal(&self) -> Option<bool> {
+        self.modal
+    }
+
+    /// The current file name.
+    pub fn get_current_name(&self) -> Option<&str> {
+        self.current_name.as_deref()
+    }
+
+    /// The current folder.
+    pub fn get_current_folder(&self) -> Option<&Path> {
+        self.current_folder.as_deref().map(byte_path)
+    }
+
+    /// The absolute path of the file.
+    pub fn get_current_file(&self) -> Option<&Path> {
+        self.current_file.as_deref().map(byte_path)
+    }
+
+    /// The files filters.
+    pub fn get_filters(&self) -> &[FileFilter] {
+        self.filters.as_deref().unwrap_or_default()
+    }
+
+    /// The default filter.
+    pub fn get_current_filter(&self) -> Option<&FileFilter> {
+        self.current_filter.as_ref()
+    }
+
+    /// The choices.
+    pub fn get_choices(&self) -> &[Choice] {
+        self.choices.as_deref().unwrap_or_default()
+    }
 }
 
 #[derive(SerializeDict, DeserializeDict, TypeDict, Debug, Default)]
@@ -347,7 +455,7 @@ pub struct SaveFilesOptions {
     /// Whether the dialog should be modal.
     modal: Option<bool>,
     /// List of serialized combo boxes to add to the file chooser
-    choices: Vec<Choice>,
+    choices: Option<Vec<Choice>>,
     /// Suggested folder to save the file in.
     current_folder: Option<Vec<u8>>,
     /// An array of file names to be saved.
@@ -369,7 +477,7 @@ impl SaveFilesOptions {
 
     /// Adds a choice.
     pub fn add_choice(mut self, choice: Choice) -> Self {
-        self.choices.push(choice);
+        self.choices.get_or_insert_with(Vec::new).push(choice);
         self
     }
 
@@ -398,9 +506,43 @@ impl SaveFilesOptions {
         );
         self
     }
+
+    /// The label of the accept button.
+    pub fn get_accept_label(&self) -> Option<&str> {
+        self.accept_label.as_deref()
+    }
+
+    /// Whether the dialog should be modal.
+    pub fn get_modal(&self) -> Option<bool> {
+        self.modal
+    }
+
+    /// The choices.
+    pub fn get_choices(&self) -> &[Choice] {
+        self.choices.as_deref().unwrap_or_default()
+    }
+
+    /// The current folder.
+    pub fn get_current_folder(&self) -> Option<&Path> {
+        self.current_folder.as_deref().map(byte_path)
+    }
+
+    /// The files to save.
+    pub fn get_files(&self) -> Vec<&Path> {
+        self.files
+            .iter()
+            .flatten()
+            .map(|file| byte_path(file))
+            .collect()
+    }
 }
 
-#[derive(Debug, TypeDict, SerializeDict, Clone, DeserializeDict)]
+/// The paths are sent as nul-terminated byte strings.
+fn byte_path(bytes: &[u8]) -> &Path {
+    Path::new(OsStr::from_bytes(bytes.strip_suffix(&[0]).unwrap_or(bytes)))
+}
+
+#[derive(Debug, TypeDict, SerializeDict, Clone, DeserializeDict, Default)]
 /// A response to a
 /// [`FileChooserProxy::open_file`]/[`FileChooserProxy::save_file`]/
 /// [`FileChooserProxy::save_files`] request.
@@ -410,6 +552,20 @@ pub struct SelectedFiles {
 }
 
 impl SelectedFiles {
+    /// Adds a selected file uri.
+    pub fn uri(mut self, uri: &str) -> Self {
+        self.uris.push(uri.to_string());
+        self
+    }
+
+    /// Sets the selected value of a choice.
+    pub fn choice(mut self, key: &str, value: &str) -> Self {
+        self.choices
+            .get_or_insert_with(Vec::new)
+            .push((key.to_string(), value.to_string()));
+        self
+    }
+
     /// The selected files uris.
     pub fn uris(&self) -> &[String] {
         self.uris.as_slice()
@@ -428,7 +584,7 @@ impl SelectedFiles {
 /// Wrapper of the DBus interface: [`org.freedesktop.portal.FileChooser`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.portal.FileChooser).
 #[derive(Debug)]
 #[doc(alias = "org.freedesktop.portal.FileChooser")]
-pub struct FileChooserProxy<'a>(zbus::azync::Proxy<'a>);
+pub struct FileChooserProxy<'a>(crate::helpers::Proxy<'a>);
 
 impl<'a> FileChooserProxy<'a> {
     /// Create a new instance of [`FileChooserProxy`].
@@ -439,7 +595,7 @@ impl<'a> FileChooserProxy<'a> {
             .destination(DESTINATION)?
             .build()
             .await?;
-        Ok(Self(proxy))
+        Ok(Self(proxy.into()))
     }
 
     /// Get a reference to the underlying Proxy.
@@ -447,6 +603,10 @@ impl<'a> FileChooserProxy<'a> {
         &self.0
     }
 
+    proxy_set_timeout!();
+
+    proxy_version!(FileChooser);
+
     /// Asks to open one or more files.
     ///
     /// # Arguments
@@ -459,19 +619,22 @@ impl<'a> FileChooserProxy<'a> {
     ///
     /// See also [`OpenFile`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-portal-FileChooser.OpenFile).
     #[doc(alias = "OpenFile")]
-    pub async fn open_file(
-        &self,
-        identifier: &WindowIdentifier,
-        title: &str,
+    pub fn open_file<'r>(
+        &'r self,
+        identifier: &'r WindowIdentifier,
+        title: &'r str,
         options: OpenFileOptions,
-    ) -> Result<SelectedFiles, Error> {
-        call_request_method(
-            &self.0,
-            &options.handle_token,
-            "OpenFile",
-            &(&identifier, title, &options),
-        )
-        .await
+    ) -> Request<'r, SelectedFiles> {
+        Request::new(move |call| async move {
+            call_request_method(
+                &self.0,
+                &call,
+                &options.handle_token,
+                "OpenFile",
+                &(&identifier, title, &options),
+            )
+            .await
+        })
     }
 
     /// Asks for a location to save a file.
@@ -486,19 +649,22 @@ impl<'a> FileChooserProxy<'a> {
     ///
     /// See also [`SaveFile`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-portal-FileChooser.SaveFile).
     #[doc(alias = "SaveFile")]
-    pub async fn save_file(
-        &self,
-        identifier: &WindowIdentifier,
-        title: &str,
+    pub fn save_file<'r>(
+        &'r self,
+        identifier: &'r WindowIdentifier,
+        title: &'r str,
         options: SaveFileOptions,
-    ) -> Result<SelectedFiles, Error> {
-        call_request_method(
-            &self.0,
-            &options.handle_token,
-            "SaveFile",
-            &(&identifier, title, &options),
-        )
-        .await
+    ) -> Request<'r, SelectedFiles> {
+        Request::new(move |call| async move {
+            call_request_method(
+                &self.0,
+                &call,
+                &options.handle_token,
+                "SaveFile",
+                &(&identifier, title, &options),
+            )
+            .await
+        })
     }
 
     /// Asks for a folder as a location to save one or more files.
@@ -518,18 +684,21 @@ impl<'a> FileChooserProxy<'a> {
     ///
     /// See also [`SaveFiles`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-portal-FileChooser.SaveFiles).
     #[doc(alias = "SaveFiles")]
-    pub async fn save_files(
-        &self,
-        identifier: &WindowIdentifier,
-        title: &str,
+    pub fn save_files<'r>(
+        &'r self,
+        identifier: &'r WindowIdentifier,
+        title: &'r str,
         options: SaveFilesOptions,
-    ) -> Result<SelectedFiles, Error> {
-        call_request_method(
-            &self.0,
-            &options.handle_token,
-            "SaveFiles",
-            &(&identifier, title, &options),
-        )
-        .await
+    ) -> Request<'r, SelectedFiles> {
+        Request::new(move |call| async move {
+            call_request_method(
+                &self.0,
+                &call,
+                &options.handle_token,
+                "SaveFiles",
+                &(&identifier, title, &options),
+            )
+            .await
+        })
     }
 }
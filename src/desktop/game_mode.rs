@@ -75,7 +75,7 @@ enum RegisterStatus {
 /// Wrapper of the DBus interface: [`org.freedesktop.portal.GameMode`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.portal.GameMode).
 #[derive(Debug)]
 #[doc(alias = "org.freedesktop.portal.GameMode")]
-pub struct GameModeProxy<'a>(zbus::azync::Proxy<'a>);
+pub struct GameModeProxy<'a>(crate::helpers::Proxy<'a>);
 
 impl<'a> GameModeProxy<'a> {
     /// Create a new instance of [`GameModeProxy`].
@@ -86,7 +86,7 @@ impl<'a> GameModeProxy<'a> {
             .destination(DESTINATION)?
             .build()
             .await?;
-        Ok(Self(proxy))
+        Ok(Self(proxy.into()))
     }
 
     /// Get a reference to the underlying Proxy.
@@ -94,6 +94,10 @@ impl<'a> GameModeProxy<'a> {
         &self.0
     }
 
+    proxy_set_timeout!();
+
+    proxy_version!(GameMode);
+
     /// Query the GameMode status for a process.
     /// If the caller is running inside a sandbox with pid namespace isolation,
     /// the pid will be translated to the respective host pid.
@@ -175,7 +179,9 @@ impl<'a> GameModeProxy<'a> {
         let status = call_method(&self.0, "RegisterGame", &(pid)).await?;
         match status {
             RegisterStatus::Success => Ok(()),
-            RegisterStatus::Rejected => Err(Error::Portal(PortalError::Failed)),
+            RegisterStatus::Rejected => Err(Error::Portal(PortalError::Failed(
+                "Failed to register the game".to_owned(),
+            ))),
         }
     }
 
@@ -207,7 +213,9 @@ impl<'a> GameModeProxy<'a> {
         .await?;
         match status {
             RegisterStatus::Success => Ok(()),
-            RegisterStatus::Rejected => Err(Error::Portal(PortalError::Failed)),
+            RegisterStatus::Rejected => Err(Error::Portal(PortalError::Failed(
+                "Failed to register the game".to_owned(),
+            ))),
         }
     }
 
@@ -226,7 +234,9 @@ impl<'a> GameModeProxy<'a> {
         let status = call_method(&self.0, "RegisterGameByPid", &(target, requester)).await?;
         match status {
             RegisterStatus::Success => Ok(()),
-            RegisterStatus::Rejected => Err(Error::Portal(PortalError::Failed)),
+            RegisterStatus::Rejected => Err(Error::Portal(PortalError::Failed(
+                "Failed to register the game".to_owned(),
+            ))),
         }
     }
 
@@ -248,7 +258,9 @@ impl<'a> GameModeProxy<'a> {
         let status = call_method(&self.0, "UnregisterGame", &(pid)).await?;
         match status {
             RegisterStatus::Success => Ok(()),
-            RegisterStatus::Rejected => Err(Error::Portal(PortalError::Failed)),
+            RegisterStatus::Rejected => Err(Error::Portal(PortalError::Failed(
+                "Failed to unregister the game".to_owned(),
+            ))),
         }
     }
 
@@ -284,7 +296,9 @@ impl<'a> GameModeProxy<'a> {
         .await?;
         match status {
             RegisterStatus::Success => Ok(()),
-            RegisterStatus::Rejected => Err(Error::Portal(PortalError::Failed)),
+            RegisterStatus::Rejected => Err(Error::Portal(PortalError::Failed(
+                "Failed to unregister the game".to_owned(),
+            ))),
         }
     }
 
@@ -304,7 +318,9 @@ impl<'a> GameModeProxy<'a> {
         let status = call_method(&self.0, "UnregisterGameByPid", &(target, requester)).await?;
         match status {
             RegisterStatus::Success => Ok(()),
-            RegisterStatus::Rejected => Err(Error::Portal(PortalError::Failed)),
+            RegisterStatus::Rejected => Err(Error::Portal(PortalError::Failed(
+                "Failed to unregister the game".to_owned(),
+            ))),
         }
     }
 }
@@ -0,0 +1,404 @@
+//! # Examples
+//!
+//! How to register a global shortcut & listen to its activation.
+//!
+//! ```rust,no_run
+//! use ashpd::desktop::global_shortcuts::{GlobalShortcutsProxy, NewShortcut};
+//! use ashpd::WindowIdentifier;
+//! use futures::StreamExt;
+//!
+//! async fn run() -> ashpd::Result<()> {
+//!     let connection = zbus::azync::Connection::session().await?;
+//!     let proxy = GlobalShortcutsProxy::new(&connection).await?;
+//!
+//!     let session = proxy.create_session().await?;
+//!
+//!     let shortcuts = proxy
+//!         .bind_shortcuts(
+//!             &session,
+//!             &[NewShortcut::new("play-pause", "Play or pause the current track")
+//!                 .preferred_trigger("CTRL+ALT+P")],
+//!             &WindowIdentifier::default(),
+//!         )
+//!         .await?;
+//!     for shortcut in shortcuts {
+//!         if let Some(trigger) = shortcut.trigger_description() {
+//!             println!("{}: {}", shortcut.id(), trigger);
+//!         }
+//!     }
+//!
+//!     let activations = proxy.receive_activated_stream().await?;
+//!     futures::pin_mut!(activations);
+//!     while let Some(activation) = activations.next().await {
+//!         println!("{} was activated", activation.shortcut_id());
+//!     }
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use std::{collections::HashMap, fmt::Debug, time::Duration};
+
+use futures::{Stream, TryFutureExt};
+use serde::{Deserialize, Serialize};
+use zvariant::{OwnedObjectPath, OwnedValue};
+use zvariant_derive::{DeserializeDict, SerializeDict, Type, TypeDict};
+
+use super::{HandleToken, Request, SessionProxy, DESTINATION, PATH};
+use crate::{
+    helpers::{call_request_method, receive_signal, receive_signal_stream},
+    Error, WindowIdentifier,
+};
+
+#[derive(SerializeDict, DeserializeDict, TypeDict, Debug, Default)]
+/// Specified options for a [`GlobalShortcutsProxy::create_session`] request.
+struct CreateSessionOptions {
+    /// A string that will be used as the last element of the handle.
+    handle_token: HandleToken,
+    /// A string that will be used as the last element of the session handle.
+    session_handle_token: HandleToken,
+}
+
+#[derive(SerializeDict, DeserializeDict, TypeDict, Debug)]
+/// A response to a [`GlobalShortcutsProxy::create_session`] request.
+struct CreateSession {
+    /// The session handle.
+    session_handle: String,
+}
+
+#[derive(SerializeDict, DeserializeDict, TypeDict, Debug, Default)]
+/// Specified options for a [`GlobalShortcutsProxy::bind_shortcuts`] request.
+struct BindShortcutsOptions {
+    /// A string that will be used as the last element of the handle.
+    handle_token: HandleToken,
+}
+
+#[derive(SerializeDict, DeserializeDict, TypeDict, Debug, Default)]
+/// Specified options for a [`GlobalShortcutsProxy::list_shortcuts`] request.
+struct ListShortcutsOptions {
+    /// A string that will be used as the last element of the handle.
+    handle_token: HandleToken,
+}
+
+#[derive(SerializeDict, DeserializeDict, TypeDict, Debug)]
+/// A response to a [`GlobalShortcutsProxy::bind_shortcuts`] or a
+/// [`GlobalShortcutsProxy::list_shortcuts`] request.
+struct Shortcuts {
+    shortcuts: Vec<Shortcut>,
+}
+
+#[derive(SerializeDict, DeserializeDict, TypeDict, Debug, Clone)]
+/// The properties of a shortcut to bind.
+struct NewShortcutInfo {
+    /// User-readable text describing what the shortcut does.
+    description: String,
+    /// The preferred shortcut trigger, defined as described by the "shortcuts"
+    /// XDG specification.
+    preferred_trigger: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Type, Debug, Clone)]
+/// A shortcut to bind with [`GlobalShortcutsProxy::bind_shortcuts`].
+pub struct NewShortcut(String, NewShortcutInfo);
+
+impl NewShortcut {
+    /// Create a new shortcut.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The application-provided ID of the shortcut.
+    /// * `description` - User-readable text describing what the shortcut does.
+    pub fn new(id: &str, description: &str) -> Self {
+        Self(
+            id.to_string(),
+            NewShortcutInfo {
+                description: description.to_string(),
+                preferred_trigger: None,
+            },
+        )
+    }
+
+    /// Sets the preferred trigger of the shortcut, e.g `CTRL+ALT+P`.
+    ///
+    /// The portal may assign a different trigger.
+    pub fn preferred_trigger(mut self, preferred_trigger: &str) -> Self {
+        self.1.preferred_trigger = Some(preferred_trigger.to_string());
+        self
+    }
+}
+
+#[derive(SerializeDict, DeserializeDict, TypeDict, Debug, Clone)]
+/// The properties of a bound shortcut.
+struct ShortcutInfo {
+    /// User-readable text describing what the shortcut does.
+    description: String,
+    /// User-readable text describing how to trigger the shortcut.
+    trigger_description: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Type, Debug, Clone)]
+/// A shortcut bound by the portal.
+pub struct Shortcut(String, ShortcutInfo);
+
+impl Shortcut {
+    /// The application-provided ID of the shortcut.
+    pub fn id(&self) -> &str {
+        &self.0
+    }
+
+    /// User-readable text describing what the shortcut does.
+    pub fn description(&self) -> &str {
+        &self.1.description
+    }
+
+    /// User-readable text describing how to trigger the shortcut, if the
+    /// portal provided one.
+    pub fn trigger_description(&self) -> Option<&str> {
+        self.1.trigger_description.as_deref()
+    }
+}
+
+#[derive(Serialize, Deserialize, Type, Debug)]
+/// A shortcut was activated or deactivated, received with the
+/// [`GlobalShortcutsProxy::receive_activated`] or the
+/// [`GlobalShortcutsProxy::receive_deactivated`] signals.
+pub struct ShortcutEvent(OwnedObjectPath, String, u64, HashMap<String, OwnedValue>);
+
+impl ShortcutEvent {
+    /// The session the shortcut belongs to.
+    pub fn session_handle(&self) -> &OwnedObjectPath {
+        &self.0
+    }
+
+    /// The application-provided ID of the shortcut.
+    pub fn shortcut_id(&self) -> &str {
+        &self.1
+    }
+
+    /// The time of the event, in milliseconds with an undefined base.
+    pub fn timestamp(&self) -> Duration {
+        Duration::from_millis(self.2)
+    }
+}
+
+#[derive(Serialize, Deserialize, Type, Debug)]
+/// The shortcuts of a session changed, received with the
+/// [`GlobalShortcutsProxy::receive_shortcuts_changed`] signal.
+pub struct ShortcutsChanged(OwnedObjectPath, Vec<Shortcut>);
+
+impl ShortcutsChanged {
+    /// The session the shortcuts belong to.
+    pub fn session_handle(&self) -> &OwnedObjectPath {
+        &self.0
+    }
+
+    /// The bound shortcuts.
+    pub fn shortcuts(&self) -> &[Shortcut] {
+        &self.1
+    }
+}
+
+/// The interface lets applications create global shortcuts sessions, and
+/// register shortcuts with them. These shortcuts are activated regardless of
+/// the focused state of the application window.
+///
+/// Wrapper of the DBus interface: [`org.freedesktop.portal.GlobalShortcuts`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.portal.GlobalShortcuts).
+#[derive(Debug)]
+#[doc(alias = "org.freedesktop.portal.GlobalShortcuts")]
+pub struct GlobalShortcutsProxy<'a>(crate::helpers::Proxy<'a>);
+
+impl<'a> GlobalShortcutsProxy<'a> {
+    /// Create a new instance of [`GlobalShortcutsProxy`].
+    pub async fn new(
+        connection: &zbus::azync::Connection,
+    ) -> Result<GlobalShortcutsProxy<'a>, Error> {
+        let proxy = zbus::azync::ProxyBuilder::new_bare(connection)
+            .interface("org.freedesktop.portal.GlobalShortcuts")?
+            .path(PATH)?
+            .destination(DESTINATION)?
+            .build()
+            .await?;
+        Ok(Self(proxy.into()))
+    }
+
+    /// Get a reference to the underlying Proxy.
+    pub fn inner(&self) -> &zbus::azync::Proxy<'_> {
+        &self.0
+    }
+
+    proxy_set_timeout!();
+
+    proxy_version!(GlobalShortcuts);
+
+    /// Create a global shortcuts session.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`CreateSession`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-portal-GlobalShortcuts.CreateSession).
+    #[doc(alias = "CreateSession")]
+    pub fn create_session(&self) -> Request<'_, SessionProxy<'a>> {
+        let options = CreateSessionOptions::default();
+        Request::new(move |call| async move {
+            let (session, proxy) = futures::try_join!(
+                call_request_method::<CreateSession, CreateSessionOptions>(
+                    &self.0,
+                    &call,
+                    &options.handle_token,
+                    "CreateSession",
+                    &options
+                )
+                .into_future(),
+                SessionProxy::from_unique_name(self.0.connection(), &options.session_handle_token)
+                    .into_future(),
+            )?;
+            proxy.resolve(&session.session_handle).await
+        })
+    }
+
+    /// Bind the shortcuts of a session.
+    ///
+    /// This will typically result in the portal presenting a dialog letting
+    /// the user configure the triggers of the shortcuts.
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - A [`SessionProxy`], created with
+    ///   [`create_session()`][`GlobalShortcutsProxy::create_session`].
+    /// * `shortcuts` - The shortcuts to bind.
+    /// * `identifier` - Identifier for the application window.
+    ///
+    /// # Returns
+    ///
+    /// The shortcuts that were bound.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`BindShortcuts`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-portal-GlobalShortcuts.BindShortcuts).
+    #[doc(alias = "BindShortcuts")]
+    pub fn bind_shortcuts<'r>(
+        &'r self,
+        session: &'r SessionProxy<'_>,
+        shortcuts: &'r [NewShortcut],
+        identifier: &'r WindowIdentifier,
+    ) -> Request<'r, Vec<Shortcut>> {
+        let options = BindShortcutsOptions::default();
+        Request::new(move |call| async move {
+            let response: Shortcuts = call_request_method(
+                &self.0,
+                &call,
+                &options.handle_token,
+                "BindShortcuts",
+                &(session, shortcuts, &identifier, &options),
+            )
+            .await?;
+            Ok(response.shortcuts)
+        })
+    }
+
+    /// List the shortcuts bound in a session.
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - A [`SessionProxy`], created with
+    ///   [`create_session()`][`GlobalShortcutsProxy::create_session`].
+    ///
+    /// # Specifications
+    ///
+    /// See also [`ListShortcuts`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-portal-GlobalShortcuts.ListShortcuts).
+    #[doc(alias = "ListShortcuts")]
+    pub fn list_shortcuts<'r>(
+        &'r self,
+        session: &'r SessionProxy<'_>,
+    ) -> Request<'r, Vec<Shortcut>> {
+        let options = ListShortcutsOptions::default();
+        Request::new(move |call| async move {
+            let response: Shortcuts = call_request_method(
+                &self.0,
+                &call,
+                &options.handle_token,
+                "ListShortcuts",
+                &(session, &options),
+            )
+            .await?;
+            Ok(response.shortcuts)
+        })
+    }
+
+    /// Signal emitted when a shortcut is activated.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`Activated`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-signal-org-freedesktop-portal-GlobalShortcuts.Activated).
+    #[doc(alias = "Activated")]
+    pub async fn receive_activated(&self) -> Result<ShortcutEvent, Error> {
+        receive_signal(&self.0, "Activated").await
+    }
+
+    /// Signal emitted when a shortcut is activated.
+    ///
+    /// Unlike [`receive_activated()`][`GlobalShortcutsProxy::receive_activated`],
+    /// the returned stream stays subscribed and yields every emitted signal.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`Activated`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-signal-org-freedesktop-portal-GlobalShortcuts.Activated).
+    #[doc(alias = "Activated")]
+    pub async fn receive_activated_stream(
+        &self,
+    ) -> Result<impl Stream<Item = ShortcutEvent> + '_, Error> {
+        receive_signal_stream(&self.0, "Activated").await
+    }
+
+    /// Signal emitted when a shortcut is deactivated.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`Deactivated`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-signal-org-freedesktop-portal-GlobalShortcuts.Deactivated).
+    #[doc(alias = "Deactivated")]
+    pub async fn receive_deactivated(&self) -> Result<ShortcutEvent, Error> {
+        receive_signal(&self.0, "Deactivated").await
+    }
+
+    /// Signal emitted when a shortcut is deactivated.
+    ///
+    /// Unlike
+    /// [`receive_deactivated()`][`GlobalShortcutsProxy::receive_deactivated`],
+    /// the returned stream stays subscribed and yields every emitted signal.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`Deactivated`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-signal-org-freedesktop-portal-GlobalShortcuts.Deactivated).
+    #[doc(alias = "Deactivated")]
+    pub async fn receive_deactivated_stream(
+        &self,
+    ) -> Result<impl Stream<Item = ShortcutEvent> + '_, Error> {
+        receive_signal_stream(&self.0, "Deactivated").await
+    }
+
+    /// Signal emitted when the shortcuts of a session changed, e.g. after the
+    /// user configured their triggers.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`ShortcutsChanged`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-signal-org-freedesktop-portal-GlobalShortcuts.ShortcutsChanged).
+    #[doc(alias = "ShortcutsChanged")]
+    pub async fn receive_shortcuts_changed(&self) -> Result<ShortcutsChanged, Error> {
+        receive_signal(&self.0, "ShortcutsChanged").await
+    }
+
+    /// Signal emitted when the shortcuts of a session changed.
+    ///
+    /// Unlike
+    /// [`receive_shortcuts_changed()`][`GlobalShortcutsProxy::receive_shortcuts_changed`],
+    /// the returned stream stays subscribed and yields every emitted signal.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`ShortcutsChanged`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-signal-org-freedesktop-portal-GlobalShortcuts.ShortcutsChanged).
+    #[doc(alias = "ShortcutsChanged")]
+    pub async fn receive_shortcuts_changed_stream(
+        &self,
+    ) -> Result<impl Stream<Item = ShortcutsChanged> + '_, Error> {
+        receive_signal_stream(&self.0, "ShortcutsChanged").await
+    }
+}
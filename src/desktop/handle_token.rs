@@ -34,9 +34,18 @@ impl Default for HandleToken {
     }
 }
 
-#[derive(Debug)]
+/// The error returned when creating a handle token from a string containing
+/// a character not allowed in an object path element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct HandleInvalidCharacter(char);
 
+impl HandleInvalidCharacter {
+    /// The invalid character.
+    pub fn character(&self) -> char {
+        self.0
+    }
+}
+
 impl std::fmt::Display for HandleInvalidCharacter {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_fmt(format_args!("Invalid Character {}", self.0))
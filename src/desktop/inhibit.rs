@@ -18,7 +18,7 @@
 //!     match state.session_state() {
 //!         SessionState::Running => (),
 //!         SessionState::QueryEnd => {
-//!             proxy
+//!             let inhibitor = proxy
 //!                 .inhibit(
 //!                     &identifier,
 //!                     InhibitFlags::Logout | InhibitFlags::UserSwitch,
@@ -27,6 +27,8 @@
 //!                 .await?;
 //!             thread::sleep(time::Duration::from_secs(1));
 //!             proxy.query_end_response(&session).await?;
+//!             // Let the session end.
+//!             inhibitor.release().await?;
 //!         }
 //!         SessionState::Ending => {
 //!             println!("ending the session");
@@ -36,16 +38,32 @@
 //! }
 //! ```
 
+use std::{
+    fmt::Debug,
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
 use enumflags2::BitFlags;
-use futures::TryFutureExt;
+use futures::{
+    channel::{mpsc, oneshot},
+    future::{self, Either},
+    stream, Stream, StreamExt, TryFutureExt,
+};
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
-use zvariant::OwnedObjectPath;
+use zvariant::{ObjectPath, OwnedObjectPath};
 use zvariant_derive::{DeserializeDict, SerializeDict, Type, TypeDict};
 
-use super::{HandleToken, SessionProxy, DESTINATION, PATH};
+use super::{
+    request::RequestProxy, session::spawn, HandleToken, Request, SessionProxy, DESTINATION, PATH,
+};
 use crate::{
-    helpers::{call_basic_response_method, call_method, call_request_method, receive_signal},
+    helpers::{call_method, call_request_method, receive_signal, receive_signal_stream},
     Error, WindowIdentifier,
 };
 
@@ -99,11 +117,11 @@ struct CreateMonitor {
 
 #[derive(Debug, SerializeDict, DeserializeDict, TypeDict)]
 #[doc(hidden)]
-struct State {
+pub(crate) struct State {
     #[zvariant(rename = "screensaver-active")]
-    screensaver_active: bool,
+    pub(crate) screensaver_active: bool,
     #[zvariant(rename = "session-state")]
-    session_state: SessionState,
+    pub(crate) session_state: SessionState,
 }
 
 #[derive(Debug, Serialize, Deserialize, Type)]
@@ -134,13 +152,57 @@ pub enum SessionState {
     Ending = 3,
 }
 
+/// An inhibition made with [`InhibitProxy::inhibit`].
+///
+/// The inhibition lasts until released with [`InhibitGuard::release`] or
+/// dropped. As dropping can't wait, the release then runs on the executor set
+/// with [`set_session_executor`](crate::desktop::set_session_executor).
+pub struct InhibitGuard(Option<RequestProxy<'static>>);
+
+impl InhibitGuard {
+    /// The object path of the request backing the inhibition.
+    pub fn path(&self) -> &ObjectPath<'_> {
+        self.0.as_ref().unwrap().inner().path()
+    }
+
+    /// Releases the inhibition.
+    pub async fn release(mut self) -> Result<(), Error> {
+        let request = self.0.take().unwrap();
+        request.close().await
+    }
+}
+
+impl Drop for InhibitGuard {
+    fn drop(&mut self) {
+        if let Some(request) = self.0.take() {
+            tracing::info!(
+                "Releasing the dropped inhibition {}",
+                request.inner().path()
+            );
+            spawn(Box::pin(async move {
+                if let Err(err) = request.close().await {
+                    tracing::warn!("Failed to release the dropped inhibition: {}", err);
+                }
+            }));
+        }
+    }
+}
+
+impl Debug for InhibitGuard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("InhibitGuard")
+            .field(&self.path().as_str())
+            .finish()
+    }
+}
+
 /// The interface lets sandboxed applications inhibit the user session from
 /// ending, suspending, idling or getting switched away.
 ///
 /// Wrapper of the DBus interface: [`org.freedesktop.portal.Inhibit`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.portal.Inhibit).
 #[derive(Debug)]
 #[doc(alias = "org.freedesktop.portal.Inhibit")]
-pub struct InhibitProxy<'a>(zbus::azync::Proxy<'a>);
+pub struct InhibitProxy<'a>(crate::helpers::Proxy<'a>);
 
 impl<'a> InhibitProxy<'a> {
     /// Create a new instance of [`InhibitProxy`].
@@ -151,7 +213,7 @@ impl<'a> InhibitProxy<'a> {
             .destination(DESTINATION)?
             .build()
             .await?;
-        Ok(Self(proxy))
+        Ok(Self(proxy.into()))
     }
 
     /// Get a reference to the underlying Proxy.
@@ -159,6 +221,10 @@ impl<'a> InhibitProxy<'a> {
         &self.0
     }
 
+    proxy_set_timeout!();
+
+    proxy_version!(Inhibit);
+
     /// Creates a monitoring session.
     /// While this session is active, the caller will receive `state_changed`
     /// signals with updates on the session state.
@@ -171,24 +237,28 @@ impl<'a> InhibitProxy<'a> {
     ///
     /// See also [`CreateMonitor`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-portal-Inhibit.CreateMonitor).
     #[doc(alias = "CreateMonitor")]
-    pub async fn create_monitor(
-        &self,
-        identifier: &WindowIdentifier,
-    ) -> Result<SessionProxy<'a>, Error> {
+    pub fn create_monitor<'r>(
+        &'r self,
+        identifier: &'r WindowIdentifier,
+    ) -> Request<'r, SessionProxy<'a>> {
         let options = CreateMonitorOptions::default();
-        let body = &(&identifier, &options);
-        let (monitor, proxy): (CreateMonitor, SessionProxy) = futures::try_join!(
-            call_request_method(&self.0, &options.handle_token, "CreateMonitor", body)
-                .into_future(),
-            SessionProxy::from_unique_name(self.0.connection(), &options.session_handle_token)
-                .into_future(),
-        )?;
-        assert_eq!(proxy.inner().path().as_str(), &monitor.session_handle);
-        Ok(proxy)
+        Request::new(move |call| async move {
+            let body = &(&identifier, &options);
+            let (monitor, proxy): (CreateMonitor, SessionProxy) = futures::try_join!(
+                call_request_method(&self.0, &call, &options.handle_token, "CreateMonitor", body)
+                    .into_future(),
+                SessionProxy::from_unique_name(self.0.connection(), &options.session_handle_token)
+                    .into_future(),
+            )?;
+            proxy.resolve(&monitor.session_handle).await
+        })
     }
 
     /// Inhibits a session status changes.
     ///
+    /// The inhibition lasts until the returned [`InhibitGuard`] is released or
+    /// dropped.
+    ///
     /// # Arguments
     ///
     /// * `identifier` - The application window identifier.
@@ -204,15 +274,41 @@ impl<'a> InhibitProxy<'a> {
         identifier: &WindowIdentifier,
         flags: BitFlags<InhibitFlags>,
         reason: &str,
-    ) -> Result<(), Error> {
+    ) -> Result<InhibitGuard, Error> {
         let options = InhibitOptions::default().reason(reason);
-        call_basic_response_method(
-            &self.0,
-            &options.handle_token,
-            "Inhibit",
-            &(&identifier, flags, &options),
-        )
-        .await
+        // The portal doesn't emit a response, the inhibition is active once
+        // the request handle is returned.
+        let path: OwnedObjectPath =
+            call_method(&self.0, "Inhibit", &(&identifier, flags, &options)).await?;
+        let request = RequestProxy::new(self.0.connection(), path.into_inner()).await?;
+        Ok(InhibitGuard(Some(request)))
+    }
+
+    /// Inhibits the session status changes in `flags` until `future`
+    /// completes, then releases the inhibition.
+    ///
+    /// The output of `future` is returned even if releasing the inhibition
+    /// fails, the failure is logged instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `identifier` - The application window identifier.
+    /// * `flags` - The flags determine what changes are inhibited.
+    /// * `reason` - User-visible reason for the inhibition.
+    /// * `future` - The work to carry out while inhibited.
+    pub async fn inhibit_while<F: Future>(
+        &self,
+        identifier: &WindowIdentifier,
+        flags: BitFlags<InhibitFlags>,
+        reason: &str,
+        future: F,
+    ) -> Result<F::Output, Error> {
+        let inhibitor = self.inhibit(identifier, flags, reason).await?;
+        let output = future.await;
+        if let Err(err) = inhibitor.release().await {
+            tracing::warn!("Failed to release the inhibition: {}", err);
+        }
+        Ok(output)
     }
 
     /// Signal emitted when the session state changes.
@@ -225,6 +321,22 @@ impl<'a> InhibitProxy<'a> {
         receive_signal(&self.0, "StateChanged").await
     }
 
+    /// Signal emitted when the session state changes.
+    ///
+    /// Unlike
+    /// [`receive_state_changed()`][`InhibitProxy::receive_state_changed`],
+    /// the returned stream stays subscribed and yields every emitted signal.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`StateChanged`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-signal-org-freedesktop-portal-Inhibit.StateChanged).
+    #[doc(alias = "StateChanged")]
+    pub async fn receive_state_changed_stream(
+        &self,
+    ) -> Result<impl Stream<Item = InhibitState> + '_, Error> {
+        receive_signal_stream(&self.0, "StateChanged").await
+    }
+
     /// Acknowledges that the caller received the "state_changed" signal.
     /// This method should be called within one second after receiving a
     /// [`receive_state_changed()`][`InhibitProxy::receive_state_changed`]
@@ -243,3 +355,270 @@ impl<'a> InhibitProxy<'a> {
         call_method(&self.0, "QueryEndResponse", &(session)).await
     }
 }
+
+/// How long the portal waits for a [`QueryEndResponder`] to respond.
+const QUERY_END_DEADLINE: Duration = Duration::from_secs(1);
+
+/// How early before the portal's deadline a [`QueryEndResponder`] responds
+/// by itself, so the response reaches the portal in time.
+const QUERY_END_MARGIN: Duration = Duration::from_millis(200);
+
+/// Watches the user's session for it ending, on top of an
+/// [`InhibitProxy::create_monitor`] session.
+///
+/// ```rust,no_run
+/// use ashpd::desktop::inhibit::{InhibitFlags, SessionEndEvent, SessionEndMonitor};
+/// use ashpd::WindowIdentifier;
+/// use futures::StreamExt;
+///
+/// async fn run() -> ashpd::Result<()> {
+///     let connection = zbus::azync::Connection::session().await?;
+///     let identifier = WindowIdentifier::default();
+///     let monitor = SessionEndMonitor::new(&connection, &identifier).await?;
+///
+///     let events = monitor.receive_events().await?;
+///     futures::pin_mut!(events);
+///     while let Some(event) = events.next().await {
+///         match event {
+///             SessionEndEvent::QueryEnd(responder) => {
+///                 let inhibitor = responder
+///                     .inhibit_and_respond(
+///                         &identifier,
+///                         InhibitFlags::Logout.into(),
+///                         "saving the opened documents",
+///                     )
+///                     .await?;
+///                 // Save the documents...
+///                 inhibitor.release().await?;
+///             }
+///             SessionEndEvent::Ending => break,
+///             _ => (),
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct SessionEndMonitor {
+    proxy: Arc<InhibitProxy<'static>>,
+    session: SessionProxy<'static>,
+}
+
+impl SessionEndMonitor {
+    /// Create a monitoring session for the window `identifier`.
+    pub async fn new(
+        connection: &zbus::azync::Connection,
+        identifier: &WindowIdentifier,
+    ) -> Result<SessionEndMonitor, Error> {
+        let proxy = InhibitProxy::new(connection).await?;
+        let session = proxy.create_monitor(identifier).await?;
+        Ok(Self {
+            proxy: Arc::new(proxy),
+            session,
+        })
+    }
+
+    /// The monitoring session, closed when the monitor is dropped.
+    pub fn session(&self) -> &SessionProxy<'static> {
+        &self.session
+    }
+
+    /// The changes of the state of the user's session.
+    ///
+    /// A [`SessionEndEvent::Running`] or [`SessionEndEvent::Ending`] event is
+    /// yielded when the state changes, a [`SessionEndEvent::QueryEnd`] event
+    /// each time the session is about to end.
+    ///
+    /// The signals are received on the executor set with
+    /// [`set_session_executor`](crate::desktop::set_session_executor), so the
+    /// deadline of a [`QueryEndResponder`] runs from the moment the portal
+    /// emitted the query rather than from the moment the stream is polled.
+    pub async fn receive_events(&self) -> Result<impl Stream<Item = SessionEndEvent>, Error> {
+        let (subscribed_sender, subscribed) = oneshot::channel();
+        let (mut dropped, alive) = oneshot::channel::<()>();
+        let (sender, received) = mpsc::unbounded();
+        let proxy = self.proxy.clone();
+        spawn(Box::pin(async move {
+            let states = match proxy.receive_state_changed_stream().await {
+                Ok(states) => {
+                    let _ = subscribed_sender.send(Ok(()));
+                    states
+                }
+                Err(err) => {
+                    let _ = subscribed_sender.send(Err(err));
+                    return;
+                }
+            };
+            futures::pin_mut!(states);
+            loop {
+                let state = match future::select(states.next(), dropped.cancellation()).await {
+                    Either::Left((Some(state), _)) => state,
+                    // The signal stream ended or the events were dropped.
+                    _ => break,
+                };
+                if sender.unbounded_send((Instant::now(), state)).is_err() {
+                    break;
+                }
+            }
+        }));
+        subscribed.await.map_err(|_| Error::SignalStreamEnded)??;
+
+        let proxy = self.proxy.clone();
+        let session = OwnedObjectPath::from(self.session.inner().path().clone());
+        let mut last_state = SessionState::Running;
+        let mut screensaver_active = false;
+
+        Ok(received
+            .filter(move |(_, state)| future::ready(state.0 == session))
+            .flat_map(move |(received_at, state)| {
+                // Keep the receiving task alive for as long as the events are.
+                let _ = &alive;
+                let mut events = Vec::new();
+                let session_state = state.session_state();
+                if state.screensaver_active() != screensaver_active {
+                    screensaver_active = state.screensaver_active();
+                    events.push(SessionEndEvent::ScreensaverChanged(screensaver_active));
+                }
+                match session_state {
+                    SessionState::QueryEnd => {
+                        events.push(SessionEndEvent::QueryEnd(QueryEndResponder::new(
+                            proxy.clone(),
+                            state.0,
+                            received_at + QUERY_END_DEADLINE - QUERY_END_MARGIN,
+                        )))
+                    }
+                    SessionState::Running if last_state != SessionState::Running => {
+                        events.push(SessionEndEvent::Running)
+                    }
+                    SessionState::Ending if last_state != SessionState::Ending => {
+                        events.push(SessionEndEvent::Ending)
+                    }
+                    _ => (),
+                }
+                last_state = session_state;
+                stream::iter(events)
+            }))
+    }
+}
+
+/// A change of the user's session, see
+/// [`SessionEndMonitor::receive_events`].
+#[derive(Debug)]
+pub enum SessionEndEvent {
+    /// The session is running again, ending it was cancelled.
+    Running,
+    /// The user asked to end the session, use the responder to acknowledge it.
+    QueryEnd(QueryEndResponder),
+    /// The session is ending.
+    Ending,
+    /// The screensaver got activated or deactivated.
+    ScreensaverChanged(bool),
+}
+
+/// Acknowledges a [`SessionEndEvent::QueryEnd`] event.
+///
+/// The portal waits for a response for one second, at most, before ending the
+/// session. Inhibit the session end with
+/// [`QueryEndResponder::inhibit_and_respond`] to get more time. The responder
+/// responds by itself at its [`deadline`][`QueryEndResponder::deadline`],
+/// slightly ahead of the portal's, or when dropped, if it wasn't used.
+pub struct QueryEndResponder {
+    proxy: Arc<InhibitProxy<'static>>,
+    session: OwnedObjectPath,
+    deadline: Instant,
+    /// Set once the query got a response, from the responder or its timer.
+    responded: Arc<AtomicBool>,
+}
+
+impl QueryEndResponder {
+    fn new(proxy: Arc<InhibitProxy<'static>>, session: OwnedObjectPath, deadline: Instant) -> Self {
+        let responded = Arc::new(AtomicBool::new(false));
+        let timer = {
+            let proxy = proxy.clone();
+            let session = session.clone();
+            let responded = responded.clone();
+            async move {
+                async_io::Timer::at(deadline).await;
+                if !responded.swap(true, Ordering::SeqCst) {
+                    tracing::info!("Responding to the session end query at the deadline");
+                    if let Err(err) = query_end_response(&proxy, &session).await {
+                        tracing::warn!("Failed to respond to the session end query: {}", err);
+                    }
+                }
+            }
+        };
+        spawn(Box::pin(timer));
+        Self {
+            proxy,
+            session,
+            deadline,
+            responded,
+        }
+    }
+
+    /// The moment the responder responds by itself, 200ms before the portal
+    /// stops waiting for the response.
+    pub fn deadline(&self) -> Instant {
+        self.deadline
+    }
+
+    /// Responds to the query, letting the session end unless inhibited.
+    ///
+    /// Nothing is sent if the responder already responded at the deadline.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`QueryEndResponse`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-signal-org-freedesktop-portal-Inhibit.QueryEndResponse).
+    #[doc(alias = "QueryEndResponse")]
+    pub async fn respond(self) -> Result<(), Error> {
+        if self.responded.swap(true, Ordering::SeqCst) {
+            tracing::warn!("The session end query was already responded to at the deadline");
+            return Ok(());
+        }
+        query_end_response(&self.proxy, &self.session).await
+    }
+
+    /// Inhibits the session end with [`InhibitProxy::inhibit`], then
+    /// responds to the query.
+    pub async fn inhibit_and_respond(
+        self,
+        identifier: &WindowIdentifier,
+        flags: BitFlags<InhibitFlags>,
+        reason: &str,
+    ) -> Result<InhibitGuard, Error> {
+        let inhibitor = self.proxy.inhibit(identifier, flags, reason).await?;
+        self.respond().await?;
+        Ok(inhibitor)
+    }
+}
+
+impl Drop for QueryEndResponder {
+    fn drop(&mut self) {
+        if self.responded.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let proxy = self.proxy.clone();
+        let session = self.session.clone();
+        spawn(Box::pin(async move {
+            if let Err(err) = query_end_response(&proxy, &session).await {
+                tracing::warn!("Failed to respond to the session end query: {}", err);
+            }
+        }));
+    }
+}
+
+impl Debug for QueryEndResponder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QueryEndResponder")
+            .field("session", &self.session.as_str())
+            .field("deadline", &self.deadline)
+            .finish()
+    }
+}
+
+async fn query_end_response(
+    proxy: &InhibitProxy<'_>,
+    session: &OwnedObjectPath,
+) -> Result<(), Error> {
+    call_method(&proxy.0, "QueryEndResponse", &(session)).await
+}
@@ -28,15 +28,15 @@
 
 use std::fmt::Debug;
 
-use futures::TryFutureExt;
+use futures::{Stream, TryFutureExt};
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use zvariant::OwnedObjectPath;
 use zvariant_derive::{DeserializeDict, SerializeDict, Type, TypeDict};
 
-use super::{HandleToken, SessionProxy, DESTINATION, PATH};
+use super::{HandleToken, Request, SessionProxy, DESTINATION, PATH};
 use crate::{
-    helpers::{call_basic_response_method, call_method, receive_signal},
+    helpers::{call_basic_response_method, call_method, receive_signal, receive_signal_stream},
     Error, WindowIdentifier,
 };
 
@@ -186,7 +186,7 @@ struct LocationInner {
 /// Wrapper of the DBus interface: [`org.freedesktop.portal.Location`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.portal.Location).
 #[derive(Debug)]
 #[doc(alias = "org.freedesktop.portal.Location")]
-pub struct LocationProxy<'a>(zbus::azync::Proxy<'a>);
+pub struct LocationProxy<'a>(crate::helpers::Proxy<'a>);
 
 impl<'a> LocationProxy<'a> {
     /// Create a new instance of [`LocationProxy`].
@@ -197,7 +197,7 @@ impl<'a> LocationProxy<'a> {
             .destination(DESTINATION)?
             .build()
             .await?;
-        Ok(Self(proxy))
+        Ok(Self(proxy.into()))
     }
 
     /// Get a reference to the underlying Proxy.
@@ -205,6 +205,10 @@ impl<'a> LocationProxy<'a> {
         &self.0
     }
 
+    proxy_set_timeout!();
+
+    proxy_version!(Location);
+
     /// Signal emitted when the user location is updated.
     ///
     /// # Specifications
@@ -215,6 +219,22 @@ impl<'a> LocationProxy<'a> {
         receive_signal(&self.0, "LocationUpdated").await
     }
 
+    /// Signal emitted when the user location is updated.
+    ///
+    /// Unlike
+    /// [`receive_location_updated()`][`LocationProxy::receive_location_updated`],
+    /// the returned stream stays subscribed and yields every emitted signal.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`LocationUpdated`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-signal-org-freedesktop-portal-Location.LocationUpdated).
+    #[doc(alias = "LocationUpdated")]
+    pub async fn receive_location_updated_stream(
+        &self,
+    ) -> Result<impl Stream<Item = Location> + '_, Error> {
+        receive_signal_stream(&self.0, "LocationUpdated").await
+    }
+
     /// Create a location session.
     ///
     /// # Arguments
@@ -249,8 +269,7 @@ impl<'a> LocationProxy<'a> {
             SessionProxy::from_unique_name(self.0.connection(), &options.session_handle_token)
                 .into_future(),
         )?;
-        assert_eq!(proxy.inner().path(), &path.into_inner());
-        Ok(proxy)
+        proxy.resolve(path.as_str()).await
     }
 
     /// Start the location session.
@@ -266,18 +285,21 @@ impl<'a> LocationProxy<'a> {
     ///
     /// See also [`Start`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-portal-Location.Start).
     #[doc(alias = "Start")]
-    pub async fn start(
-        &self,
-        session: &SessionProxy<'_>,
-        identifier: &WindowIdentifier,
-    ) -> Result<(), Error> {
-        let options = SessionStartOptions::default();
-        call_basic_response_method(
-            &self.0,
-            &options.handle_token,
-            "Start",
-            &(session, &identifier, &options),
-        )
-        .await
+    pub fn start<'r>(
+        &'r self,
+        session: &'r SessionProxy<'_>,
+        identifier: &'r WindowIdentifier,
+    ) -> Request<'r, ()> {
+        Request::new(move |call| async move {
+            let options = SessionStartOptions::default();
+            call_basic_response_method(
+                &self.0,
+                &call,
+                &options.handle_token,
+                "Start",
+                &(session, &identifier, &options),
+            )
+            .await
+        })
     }
 }
@@ -14,8 +14,13 @@
 //! }
 //! ```
 
+use futures::Stream;
+
 use super::{DESTINATION, PATH};
-use crate::{helpers::receive_signal, Error};
+use crate::{
+    helpers::{receive_signal, receive_signal_stream},
+    Error,
+};
 
 /// The interface provides information about low system memory to sandboxed
 /// applications. It is not a portal in the strict sense, since it does not
@@ -24,7 +29,7 @@ use crate::{helpers::receive_signal, Error};
 /// Wrapper of the DBus interface: [`org.freedesktop.portal.MemoryMonitor`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.portal.MemoryMonitor).
 #[derive(Debug)]
 #[doc(alias = "org.freedesktop.portal.MemoryMonitor")]
-pub struct MemoryMonitorProxy<'a>(zbus::azync::Proxy<'a>);
+pub struct MemoryMonitorProxy<'a>(crate::helpers::Proxy<'a>);
 
 impl<'a> MemoryMonitorProxy<'a> {
     /// Create a new instance of [`MemoryMonitorProxy`].
@@ -37,7 +42,7 @@ impl<'a> MemoryMonitorProxy<'a> {
             .destination(DESTINATION)?
             .build()
             .await?;
-        Ok(Self(proxy))
+        Ok(Self(proxy.into()))
     }
 
     /// Get a reference to the underlying Proxy.
@@ -45,6 +50,10 @@ impl<'a> MemoryMonitorProxy<'a> {
         &self.0
     }
 
+    proxy_set_timeout!();
+
+    proxy_version!(MemoryMonitor);
+
     /// Signal emitted when a particular low memory situation happens
     /// with 0 being the lowest level of memory availability warning, and 255
     /// being the highest.
@@ -56,4 +65,22 @@ impl<'a> MemoryMonitorProxy<'a> {
     pub async fn receive_low_memory_warning(&self) -> Result<i32, Error> {
         receive_signal(&self.0, "LowMemoryWarning").await
     }
+
+    /// Signal emitted when a particular low memory situation happens
+    /// with 0 being the lowest level of memory availability warning, and 255
+    /// being the highest.
+    ///
+    /// Unlike
+    /// [`receive_low_memory_warning()`][`MemoryMonitorProxy::receive_low_memory_warning`],
+    /// the returned stream stays subscribed and yields every emitted signal.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`LowMemoryWarning`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-signal-org-freedesktop-portal-MemoryMonitor.LowMemoryWarning).
+    #[doc(alias = "LowMemoryWarning")]
+    pub async fn receive_low_memory_warning_stream(
+        &self,
+    ) -> Result<impl Stream<Item = i32> + '_, Error> {
+        receive_signal_stream(&self.0, "LowMemoryWarning").await
+    }
 }
@@ -4,9 +4,10 @@ pub(crate) const PATH: &str = "/org/freedesktop/portal/desktop";
 mod handle_token;
 pub(crate) mod request;
 mod session;
+pub use self::handle_token::HandleInvalidCharacter;
 pub(crate) use self::handle_token::HandleToken;
-pub use self::request::ResponseError;
-pub use self::session::SessionProxy;
+pub use self::request::{AbortToken, Request, ResponseError};
+pub use self::session::{set_session_executor, SessionProxy};
 
 /// Request access to the current logged user information such as the id, name
 /// or their avatar uri.
@@ -31,6 +32,9 @@ pub mod file_chooser;
 /// Enable/disable/query the status of Game Mode.
 pub mod game_mode;
 
+/// Register global shortcuts.
+pub mod global_shortcuts;
+
 /// Inhibit the session from being restarted or the user from logging out.
 pub mod inhibit;
 
@@ -55,6 +59,9 @@ pub mod print;
 /// Proxy information.
 pub mod proxy_resolver;
 
+/// Raise the scheduling priority of threads.
+pub mod realtime;
+
 /// Start a remote desktop session and interact with it.
 pub mod remote_desktop;
 
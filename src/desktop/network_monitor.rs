@@ -20,12 +20,13 @@
 
 use std::fmt;
 
+use futures::Stream;
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use zvariant_derive::{DeserializeDict, SerializeDict, Type, TypeDict};
 
 use super::{DESTINATION, PATH};
 use crate::{
-    helpers::{call_method, receive_signal},
+    helpers::{call_method, receive_signal, receive_signal_stream},
     Error,
 };
 
@@ -74,7 +75,7 @@ impl fmt::Display for Connectivity {
 /// Wrapper of the DBus interface: [`org.freedesktop.portal.NetworkMonitor`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.portal.NetworkMonitor).
 #[derive(Debug)]
 #[doc(alias = "org.freedesktop.portal.NetworkMonitor")]
-pub struct NetworkMonitorProxy<'a>(zbus::azync::Proxy<'a>);
+pub struct NetworkMonitorProxy<'a>(crate::helpers::Proxy<'a>);
 
 impl<'a> NetworkMonitorProxy<'a> {
     /// Create a new instance of [`NetworkMonitorProxy`].
@@ -87,7 +88,7 @@ impl<'a> NetworkMonitorProxy<'a> {
             .destination(DESTINATION)?
             .build()
             .await?;
-        Ok(Self(proxy))
+        Ok(Self(proxy.into()))
     }
 
     /// Get a reference to the underlying Proxy.
@@ -95,6 +96,10 @@ impl<'a> NetworkMonitorProxy<'a> {
         &self.0
     }
 
+    proxy_set_timeout!();
+
+    proxy_version!(NetworkMonitor);
+
     /// Returns whether the given hostname is believed to be reachable.
     ///
     /// # Arguments
@@ -166,4 +171,17 @@ impl<'a> NetworkMonitorProxy<'a> {
     pub async fn receive_changed(&self) -> Result<(), Error> {
         receive_signal(&self.0, "changed").await
     }
+
+    /// Emitted when the network configuration changes.
+    ///
+    /// Unlike
+    /// [`receive_changed()`][`NetworkMonitorProxy::receive_changed`],
+    /// the returned stream stays subscribed and yields every emitted signal.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`changed`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-signal-org-freedesktop-portal-NetworkMonitor.changed).
+    pub async fn receive_changed_stream(&self) -> Result<impl Stream<Item = ()> + '_, Error> {
+        receive_signal_stream(&self.0, "changed").await
+    }
 }
@@ -40,6 +40,7 @@
 //! }
 //! ```
 
+use futures::Stream;
 use serde::{self, Deserialize, Serialize, Serializer};
 use strum_macros::{AsRefStr, EnumString, IntoStaticStr, ToString};
 use zvariant::{OwnedValue, Signature};
@@ -47,7 +48,7 @@ use zvariant_derive::{DeserializeDict, SerializeDict, Type, TypeDict};
 
 use super::{DESTINATION, PATH};
 use crate::{
-    helpers::{call_method, receive_signal},
+    helpers::{call_method, receive_signal, receive_signal_stream},
     Error,
 };
 
@@ -55,6 +56,7 @@ use crate::{
     Debug, Clone, Deserialize, AsRefStr, EnumString, IntoStaticStr, ToString, PartialEq, Eq,
 )]
 #[strum(serialize_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
 /// The notification priority
 pub enum Priority {
     /// Low.
@@ -162,6 +164,41 @@ impl Notification {
         };
         self
     }
+
+    /// The notification title.
+    pub fn get_title(&self) -> &str {
+        &self.title
+    }
+
+    /// The notification body.
+    pub fn get_body(&self) -> Option<&str> {
+        self.body.as_deref()
+    }
+
+    /// The icon of the notification.
+    pub fn get_icon(&self) -> Option<&OwnedValue> {
+        self.icon.as_ref()
+    }
+
+    /// The notification priority.
+    pub fn get_priority(&self) -> Option<&Priority> {
+        self.priority.as_ref()
+    }
+
+    /// The default action when the user clicks on the notification.
+    pub fn get_default_action(&self) -> Option<&str> {
+        self.default_action.as_deref()
+    }
+
+    /// The value sent along the default action.
+    pub fn get_default_action_target(&self) -> Option<&OwnedValue> {
+        self.default_action_target.as_ref()
+    }
+
+    /// The buttons of the notification.
+    pub fn get_buttons(&self) -> &[Button] {
+        self.buttons.as_deref().unwrap_or_default()
+    }
 }
 
 #[derive(SerializeDict, DeserializeDict, TypeDict, Debug)]
@@ -197,6 +234,21 @@ impl Button {
         self.target = Some(target);
         self
     }
+
+    /// The user visible label of the button.
+    pub fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    /// The action name invoked when the user clicks on the button.
+    pub fn get_action(&self) -> &str {
+        &self.action
+    }
+
+    /// The value sent along the action.
+    pub fn get_target(&self) -> Option<&OwnedValue> {
+        self.target.as_ref()
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Type)]
@@ -204,6 +256,17 @@ impl Button {
 pub struct Action(String, String, Vec<OwnedValue>);
 
 impl Action {
+    /// Create a new invoked action.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The notification ID.
+    /// * `name` - The action name.
+    /// * `parameter` - The parameters passed to the action.
+    pub fn new(id: &str, name: &str, parameter: Vec<OwnedValue>) -> Self {
+        Self(id.to_string(), name.to_string(), parameter)
+    }
+
     /// Notification ID.
     pub fn id(&self) -> &str {
         &self.0
@@ -240,7 +303,7 @@ impl Action {
 /// Wrapper of the DBus interface: [`org.freedesktop.portal.Notification`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.portal.Notification).
 #[derive(Debug)]
 #[doc(alias = "org.freedesktop.portal.Notification")]
-pub struct NotificationProxy<'a>(zbus::azync::Proxy<'a>);
+pub struct NotificationProxy<'a>(crate::helpers::Proxy<'a>);
 
 impl<'a> NotificationProxy<'a> {
     /// Create a new instance of [`NotificationProxy`].
@@ -251,7 +314,7 @@ impl<'a> NotificationProxy<'a> {
             .destination(DESTINATION)?
             .build()
             .await?;
-        Ok(Self(proxy))
+        Ok(Self(proxy.into()))
     }
 
     /// Get a reference to the underlying Proxy.
@@ -259,6 +322,10 @@ impl<'a> NotificationProxy<'a> {
         &self.0
     }
 
+    proxy_set_timeout!();
+
+    proxy_version!(Notification);
+
     /// Signal emitted when a particular action is invoked.
     ///
     /// # Specifications
@@ -269,6 +336,22 @@ impl<'a> NotificationProxy<'a> {
         receive_signal(&self.0, "ActionInvoked").await
     }
 
+    /// Signal emitted when a particular action is invoked.
+    ///
+    /// Unlike
+    /// [`receive_action_invoked()`][`NotificationProxy::receive_action_invoked`],
+    /// the returned stream stays subscribed and yields every emitted signal.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`ActionInvoked`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-signal-org-freedesktop-portal-Notification.ActionInvoked).
+    #[doc(alias = "ActionInvoked")]
+    pub async fn receive_action_invoked_stream(
+        &self,
+    ) -> Result<impl Stream<Item = Action> + '_, Error> {
+        receive_signal_stream(&self.0, "ActionInvoked").await
+    }
+
     /// Sends a notification.
     ///
     /// The ID can be used to later withdraw the notification.
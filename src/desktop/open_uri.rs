@@ -99,7 +99,7 @@ use std::os::unix::prelude::AsRawFd;
 use zvariant::Fd;
 use zvariant_derive::{DeserializeDict, SerializeDict, TypeDict};
 
-use super::{HandleToken, DESTINATION, PATH};
+use super::{HandleToken, Request, DESTINATION, PATH};
 use crate::{helpers::call_basic_response_method, Error, WindowIdentifier};
 
 #[derive(SerializeDict, DeserializeDict, TypeDict, Debug, Default)]
@@ -146,7 +146,7 @@ impl OpenFileOptions {
 /// Wrapper of the DBus interface: [`org.freedesktop.portal.OpenURI`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.portal.OpenURI).
 #[derive(Debug)]
 #[doc(alias = "org.freedesktop.portal.OpenURI")]
-pub struct OpenURIProxy<'a>(zbus::azync::Proxy<'a>);
+pub struct OpenURIProxy<'a>(crate::helpers::Proxy<'a>);
 
 impl<'a> OpenURIProxy<'a> {
     /// Create a new instance of [`OpenURIProxy`].
@@ -157,7 +157,7 @@ impl<'a> OpenURIProxy<'a> {
             .destination(DESTINATION)?
             .build()
             .await?;
-        Ok(Self(proxy))
+        Ok(Self(proxy.into()))
     }
 
     /// Get a reference to the underlying Proxy.
@@ -165,6 +165,10 @@ impl<'a> OpenURIProxy<'a> {
         &self.0
     }
 
+    proxy_set_timeout!();
+
+    proxy_version!(OpenURI);
+
     /// Asks to open the directory containing a local file in the file browser.
     ///
     /// # Arguments
@@ -176,22 +180,26 @@ impl<'a> OpenURIProxy<'a> {
     ///
     /// See also [`OpenDirectory`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-portal-OpenURI.OpenDirectory).
     #[doc(alias = "OpenDirectory")]
-    pub async fn open_directory<F>(
-        &self,
-        identifier: &WindowIdentifier,
+    pub fn open_directory<'r, F>(
+        &'r self,
+        identifier: &'r WindowIdentifier,
         directory: &F,
-    ) -> Result<(), Error>
+    ) -> Request<'r, ()>
     where
         F: AsRawFd,
     {
         let options = OpenDirOptions::default();
-        call_basic_response_method(
-            &self.0,
-            &options.handle_token,
-            "OpenDirectory",
-            &(&identifier, Fd::from(directory.as_raw_fd()), &options),
-        )
-        .await
+        let fd = Fd::from(directory.as_raw_fd());
+        Request::new(move |call| async move {
+            call_basic_response_method(
+                &self.0,
+                &call,
+                &options.handle_token,
+                "OpenDirectory",
+                &(&identifier, fd, &options),
+            )
+            .await
+        })
     }
 
     /// Asks to open a local file.
@@ -208,24 +216,28 @@ impl<'a> OpenURIProxy<'a> {
     ///
     /// See also [`OpenFile`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-portal-OpenURI.OpenFile).
     #[doc(alias = "OpenFile")]
-    pub async fn open_file<F>(
-        &self,
-        identifier: &WindowIdentifier,
+    pub fn open_file<'r, F>(
+        &'r self,
+        identifier: &'r WindowIdentifier,
         file: &F,
         writeable: bool,
         ask: bool,
-    ) -> Result<(), Error>
+    ) -> Request<'r, ()>
     where
         F: AsRawFd,
     {
         let options = OpenFileOptions::default().ask(ask).writeable(writeable);
-        call_basic_response_method(
-            &self.0,
-            &options.handle_token,
-            "OpenFile",
-            &(&identifier, Fd::from(file.as_raw_fd()), &options),
-        )
-        .await
+        let fd = Fd::from(file.as_raw_fd());
+        Request::new(move |call| async move {
+            call_basic_response_method(
+                &self.0,
+                &call,
+                &options.handle_token,
+                "OpenFile",
+                &(&identifier, fd, &options),
+            )
+            .await
+        })
     }
 
     /// Asks to open a local file.
@@ -242,21 +254,24 @@ impl<'a> OpenURIProxy<'a> {
     ///
     /// See also [`OpenURI`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-portal-OpenURI.OpenURI).
     #[doc(alias = "OpenURI")]
-    pub async fn open_uri(
-        &self,
-        identifier: &WindowIdentifier,
-        uri: &str,
+    pub fn open_uri<'r>(
+        &'r self,
+        identifier: &'r WindowIdentifier,
+        uri: &'r str,
         writeable: bool,
         ask: bool,
-    ) -> Result<(), Error> {
+    ) -> Request<'r, ()> {
         let options = OpenFileOptions::default().ask(ask).writeable(writeable);
-        call_basic_response_method(
-            &self.0,
-            &options.handle_token,
-            "OpenURI",
-            &(&identifier, uri, &options),
-        )
-        .await
+        Request::new(move |call| async move {
+            call_basic_response_method(
+                &self.0,
+                &call,
+                &options.handle_token,
+                "OpenURI",
+                &(&identifier, uri, &options),
+            )
+            .await
+        })
     }
 }
 
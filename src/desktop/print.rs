@@ -43,7 +43,7 @@ use strum_macros::{AsRefStr, EnumString, IntoStaticStr, ToString};
 use zvariant::{Fd, Signature};
 use zvariant_derive::{DeserializeDict, SerializeDict, TypeDict};
 
-use super::{HandleToken, DESTINATION, PATH};
+use super::{HandleToken, Request, DESTINATION, PATH};
 use crate::{
     helpers::{call_basic_response_method, call_request_method},
     Error, WindowIdentifier,
@@ -513,7 +513,7 @@ pub struct PreparePrint {
 /// Wrapper of the DBus interface: [`org.freedesktop.portal.Print`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.portal.Print).
 #[derive(Debug)]
 #[doc(alias = "org.freedesktop.portal.Print")]
-pub struct PrintProxy<'a>(zbus::azync::Proxy<'a>);
+pub struct PrintProxy<'a>(crate::helpers::Proxy<'a>);
 
 impl<'a> PrintProxy<'a> {
     /// Create a new instance of [`PrintProxy`].
@@ -524,7 +524,7 @@ impl<'a> PrintProxy<'a> {
             .destination(DESTINATION)?
             .build()
             .await?;
-        Ok(Self(proxy))
+        Ok(Self(proxy.into()))
     }
 
     /// Get a reference to the underlying Proxy.
@@ -532,6 +532,10 @@ impl<'a> PrintProxy<'a> {
         &self.0
     }
 
+    proxy_set_timeout!();
+
+    proxy_version!(Print);
+
     /// Presents a print dialog to the user and returns print settings and page
     /// setup.
     ///
@@ -547,22 +551,25 @@ impl<'a> PrintProxy<'a> {
     ///
     /// See also [`PreparePrint`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-portal-Print.PreparePrint).
     #[doc(alias = "PreparePrint")]
-    pub async fn prepare_print(
-        &self,
-        identifier: &WindowIdentifier,
-        title: &str,
+    pub fn prepare_print<'r>(
+        &'r self,
+        identifier: &'r WindowIdentifier,
+        title: &'r str,
         settings: Settings,
         page_setup: PageSetup,
         modal: bool,
-    ) -> Result<PreparePrint, Error> {
+    ) -> Request<'r, PreparePrint> {
         let options = PreparePrintOptions::default().modal(modal);
-        call_request_method(
-            &self.0,
-            &options.handle_token,
-            "PreparePrint",
-            &(&identifier, title, settings, page_setup, &options),
-        )
-        .await
+        Request::new(move |call| async move {
+            call_request_method(
+                &self.0,
+                &call,
+                &options.handle_token,
+                "PreparePrint",
+                &(&identifier, title, settings, page_setup, &options),
+            )
+            .await
+        })
     }
 
     /// Asks to print a file.
@@ -583,26 +590,30 @@ impl<'a> PrintProxy<'a> {
     ///
     /// See also [`Print`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-portal-Print.Print).
     #[doc(alias = "Print")]
-    pub async fn print<F>(
-        &self,
-        identifier: &WindowIdentifier,
-        title: &str,
+    pub fn print<'r, F>(
+        &'r self,
+        identifier: &'r WindowIdentifier,
+        title: &'r str,
         fd: &F,
         token: Option<u32>,
         modal: bool,
-    ) -> Result<(), Error>
+    ) -> Request<'r, ()>
     where
         F: AsRawFd,
     {
         let options = PrintOptions::default()
             .token(token.unwrap_or(0))
             .modal(modal);
-        call_basic_response_method(
-            &self.0,
-            &options.handle_token,
-            "Print",
-            &(&identifier, title, Fd::from(fd.as_raw_fd()), &options),
-        )
-        .await
+        let fd = Fd::from(fd.as_raw_fd());
+        Request::new(move |call| async move {
+            call_basic_response_method(
+                &self.0,
+                &call,
+                &options.handle_token,
+                "Print",
+                &(&identifier, title, fd, &options),
+            )
+            .await
+        })
     }
 }
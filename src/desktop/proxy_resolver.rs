@@ -25,7 +25,7 @@ use crate::{helpers::call_method, Error};
 /// Wrapper of the DBus interface: [`org.freedesktop.portal.ProxyResolver`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.portal.ProxyResolver).
 #[derive(Debug)]
 #[doc(alias = "org.freedesktop.portal.ProxyResolver")]
-pub struct ProxyResolverProxy<'a>(zbus::azync::Proxy<'a>);
+pub struct ProxyResolverProxy<'a>(crate::helpers::Proxy<'a>);
 
 impl<'a> ProxyResolverProxy<'a> {
     /// Create a new instance of [`ProxyResolverProxy`].
@@ -38,7 +38,7 @@ impl<'a> ProxyResolverProxy<'a> {
             .destination(DESTINATION)?
             .build()
             .await?;
-        Ok(Self(proxy))
+        Ok(Self(proxy.into()))
     }
 
     /// Get a reference to the underlying Proxy.
@@ -46,6 +46,10 @@ impl<'a> ProxyResolverProxy<'a> {
         &self.0
     }
 
+    proxy_set_timeout!();
+
+    proxy_version!(ProxyResolver);
+
     /// Looks up which proxy to use to connect to `uri`.
     ///
     /// # Returns
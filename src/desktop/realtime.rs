@@ -0,0 +1,203 @@
+//! # Examples
+//!
+//! ```rust,no_run
+//! use ashpd::desktop::realtime::RealtimeProxy;
+//!
+//! async fn run() -> ashpd::Result<()> {
+//!     let connection = zbus::azync::Connection::session().await?;
+//!     let proxy = RealtimeProxy::new(&connection).await?;
+//!
+//!     let priority = proxy.max_realtime_priority().await?;
+//!     proxy
+//!         .make_current_thread_realtime(priority as u32)
+//!         .await?;
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use std::future::Future;
+
+use super::{DESTINATION, PATH};
+use crate::{
+    helpers::{call_method, get_property},
+    Error,
+};
+
+/// The interface lets sandboxed applications raise the scheduling priority of
+/// their threads, like RealtimeKit does for applications running on the host.
+///
+/// The portal translates the process & thread ids of the caller to the host
+/// pid namespace, similarly to the
+/// [`GameModeProxy`](crate::desktop::game_mode::GameModeProxy).
+///
+/// Wrapper of the DBus interface: [`org.freedesktop.portal.Realtime`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.portal.Realtime).
+#[derive(Debug)]
+#[doc(alias = "org.freedesktop.portal.Realtime")]
+pub struct RealtimeProxy<'a>(crate::helpers::Proxy<'a>);
+
+impl<'a> RealtimeProxy<'a> {
+    /// Create a new instance of [`RealtimeProxy`].
+    pub async fn new(connection: &zbus::azync::Connection) -> Result<RealtimeProxy<'a>, Error> {
+        let proxy = zbus::azync::ProxyBuilder::new_bare(connection)
+            .interface("org.freedesktop.portal.Realtime")?
+            .path(PATH)?
+            .destination(DESTINATION)?
+            .build()
+            .await?;
+        Ok(Self(proxy.into()))
+    }
+
+    /// Get a reference to the underlying Proxy.
+    pub fn inner(&self) -> &zbus::azync::Proxy<'_> {
+        &self.0
+    }
+
+    proxy_set_timeout!();
+
+    proxy_version!(Realtime);
+
+    /// Moves a thread to the realtime scheduling class.
+    ///
+    /// # Arguments
+    ///
+    /// * `process` - Process id of the thread, in the sandbox pid namespace.
+    /// * `thread` - Thread id, in the sandbox pid namespace.
+    /// * `priority` - The realtime priority, at most
+    ///   [`max_realtime_priority()`][`RealtimeProxy::max_realtime_priority`].
+    ///
+    /// # Specifications
+    ///
+    /// See also [`MakeThreadRealtimeWithPID`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-portal-Realtime.MakeThreadRealtimeWithPID).
+    #[doc(alias = "MakeThreadRealtimeWithPID")]
+    pub async fn make_thread_realtime_with_pid(
+        &self,
+        process: u64,
+        thread: u64,
+        priority: u32,
+    ) -> Result<(), Error> {
+        call_method(
+            &self.0,
+            "MakeThreadRealtimeWithPID",
+            &(process, thread, priority),
+        )
+        .await
+    }
+
+    /// Changes the nice level of a thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `process` - Process id of the thread, in the sandbox pid namespace.
+    /// * `thread` - Thread id, in the sandbox pid namespace.
+    /// * `priority` - The nice level, at least
+    ///   [`min_nice_level()`][`RealtimeProxy::min_nice_level`].
+    ///
+    /// # Specifications
+    ///
+    /// See also [`MakeThreadHighPriorityWithPID`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-portal-Realtime.MakeThreadHighPriorityWithPID).
+    #[doc(alias = "MakeThreadHighPriorityWithPID")]
+    pub async fn make_thread_high_priority_with_pid(
+        &self,
+        process: u64,
+        thread: u64,
+        priority: i32,
+    ) -> Result<(), Error> {
+        call_method(
+            &self.0,
+            "MakeThreadHighPriorityWithPID",
+            &(process, thread, priority),
+        )
+        .await
+    }
+
+    /// Moves the calling thread to the realtime scheduling class.
+    ///
+    /// The thread id is read when this method is called, not when the
+    /// returned future is first polled.
+    ///
+    /// # Arguments
+    ///
+    /// * `priority` - The realtime priority, at most
+    ///   [`max_realtime_priority()`][`RealtimeProxy::max_realtime_priority`].
+    pub fn make_current_thread_realtime(
+        &self,
+        priority: u32,
+    ) -> impl Future<Output = Result<(), Error>> + '_ {
+        let thread = current_thread_id();
+        async move {
+            let process = std::process::id() as u64;
+            self.make_thread_realtime_with_pid(process, thread?, priority)
+                .await
+        }
+    }
+
+    /// Changes the nice level of the calling thread.
+    ///
+    /// The thread id is read when this method is called, not when the
+    /// returned future is first polled.
+    ///
+    /// # Arguments
+    ///
+    /// * `priority` - The nice level, at least
+    ///   [`min_nice_level()`][`RealtimeProxy::min_nice_level`].
+    pub fn make_current_thread_high_priority(
+        &self,
+        priority: i32,
+    ) -> impl Future<Output = Result<(), Error>> + '_ {
+        let thread = current_thread_id();
+        async move {
+            let process = std::process::id() as u64;
+            self.make_thread_high_priority_with_pid(process, thread?, priority)
+                .await
+        }
+    }
+
+    /// The maximum realtime priority a thread can be given.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`MaxRealtimePriority`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-property-org-freedesktop-portal-Realtime.MaxRealtimePriority).
+    #[doc(alias = "MaxRealtimePriority")]
+    pub async fn max_realtime_priority(&self) -> Result<i32, Error> {
+        get_property::<i32>(&self.0, "MaxRealtimePriority").await
+    }
+
+    /// The minimum nice level a thread can be given.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`MinNiceLevel`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-property-org-freedesktop-portal-Realtime.MinNiceLevel).
+    #[doc(alias = "MinNiceLevel")]
+    pub async fn min_nice_level(&self) -> Result<i32, Error> {
+        get_property::<i32>(&self.0, "MinNiceLevel").await
+    }
+
+    /// The maximum CPU time, in microseconds, a realtime thread can consume
+    /// without sleeping. The `RLIMIT_RTTIME` limit of the process must not
+    /// exceed it.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`RTTimeUSecMax`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-property-org-freedesktop-portal-Realtime.RTTimeUSecMax).
+    #[doc(alias = "RTTimeUSecMax")]
+    pub async fn rt_time_usec_max(&self) -> Result<i64, Error> {
+        get_property::<i64>(&self.0, "RTTimeUSecMax").await
+    }
+}
+
+/// The id of the calling thread, read from `/proc/thread-self` which links to
+/// `PID/task/TID`.
+fn current_thread_id() -> Result<u64, Error> {
+    let link = std::fs::read_link("/proc/thread-self")?;
+    link.file_name()
+        .and_then(|tid| tid.to_str())
+        .and_then(|tid| tid.parse().ok())
+        .ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Failed to parse the thread id",
+            )
+            .into()
+        })
+}
@@ -1,7 +1,9 @@
 //! # Examples
 //!
 //! ```rust,no_run
-//! use ashpd::desktop::remote_desktop::{DeviceType, KeyState, RemoteDesktopProxy};
+//! use ashpd::desktop::remote_desktop::{
+//!     DeviceType, KeyState, RemoteDesktopProxy, SelectDevicesOptions,
+//! };
 //! use ashpd::WindowIdentifier;
 //!
 //! async fn run() -> ashpd::Result<()> {
@@ -10,10 +12,15 @@
 //!
 //!     let session = proxy.create_session().await?;
 //!
-//!     proxy.select_devices(&session, DeviceType::Keyboard | DeviceType::Pointer).await?;
+//!     proxy
+//!         .select_devices(
+//!             &session,
+//!             SelectDevicesOptions::default().types(DeviceType::Keyboard | DeviceType::Pointer),
+//!         )
+//!         .await?;
 //!
-//!     let (devices, _) = proxy.start(&session, &WindowIdentifier::default()).await?;
-//!     println!("{:#?}", devices);
+//!     let response = proxy.start(&session, &WindowIdentifier::default()).await?;
+//!     println!("{:#?}", response.devices());
 //!
 //!     // 13 for Enter key code
 //!     proxy.notify_keyboard_keycode(&session, 13, KeyState::Pressed).await?;
@@ -27,8 +34,10 @@
 //! on the session created with [`RemoteDesktopProxy::create_session()`](crate::desktop::remote_desktop::RemoteDesktopProxy::create_session)
 //!
 //! ```rust,no_run
-//! use ashpd::desktop::remote_desktop::{DeviceType, KeyState, RemoteDesktopProxy};
-//! use ashpd::desktop::screencast::{CursorMode, ScreenCastProxy, SourceType};
+//! use ashpd::desktop::remote_desktop::{
+//!     DeviceType, KeyState, RemoteDesktopProxy, SelectDevicesOptions,
+//! };
+//! use ashpd::desktop::screencast::{CursorMode, ScreenCastProxy, SelectSourcesOptions, SourceType};
 //! use ashpd::WindowIdentifier;
 //!
 //! async fn run() -> ashpd::Result<()> {
@@ -39,19 +48,25 @@
 //!
 //!     let session = proxy.create_session().await?;
 //!
-//!     proxy.select_devices(&session, DeviceType::Keyboard | DeviceType::Pointer).await?;
+//!     proxy
+//!         .select_devices(
+//!             &session,
+//!             SelectDevicesOptions::default().types(DeviceType::Keyboard | DeviceType::Pointer),
+//!         )
+//!         .await?;
 //!     screencast
 //!         .select_sources(
 //!             &session,
-//!             CursorMode::Metadata.into(),
-//!             SourceType::Monitor | SourceType::Window,
-//!             true,
+//!             SelectSourcesOptions::default()
+//!                 .cursor_mode(CursorMode::Metadata.into())
+//!                 .types(SourceType::Monitor | SourceType::Window)
+//!                 .multiple(true),
 //!         )
 //!         .await?;
 //!
-//!     let (devices, streams) = proxy.start(&session, &identifier).await?;
-//!     println!("{:#?}", devices);
-//!     println!("{:#?}", streams);
+//!     let response = proxy.start(&session, &identifier).await?;
+//!     println!("{:#?}", response.devices());
+//!     println!("{:#?}", response.streams());
 //!
 //!     // 13 for Enter key code
 //!     proxy.notify_keyboard_keycode(&session, 13, KeyState::Pressed).await?;
@@ -60,22 +75,32 @@
 //! }
 //! ```
 
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    future::Future,
+    os::unix::prelude::{AsRawFd, FromRawFd},
+    time::Duration,
+};
 
 use enumflags2::BitFlags;
 use futures::TryFutureExt;
 use serde_repr::{Deserialize_repr, Serialize_repr};
-use zvariant::Value;
+use zvariant::{Fd, OwnedFd, Value};
 use zvariant_derive::{DeserializeDict, SerializeDict, Type, TypeDict};
 
-use super::{screencast::Stream, HandleToken, SessionProxy, DESTINATION, PATH};
+use super::{
+    screencast::{PersistMode, Stream},
+    HandleToken, Request, SessionProxy, DESTINATION, PATH,
+};
 
 use crate::{
-    helpers::{call_basic_response_method, call_method, call_request_method},
+    helpers::{
+        call_basic_response_method, call_method, call_request_method, get_property, require_version,
+    },
     Error, WindowIdentifier,
 };
 
-#[derive(Serialize_repr, Deserialize_repr, PartialEq, Debug, Type)]
+#[derive(Serialize_repr, Deserialize_repr, PartialEq, Debug, Clone, Copy, Type)]
 #[repr(u32)]
 /// The keyboard key state.
 pub enum KeyState {
@@ -97,7 +122,7 @@ pub enum DeviceType {
     Touchscreen = 4,
 }
 
-#[derive(Serialize_repr, Deserialize_repr, PartialEq, Debug, Type)]
+#[derive(Serialize_repr, Deserialize_repr, PartialEq, Debug, Clone, Copy, Type)]
 #[repr(u32)]
 /// The available axis.
 pub enum Axis {
@@ -127,11 +152,15 @@ struct CreateSession {
 
 #[derive(SerializeDict, DeserializeDict, TypeDict, Debug, Default)]
 /// Specified options for a [`RemoteDesktopProxy::select_devices`] request.
-struct SelectDevicesOptions {
+pub struct SelectDevicesOptions {
     /// A string that will be used as the last element of the handle.
     handle_token: HandleToken,
     /// The device types to request remote controlling of. Default is all.
     types: Option<BitFlags<DeviceType>>,
+    /// The token returned by a previous session, used to restore it.
+    restore_token: Option<String>,
+    /// How the permission should be persisted.
+    persist_mode: Option<PersistMode>,
 }
 
 impl SelectDevicesOptions {
@@ -140,22 +169,90 @@ impl SelectDevicesOptions {
         self.types = Some(types);
         self
     }
+
+    /// Sets the token of a previous session to restore.
+    pub fn restore_token(mut self, restore_token: &str) -> Self {
+        self.restore_token = Some(restore_token.to_string());
+        self
+    }
+
+    /// Sets how the permission should be persisted.
+    pub fn persist_mode(mut self, persist_mode: PersistMode) -> Self {
+        self.persist_mode = Some(persist_mode);
+        self
+    }
+
+    /// The device types to request remote controlling of.
+    pub fn get_types(&self) -> Option<BitFlags<DeviceType>> {
+        self.types
+    }
+
+    /// The token of a previous session to restore.
+    pub fn get_restore_token(&self) -> Option<&str> {
+        self.restore_token.as_deref()
+    }
+
+    /// How the permission should be persisted.
+    pub fn get_persist_mode(&self) -> Option<PersistMode> {
+        self.persist_mode
+    }
 }
 
 #[derive(SerializeDict, DeserializeDict, TypeDict, Debug, Default)]
 /// Specified options for a [`RemoteDesktopProxy::start`] request.
-struct StartRemoteOptions {
+pub struct StartRemoteOptions {
     /// A string that will be used as the last element of the handle.
     handle_token: HandleToken,
 }
 
-#[derive(SerializeDict, DeserializeDict, TypeDict, Debug, Default)]
-/// A response to a [`RemoteDesktopProxy::select_devices`] request.
-struct SelectedDevices {
+#[derive(SerializeDict, DeserializeDict, TypeDict, Debug)]
+/// A response to a [`RemoteDesktopProxy::start`] request.
+pub struct SelectedDevices {
+    devices: BitFlags<DeviceType>,
+    streams: Option<Vec<Stream>>,
+    restore_token: Option<String>,
+}
+
+impl SelectedDevices {
+    /// Create a new response from the selected devices.
+    pub fn new(devices: BitFlags<DeviceType>) -> Self {
+        Self {
+            devices,
+            streams: None,
+            restore_token: None,
+        }
+    }
+
+    /// Sets the selected streams if a ScreenCast portal is used on the same
+    /// session.
+    pub fn with_streams(mut self, streams: Vec<Stream>) -> Self {
+        self.streams = Some(streams);
+        self
+    }
+
+    /// Sets the token that can be used to restore the session.
+    pub fn with_restore_token(mut self, restore_token: &str) -> Self {
+        self.restore_token = Some(restore_token.to_string());
+        self
+    }
+
     /// The selected devices.
-    pub devices: BitFlags<DeviceType>,
-    /// The selected streams if a ScreenCast portal is used on the same session
-    pub streams: Option<Vec<Stream>>,
+    pub fn devices(&self) -> BitFlags<DeviceType> {
+        self.devices
+    }
+
+    /// The selected streams if a ScreenCast portal is used on the same
+    /// session.
+    pub fn streams(&self) -> Option<&[Stream]> {
+        self.streams.as_deref()
+    }
+
+    /// The token to pass to [`RemoteDesktopProxy::select_devices`] to restore
+    /// the session, only set if a [`PersistMode`] other than
+    /// [`PersistMode::DoNot`] was requested and granted.
+    pub fn restore_token(&self) -> Option<&str> {
+        self.restore_token.as_deref()
+    }
 }
 
 /// The interface lets sandboxed applications create remote desktop sessions.
@@ -163,7 +260,7 @@ struct SelectedDevices {
 /// Wrapper of the DBus interface: [`org.freedesktop.portal.RemoteDesktop`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.portal.RemoteDesktop).
 #[derive(Debug)]
 #[doc(alias = "org.freedesktop.portal.RemoteDesktop")]
-pub struct RemoteDesktopProxy<'a>(zbus::azync::Proxy<'a>);
+pub struct RemoteDesktopProxy<'a>(crate::helpers::Proxy<'a>);
 
 impl<'a> RemoteDesktopProxy<'a> {
     /// Create a new instance of [`RemoteDesktopProxy`].
@@ -176,7 +273,7 @@ impl<'a> RemoteDesktopProxy<'a> {
             .destination(DESTINATION)?
             .build()
             .await?;
-        Ok(Self(proxy))
+        Ok(Self(proxy.into()))
     }
 
     /// Get a reference to the underlying Proxy.
@@ -184,6 +281,8 @@ impl<'a> RemoteDesktopProxy<'a> {
         &self.0
     }
 
+    proxy_set_timeout!();
+
     /// Create a remote desktop session.
     /// A remote desktop session is used to allow remote controlling a desktop
     /// session. It can also be used together with a screen cast session.
@@ -192,21 +291,23 @@ impl<'a> RemoteDesktopProxy<'a> {
     ///
     /// See also [`CreateSession`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-portal-RemoteDesktop.CreateSession).
     #[doc(alias = "CreateSession")]
-    pub async fn create_session(&self) -> Result<SessionProxy<'a>, Error> {
+    pub fn create_session(&self) -> Request<'_, SessionProxy<'a>> {
         let options = CreateRemoteOptions::default();
-        let (session, proxy) = futures::try_join!(
-            call_request_method::<CreateSession, CreateRemoteOptions>(
-                &self.0,
-                &options.handle_token,
-                "CreateSession",
-                &options
-            )
-            .into_future(),
-            SessionProxy::from_unique_name(self.0.connection(), &options.session_handle_token)
-                .into_future()
-        )?;
-        assert_eq!(proxy.inner().path().as_str(), &session.session_handle);
-        Ok(proxy)
+        Request::new(move |call| async move {
+            let (session, proxy) = futures::try_join!(
+                call_request_method::<CreateSession, CreateRemoteOptions>(
+                    &self.0,
+                    &call,
+                    &options.handle_token,
+                    "CreateSession",
+                    &options
+                )
+                .into_future(),
+                SessionProxy::from_unique_name(self.0.connection(), &options.session_handle_token)
+                    .into_future()
+            )?;
+            proxy.resolve(&session.session_handle).await
+        })
     }
 
     /// Select input devices to remote control.
@@ -215,25 +316,35 @@ impl<'a> RemoteDesktopProxy<'a> {
     ///
     /// * `session` - A [`SessionProxy`], created with
     ///   [`create_session()`][`RemoteDesktopProxy::create_session`].
-    /// * `types` - The device types to request remote controlling of.
+    /// * `options` - [`SelectDevicesOptions`].
+    ///
+    /// **Note** the [`restore_token`][`SelectDevicesOptions::restore_token`] &
+    /// [`persist_mode`][`SelectDevicesOptions::persist_mode`] options require
+    /// version 2 of the interface, an [`Error::RequiresVersion`] is returned
+    /// otherwise.
     ///
     /// # Specifications
     ///
     /// See also [`SelectDevices`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-portal-RemoteDesktop.SelectDevices).
     #[doc(alias = "SelectDevices")]
-    pub async fn select_devices(
-        &self,
-        session: &SessionProxy<'_>,
-        types: BitFlags<DeviceType>,
-    ) -> Result<(), Error> {
-        let options = SelectDevicesOptions::default().types(types);
-        call_basic_response_method(
-            &self.0,
-            &options.handle_token,
-            "SelectDevices",
-            &(session, &options),
-        )
-        .await
+    pub fn select_devices<'r>(
+        &'r self,
+        session: &'r SessionProxy<'_>,
+        options: SelectDevicesOptions,
+    ) -> Request<'r, ()> {
+        Request::new(move |call| async move {
+            if options.restore_token.is_some() || options.persist_mode.is_some() {
+                require_version(&self.0, 2).await?;
+            }
+            call_basic_response_method(
+                &self.0,
+                &call,
+                &options.handle_token,
+                "SelectDevices",
+                &(session, &options),
+            )
+            .await
+        })
     }
 
     ///  Start the remote desktop session.
@@ -248,24 +359,31 @@ impl<'a> RemoteDesktopProxy<'a> {
     ///   [`create_session()`][`RemoteDesktopProxy::create_session`].
     /// * `identifier` - The application window identifier.
     ///
+    /// # Returns
+    ///
+    /// The selected devices & streams, along with a restore token if the
+    /// session can be restored later.
+    ///
     /// # Specifications
     ///
     /// See also [`Start`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-portal-RemoteDesktop.Start).
     #[doc(alias = "Start")]
-    pub async fn start(
-        &self,
-        session: &SessionProxy<'_>,
-        identifier: &WindowIdentifier,
-    ) -> Result<(BitFlags<DeviceType>, Vec<Stream>), Error> {
+    pub fn start<'r>(
+        &'r self,
+        session: &'r SessionProxy<'_>,
+        identifier: &'r WindowIdentifier,
+    ) -> Request<'r, SelectedDevices> {
         let options = StartRemoteOptions::default();
-        let response: SelectedDevices = call_request_method(
-            &self.0,
-            &options.handle_token,
-            "Start",
-            &(session, &identifier, &options),
-        )
-        .await?;
-        Ok((response.devices, response.streams.unwrap_or_default()))
+        Request::new(move |call| async move {
+            call_request_method(
+                &self.0,
+                &call,
+                &options.handle_token,
+                "Start",
+                &(session, &identifier, &options),
+            )
+            .await
+        })
     }
 
     /// Notify keyboard code.
@@ -598,6 +716,41 @@ impl<'a> RemoteDesktopProxy<'a> {
         call_method(&self.0, "NotifyPointerAxis", &(session, options, dx, dy)).await
     }
 
+    /// Connect to an EIS implementation, to send input events with libei
+    /// instead of the `notify_*` methods, which need a D-Bus round-trip for
+    /// every event.
+    ///
+    /// The session must be started and the connection can only be requested
+    /// once per session. Once it is, the `notify_*` methods can no longer be
+    /// used on that session.
+    ///
+    /// **Note** requires version 2 of the interface, an
+    /// [`Error::RequiresVersion`] is returned otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - A [`SessionProxy`], created with
+    ///   [`create_session()`][`RemoteDesktopProxy::create_session`].
+    ///
+    /// # Returns
+    ///
+    /// File descriptor of a socket to pass to libei.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`ConnectToEIS`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-portal-RemoteDesktop.ConnectToEIS).
+    #[doc(alias = "ConnectToEIS")]
+    pub async fn connect_to_eis(&self, session: &SessionProxy<'_>) -> Result<OwnedFd, Error> {
+        require_version(&self.0, 2).await?;
+        // `options` parameter doesn't seems to be used yet
+        let options: HashMap<&str, Value<'_>> = HashMap::new();
+        let fd: Fd = call_method(&self.0, "ConnectToEIS", &(session, options)).await?;
+        // The file descriptors of the reply were disowned by `call_method`.
+        Ok(unsafe { OwnedFd::from_raw_fd(fd.as_raw_fd()) })
+    }
+
+    proxy_version!(RemoteDesktop);
+
     /// Available source types.
     ///
     /// # Specifications
@@ -605,9 +758,393 @@ impl<'a> RemoteDesktopProxy<'a> {
     /// See also [`AvailableDeviceTypes`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-property-org-freedesktop-portal-RemoteDesktop.AvailableDeviceTypes).
     #[doc(alias = "AvailableDeviceTypes")]
     pub async fn available_device_types(&self) -> Result<BitFlags<DeviceType>, Error> {
-        self.inner()
-            .get_property::<BitFlags<DeviceType>>("AvailableDeviceTypes")
-            .await
-            .map_err(From::from)
+        get_property::<BitFlags<DeviceType>>(&self.0, "AvailableDeviceTypes").await
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// A keyboard symbol, as defined by the X11 `keysymdef.h` header.
+pub struct Keysym(i32);
+
+impl Keysym {
+    /// The left Shift key.
+    pub const SHIFT: Self = Self(0xffe1);
+    /// The left Control key.
+    pub const CONTROL: Self = Self(0xffe3);
+    /// The left Alt key.
+    pub const ALT: Self = Self(0xffe9);
+    /// The left Super key.
+    pub const SUPER: Self = Self(0xffeb);
+    /// The Return key.
+    pub const RETURN: Self = Self(0xff0d);
+    /// The Tab key.
+    pub const TAB: Self = Self(0xff09);
+    /// The BackSpace key.
+    pub const BACKSPACE: Self = Self(0xff08);
+    /// The Escape key.
+    pub const ESCAPE: Self = Self(0xff1b);
+    /// The Delete key.
+    pub const DELETE: Self = Self(0xffff);
+
+    /// Create a keysym from its raw value.
+    pub fn new(keysym: i32) -> Self {
+        Self(keysym)
+    }
+
+    /// The keysym producing the character `c`.
+    ///
+    /// Latin-1 characters map to their legacy keysym, other characters to
+    /// their Unicode keysym.
+    pub fn from_char(c: char) -> Self {
+        match c {
+            '\n' | '\r' => Self::RETURN,
+            '\t' => Self::TAB,
+            '\u{8}' => Self::BACKSPACE,
+            '\u{1b}' => Self::ESCAPE,
+            '\u{7f}' => Self::DELETE,
+            ' '..='~' | '\u{a0}'..='\u{ff}' => Self(c as i32),
+            _ => Self(0x0100_0000 | c as i32),
+        }
+    }
+
+    /// The keysym of a key name such as `Ctrl`, `Alt`, `Enter`, `F5` or `t`.
+    ///
+    /// Names are case insensitive, returns `None` for an unknown name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let mut chars = name.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return Some(Self::from_char(c.to_ascii_lowercase()));
+        }
+        let keysym = match name.to_ascii_lowercase().as_str() {
+            "shift" => Self::SHIFT,
+            "ctrl" | "control" => Self::CONTROL,
+            "alt" => Self::ALT,
+            "super" | "meta" | "logo" => Self::SUPER,
+            "enter" | "return" => Self::RETURN,
+            "tab" => Self::TAB,
+            "backspace" => Self::BACKSPACE,
+            "esc" | "escape" => Self::ESCAPE,
+            "del" | "delete" => Self::DELETE,
+            "space" => Self(0x20),
+            "home" => Self(0xff50),
+            "left" => Self(0xff51),
+            "up" => Self(0xff52),
+            "right" => Self(0xff53),
+            "down" => Self(0xff54),
+            "pageup" => Self(0xff55),
+            "pagedown" => Self(0xff56),
+            "end" => Self(0xff57),
+            "insert" => Self(0xff63),
+            function => match function.strip_prefix('f')?.parse::<i32>() {
+                Ok(n) if (1..=35).contains(&n) => Self(0xffbe + n - 1),
+                _ => return None,
+            },
+        };
+        Some(keysym)
+    }
+
+    /// Parse a chord such as `Ctrl+Alt+T` into the keysyms to press, in
+    /// order.
+    ///
+    /// Returns `None` if any of the keys is unknown.
+    pub fn parse_chord(chord: &str) -> Option<Vec<Self>> {
+        chord
+            .split('+')
+            .map(|name| Self::from_name(name.trim()))
+            .collect()
+    }
+
+    /// The raw keysym value.
+    pub fn raw(&self) -> i32 {
+        self.0
+    }
+}
+
+/// Whether typing `c` on a US layout requires holding Shift.
+fn needs_shift(c: char) -> bool {
+    c.is_ascii_uppercase() || "~!@#$%^&*()_+{}|:\"<>?".contains(c)
+}
+
+/// Release what was pressed whatever `result` is. If `result` is an error, the
+/// release is best effort and the original error is returned.
+async fn release(
+    result: Result<(), Error>,
+    release: impl Future<Output = Result<(), Error>>,
+) -> Result<(), Error> {
+    let released = release.await;
+    match result {
+        Ok(()) => released,
+        Err(err) => {
+            if let Err(release_err) = released {
+                tracing::warn!("Failed to release after an error: {}", release_err);
+            }
+            Err(err)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(i32)]
+/// A pointer button, as defined by the Linux `input-event-codes.h` header.
+pub enum PointerButton {
+    /// The left button.
+    Left = 0x110,
+    /// The right button.
+    Right = 0x111,
+    /// The middle button.
+    Middle = 0x112,
+    /// The side button.
+    Side = 0x113,
+    /// The extra button.
+    Extra = 0x114,
+}
+
+/// A helper to send sequences of input events to a started remote desktop
+/// session, waiting a configurable delay between each event.
+///
+/// ```rust,no_run
+/// use ashpd::desktop::remote_desktop::{Keysym, PointerButton, RemoteDesktopProxy, VirtualInput};
+/// use ashpd::desktop::SessionProxy;
+/// use std::time::Duration;
+///
+/// async fn run(proxy: &RemoteDesktopProxy<'_>, session: &SessionProxy<'_>) -> ashpd::Result<()> {
+///     let input = VirtualInput::new(proxy, session).delay(Duration::from_millis(50));
+///
+///     input.chord(&Keysym::parse_chord("Ctrl+Alt+T").unwrap()).await?;
+///     input.type_text("echo Hello, World!\n").await?;
+///     input.click(PointerButton::Left).await?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct VirtualInput<'a, 'p> {
+    proxy: &'a RemoteDesktopProxy<'p>,
+    session: &'a SessionProxy<'p>,
+    delay: Duration,
+}
+
+impl<'a, 'p> VirtualInput<'a, 'p> {
+    /// Create a new helper sending the events to `session`.
+    ///
+    /// The delay between events defaults to 20 milliseconds.
+    pub fn new(proxy: &'a RemoteDesktopProxy<'p>, session: &'a SessionProxy<'p>) -> Self {
+        Self {
+            proxy,
+            session,
+            delay: Duration::from_millis(20),
+        }
+    }
+
+    /// Sets the delay to wait between two events.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    async fn wait(&self) {
+        if !self.delay.is_zero() {
+            async_io::Timer::after(self.delay).await;
+        }
+    }
+
+    async fn keysym(&self, keysym: Keysym, state: KeyState) -> Result<(), Error> {
+        self.proxy
+            .notify_keyboard_keysym(self.session, keysym.raw(), state)
+            .await?;
+        self.wait().await;
+        Ok(())
+    }
+
+    async fn button(&self, button: PointerButton, state: KeyState) -> Result<(), Error> {
+        self.proxy
+            .notify_pointer_button(self.session, button as i32, state)
+            .await?;
+        self.wait().await;
+        Ok(())
+    }
+
+    /// Press & release a key.
+    pub async fn key(&self, keysym: Keysym) -> Result<(), Error> {
+        self.keysym(keysym, KeyState::Pressed).await?;
+        self.keysym(keysym, KeyState::Released).await
+    }
+
+    /// Type a string, holding Shift for the characters that require it on a
+    /// US layout.
+    ///
+    /// **Note** only works if [`DeviceType::Keyboard`] access was provided
+    /// after starting the session.
+    pub async fn type_text(&self, text: &str) -> Result<(), Error> {
+        for c in text.chars() {
+            let key = Keysym::from_char(c);
+            if needs_shift(c) {
+                self.keysym(Keysym::SHIFT, KeyState::Pressed).await?;
+                let typed = self.key(key).await;
+                release(typed, self.keysym(Keysym::SHIFT, KeyState::Released)).await?;
+            } else {
+                self.key(key).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Press the keys in order, then release them in reverse order.
+    ///
+    /// **Note** only works if [`DeviceType::Keyboard`] access was provided
+    /// after starting the session.
+    pub async fn chord(&self, keys: &[Keysym]) -> Result<(), Error> {
+        let mut result = Ok(());
+        let mut pressed = 0;
+        for key in keys {
+            result = self.keysym(*key, KeyState::Pressed).await;
+            if result.is_err() {
+                break;
+            }
+            pressed += 1;
+        }
+        // Release the keys pressed so far, even if pressing the next failed.
+        for key in keys[..pressed].iter().rev() {
+            result = release(result, self.keysym(*key, KeyState::Released)).await;
+        }
+        result
+    }
+
+    /// Press & release a pointer button.
+    ///
+    /// **Note** only works if [`DeviceType::Pointer`] access was provided
+    /// after starting the session.
+    pub async fn click(&self, button: PointerButton) -> Result<(), Error> {
+        self.button(button, KeyState::Pressed).await?;
+        self.button(button, KeyState::Released).await
+    }
+
+    /// Click a pointer button twice.
+    ///
+    /// **Note** only works if [`DeviceType::Pointer`] access was provided
+    /// after starting the session.
+    pub async fn double_click(&self, button: PointerButton) -> Result<(), Error> {
+        self.click(button).await?;
+        self.click(button).await
+    }
+
+    /// Hold a pointer button while moving the pointer by (`dx`, `dy`) in
+    /// `steps` motion events.
+    ///
+    /// **Note** only works if [`DeviceType::Pointer`] access was provided
+    /// after starting the session.
+    pub async fn drag(
+        &self,
+        button: PointerButton,
+        dx: f64,
+        dy: f64,
+        steps: u32,
+    ) -> Result<(), Error> {
+        let steps = steps.max(1);
+        self.button(button, KeyState::Pressed).await?;
+        let moved = async {
+            for _ in 0..steps {
+                self.proxy
+                    .notify_pointer_motion(self.session, dx / steps as f64, dy / steps as f64)
+                    .await?;
+                self.wait().await;
+            }
+            Ok::<_, Error>(())
+        }
+        .await;
+        release(moved, self.button(button, KeyState::Released)).await
+    }
+
+    /// Move the pointer to `from`, then hold a pointer button while moving it
+    /// to `to` in `steps` motion events.
+    ///
+    /// **Note** only works if [`DeviceType::Pointer`] access was provided
+    /// after starting the session, along with a screen cast stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `button` - The button to hold.
+    /// * `stream` - The PipeWire stream node the coordinates are relative to.
+    /// * `from` - The (x, y) position to start the drag at.
+    /// * `to` - The (x, y) position to end the drag at.
+    /// * `steps` - The number of motion events.
+    pub async fn drag_absolute(
+        &self,
+        button: PointerButton,
+        stream: u32,
+        from: (f64, f64),
+        to: (f64, f64),
+        steps: u32,
+    ) -> Result<(), Error> {
+        let steps = steps.max(1);
+        self.proxy
+            .notify_pointer_motion_absolute(self.session, stream, from.0, from.1)
+            .await?;
+        self.wait().await;
+        self.button(button, KeyState::Pressed).await?;
+        let moved = async {
+            for step in 1..=steps {
+                let progress = step as f64 / steps as f64;
+                let x = from.0 + (to.0 - from.0) * progress;
+                let y = from.1 + (to.1 - from.1) * progress;
+                self.proxy
+                    .notify_pointer_motion_absolute(self.session, stream, x, y)
+                    .await?;
+                self.wait().await;
+            }
+            Ok::<_, Error>(())
+        }
+        .await;
+        release(moved, self.button(button, KeyState::Released)).await
+    }
+
+    /// Scroll by `steps` wheel clicks, one event per click. Negative steps
+    /// scroll up or left.
+    ///
+    /// **Note** only works if [`DeviceType::Pointer`] access was provided
+    /// after starting the session.
+    pub async fn scroll(&self, axis: Axis, steps: i32) -> Result<(), Error> {
+        for _ in 0..steps.abs() {
+            self.proxy
+                .notify_pointer_axis_discrete(self.session, axis, steps.signum())
+                .await?;
+            self.wait().await;
+        }
+        Ok(())
+    }
+
+    /// Scroll smoothly by (`dx`, `dy`), as a touchpad would, in `steps`
+    /// events.
+    ///
+    /// **Note** only works if [`DeviceType::Pointer`] access was provided
+    /// after starting the session.
+    pub async fn smooth_scroll(&self, dx: f64, dy: f64, steps: u32) -> Result<(), Error> {
+        let steps = steps.max(1);
+        for _ in 0..steps {
+            self.proxy
+                .notify_pointer_axis(self.session, dx / steps as f64, dy / steps as f64)
+                .await?;
+            self.wait().await;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Keysym;
+
+    #[test]
+    fn keysym() {
+        assert_eq!(Keysym::from_char('a').raw(), 0x61);
+        assert_eq!(Keysym::from_char('é').raw(), 0xe9);
+        assert_eq!(Keysym::from_char('€').raw(), 0x0100_20ac);
+        assert_eq!(Keysym::from_char('\n'), Keysym::RETURN);
+
+        assert_eq!(
+            Keysym::parse_chord("Ctrl+Alt+T"),
+            Some(vec![Keysym::CONTROL, Keysym::ALT, Keysym::from_char('t')])
+        );
+        assert_eq!(Keysym::from_name("F5"), Some(Keysym::new(0xffc2)));
+        assert_eq!(Keysym::parse_chord("Ctrl+Hyper"), None);
     }
 }
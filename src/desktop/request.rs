@@ -2,15 +2,22 @@ use std::{
     collections::HashMap,
     convert::TryFrom,
     fmt::{self, Debug},
+    future::Future,
     marker::PhantomData,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::Duration,
 };
 
+use futures::future::{AbortHandle, BoxFuture};
 use serde::{
     de::{self, Error as SeError, Visitor},
-    Deserialize, Deserializer, Serialize,
+    ser::SerializeTuple,
+    Deserialize, Deserializer, Serialize, Serializer,
 };
 use serde_repr::{Deserialize_repr, Serialize_repr};
-use zvariant::OwnedValue;
+use zvariant::{ObjectPath, OwnedObjectPath, OwnedValue};
 use zvariant_derive::Type;
 
 use super::DESTINATION;
@@ -20,10 +27,12 @@ use crate::{
     Error,
 };
 
-/// A typical response returned by the [`RequestProxy::receive_response`] signal
-/// of a [`RequestProxy`].
+/// A typical response returned by the `Response` signal of a request.
+///
+/// Portal backends reply with one of these to the method calls that end with
+/// an interaction, see [`backend`](crate::backend).
 #[derive(Debug)]
-pub(crate) enum Response<T>
+pub enum Response<T>
 where
     T: for<'de> Deserialize<'de> + zvariant::Type,
 {
@@ -54,9 +63,9 @@ where
 
         impl<'de, T> Visitor<'de> for ResponseVisitor<T>
         where
-            T: Deserialize<'de>,
+            T: for<'d> Deserialize<'d> + zvariant::Type,
         {
-            type Value = (ResponseType, Option<T>);
+            type Value = Response<T>;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
                 write!(
@@ -72,36 +81,51 @@ where
                 let type_: ResponseType = seq.next_element()?.ok_or_else(|| A::Error::custom(
                     "Failed to deserialize the response. Expected a numeric (u) value as the first item of the returned tuple",
                 ))?;
-                if type_ == ResponseType::Success {
-                    let data: T = seq.next_element()?.ok_or_else(|| A::Error::custom(
-                        "Failed to deserialize the response. Expected a vardict (a{sv}) with the returned results",
-                    ))?;
-                    Ok((type_, Some(data)))
-                } else {
-                    Ok((type_, None))
+                match type_ {
+                    ResponseType::Success => {
+                        let data: T = seq.next_element()?.ok_or_else(|| A::Error::custom(
+                            "Failed to deserialize the response. Expected a vardict (a{sv}) with the returned results",
+                        ))?;
+                        Ok(Response::Ok(data))
+                    }
+                    ResponseType::Cancelled => Ok(Response::Err(ResponseError::Cancelled)),
+                    ResponseType::Other => {
+                        let results: HashMap<String, OwnedValue> =
+                            seq.next_element()?.unwrap_or_default();
+                        Ok(Response::Err(ResponseError::Other(results)))
+                    }
                 }
             }
         }
 
-        let visitor = ResponseVisitor::<T>(PhantomData);
-        let response: (ResponseType, Option<T>) = deserializer.deserialize_tuple(2, visitor)?;
-        Ok(response.into())
+        deserializer.deserialize_tuple(2, ResponseVisitor::<T>(PhantomData))
     }
 }
 
-#[doc(hidden)]
-impl<T> From<(ResponseType, Option<T>)> for Response<T>
+impl<T> Serialize for Response<T>
 where
-    T: for<'de> Deserialize<'de> + zvariant::Type,
+    T: for<'de> Deserialize<'de> + Serialize + zvariant::Type,
 {
-    fn from(f: (ResponseType, Option<T>)) -> Self {
-        match f.0 {
-            ResponseType::Success => {
-                Response::Ok(f.1.expect("Expected a valid response, found nothing."))
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut tuple = serializer.serialize_tuple(2)?;
+        match self {
+            Self::Ok(results) => {
+                tuple.serialize_element(&ResponseType::Success)?;
+                tuple.serialize_element(results)?;
+            }
+            Self::Err(ResponseError::Other(results)) => {
+                tuple.serialize_element(&ResponseType::Other)?;
+                tuple.serialize_element(results)?;
+            }
+            Self::Err(ResponseError::Cancelled) => {
+                tuple.serialize_element(&ResponseType::Cancelled)?;
+                tuple.serialize_element(&HashMap::<&str, OwnedValue>::new())?;
             }
-            ResponseType::Cancelled => Response::Err(ResponseError::Cancelled),
-            ResponseType::Other => Response::Err(ResponseError::Other),
         }
+        tuple.end()
     }
 }
 
@@ -116,14 +140,15 @@ impl Debug for BasicResponse {
     }
 }
 
-#[derive(Debug, Copy, PartialEq, Hash, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 /// An error returned a portal request caused by either the user cancelling the
 /// request or something else.
 pub enum ResponseError {
     /// The user canceled the request.
     Cancelled,
-    /// Something else happened.
-    Other,
+    /// Something else happened. Contains the results sent along with the
+    /// response, usually empty.
+    Other(HashMap<String, OwnedValue>),
 }
 
 impl std::error::Error for ResponseError {}
@@ -132,7 +157,7 @@ impl std::fmt::Display for ResponseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Cancelled => f.write_str("Cancelled"),
-            Self::Other => f.write_str("Other"),
+            Self::Other(_) => f.write_str("Other"),
         }
     }
 }
@@ -149,16 +174,6 @@ enum ResponseType {
     Other = 2,
 }
 
-#[doc(hidden)]
-impl From<ResponseError> for ResponseType {
-    fn from(err: ResponseError) -> Self {
-        match err {
-            ResponseError::Other => Self::Other,
-            ResponseError::Cancelled => Self::Cancelled,
-        }
-    }
-}
-
 /// The Request interface is shared by all portal interfaces.
 /// When a portal method is called, the reply includes a handle (i.e. object
 /// path) for a Request object, which will stay alive for the duration of the
@@ -192,13 +207,12 @@ impl<'a> RequestProxy<'a> {
         connection: &zbus::azync::Connection,
         handle_token: &HandleToken,
     ) -> Result<RequestProxy<'a>, Error> {
-        let unique_name = connection.unique_name().unwrap();
+        let unique_name = connection.unique_name().ok_or(Error::NoUniqueName)?;
         let unique_identifier = unique_name.trim_start_matches(':').replace('.', "_");
         let path = zvariant::ObjectPath::try_from(format!(
             "/org/freedesktop/portal/desktop/request/{}/{}",
             unique_identifier, handle_token
-        ))
-        .unwrap();
+        ))?;
         tracing::info!("Creating a org.freedesktop.portal.Request {}", path);
         RequestProxy::new(connection, path).await
     }
@@ -229,7 +243,6 @@ impl<'a> RequestProxy<'a> {
     /// # Specifications
     ///
     /// See also [`Close`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-portal-Request.Close).
-    #[allow(dead_code)]
     #[doc(alias = "Close")]
     pub async fn close(&self) -> Result<(), Error> {
         call_method(&self.0, "Close", &()).await
@@ -243,3 +256,362 @@ impl<'a> Debug for RequestProxy<'a> {
             .finish()
     }
 }
+
+/// The settings of a single portal request, set through [`Request`].
+#[derive(Debug, Default)]
+pub(crate) struct CallOptions {
+    pub(crate) abort_token: Option<AbortToken>,
+    /// Overrides the default timeout of the proxy when set.
+    pub(crate) timeout: Option<Option<Duration>>,
+}
+
+type StartRequest<'a, T> =
+    Box<dyn FnOnce(CallOptions) -> BoxFuture<'a, Result<T, Error>> + Send + 'a>;
+
+/// A portal request, returned by the request based methods of the proxies,
+/// such as
+/// [`FileChooserProxy::open_file`](crate::desktop::file_chooser::FileChooserProxy::open_file).
+///
+/// The request is sent once awaited, the settings of this call only, like its
+/// timeout or the [`AbortToken`] it is registered on, are set beforehand.
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+///
+/// use ashpd::desktop::file_chooser::{FileChooserProxy, OpenFileOptions};
+/// use ashpd::WindowIdentifier;
+///
+/// async fn run() -> ashpd::Result<()> {
+///     let connection = zbus::azync::Connection::session().await?;
+///     let proxy = FileChooserProxy::new(&connection).await?;
+///
+///     let files = proxy
+///         .open_file(
+///             &WindowIdentifier::default(),
+///             "open a file to read",
+///             OpenFileOptions::default(),
+///         )
+///         .timeout(Some(Duration::from_secs(60)))
+///         .await?;
+///
+///     println!("{:#?}", files);
+///     Ok(())
+/// }
+/// ```
+#[must_use = "requests do nothing unless awaited"]
+pub struct Request<'a, T> {
+    call: CallOptions,
+    start: Option<StartRequest<'a, T>>,
+    future: Option<BoxFuture<'a, Result<T, Error>>>,
+}
+
+impl<'a, T> Request<'a, T> {
+    pub(crate) fn new<F, Fut>(start: F) -> Self
+    where
+        F: FnOnce(CallOptions) -> Fut + Send + 'a,
+        Fut: Future<Output = Result<T, Error>> + Send + 'a,
+    {
+        Self {
+            call: CallOptions::default(),
+            start: Some(Box::new(move |call| Box::pin(start(call)))),
+            future: None,
+        }
+    }
+
+    /// Registers the request on `token`, calling [`AbortToken::abort`] closes
+    /// it.
+    pub fn abort_token(mut self, token: &AbortToken) -> Self {
+        self.call.abort_token = Some(token.clone());
+        self
+    }
+
+    /// Sets the timeout of the request, overriding the one set with the
+    /// `set_timeout()` method of the proxy. `None` waits forever.
+    ///
+    /// A request failing to complete in time returns [`Error::Timeout`] and
+    /// is closed.
+    pub fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.call.timeout = Some(timeout);
+        self
+    }
+}
+
+impl<'a, T> Future for Request<'a, T> {
+    type Output = Result<T, Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        if let Some(start) = this.start.take() {
+            this.future = Some(start(std::mem::take(&mut this.call)));
+        }
+        this.future
+            .as_mut()
+            .expect("Request polled after completion")
+            .as_mut()
+            .poll(cx)
+    }
+}
+
+impl<'a, T> Debug for Request<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Request")
+            .field("call", &self.call)
+            .field("started", &self.start.is_none())
+            .finish()
+    }
+}
+
+/// A token used to dismiss pending portal requests.
+///
+/// The requests registered on the token with [`Request::abort_token`] are
+/// closed by [`AbortToken::abort`], ending the related user interaction
+/// (dialogs, etc), and the calls waiting for them return
+/// [`Error::RequestClosed`].
+///
+/// ```rust,no_run
+/// use ashpd::desktop::file_chooser::{FileChooserProxy, OpenFileOptions};
+/// use ashpd::desktop::AbortToken;
+/// use ashpd::WindowIdentifier;
+///
+/// async fn run() -> ashpd::Result<()> {
+///     let connection = zbus::azync::Connection::session().await?;
+///     let proxy = FileChooserProxy::new(&connection).await?;
+///     let token = AbortToken::default();
+///
+///     let identifier = WindowIdentifier::default();
+///     let files = proxy
+///         .open_file(
+///             &identifier,
+///             "open a file to read",
+///             OpenFileOptions::default(),
+///         )
+///         .abort_token(&token);
+///     // Later on, from somewhere else, for example when the user navigates away
+///     let (files, aborted) = futures::join!(files, token.abort());
+///     aborted?;
+///
+///     assert!(matches!(files, Err(ashpd::Error::RequestClosed)));
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AbortToken(Arc<Mutex<AbortState>>);
+
+#[derive(Debug, Default)]
+struct AbortState {
+    aborted: bool,
+    pending: Vec<RegisteredRequest>,
+}
+
+struct RegisteredRequest {
+    connection: zbus::azync::Connection,
+    /// The path computed from the handle token.
+    expected: OwnedObjectPath,
+    /// The path returned by the portal, `None` until it replied.
+    path: Option<OwnedObjectPath>,
+    handle: AbortHandle,
+}
+
+impl Debug for RegisteredRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RegisteredRequest")
+            .field("expected", &self.expected.as_str())
+            .field("path", &self.path.as_ref().map(|path| path.as_str()))
+            .finish()
+    }
+}
+
+impl AbortToken {
+    /// Close the pending portal requests and prevent new ones from being
+    /// started with this token.
+    ///
+    /// The calls waiting for those requests return [`Error::RequestClosed`].
+    /// A request the portal didn't return the path of yet is closed once it
+    /// does.
+    ///
+    /// Every request is closed even if closing one of them fails, the first
+    /// error is returned.
+    pub async fn abort(&self) -> Result<(), Error> {
+        let pending = {
+            let mut state = self.0.lock().unwrap();
+            state.aborted = true;
+            std::mem::take(&mut state.pending)
+        };
+        let mut result = Ok(());
+        for request in pending {
+            request.handle.abort();
+            let path = match request.path {
+                Some(path) => path,
+                // Closed by the caller once the portal replied.
+                None => continue,
+            };
+            if let Err(err) = close_request(&request.connection, path).await {
+                tracing::warn!("Failed to close the request: {}", err);
+                if result.is_ok() {
+                    result = Err(err);
+                }
+            }
+        }
+        result
+    }
+
+    /// Whether [`AbortToken::abort`] was called.
+    pub fn is_aborted(&self) -> bool {
+        self.0.lock().unwrap().aborted
+    }
+
+    pub(crate) fn register(
+        &self,
+        connection: &zbus::azync::Connection,
+        expected: &ObjectPath<'_>,
+        handle: AbortHandle,
+    ) {
+        let mut state = self.0.lock().unwrap();
+        if state.aborted {
+            handle.abort();
+            return;
+        }
+        state.pending.push(RegisteredRequest {
+            connection: connection.clone(),
+            expected: expected.clone().into(),
+            path: None,
+            handle,
+        });
+    }
+
+    /// Record the path the portal returned for the request registered at
+    /// `expected`.
+    ///
+    /// Returns `false` if the token was aborted meanwhile, the caller has to
+    /// close the request then.
+    pub(crate) fn resolve(&self, expected: &ObjectPath<'_>, path: &OwnedObjectPath) -> bool {
+        let mut state = self.0.lock().unwrap();
+        if state.aborted {
+            return false;
+        }
+        if let Some(request) = state
+            .pending
+            .iter_mut()
+            .find(|request| request.expected.as_str() == expected.as_str())
+        {
+            request.path = Some(path.clone());
+        }
+        true
+    }
+
+    pub(crate) fn unregister(&self, expected: &ObjectPath<'_>) {
+        self.0
+            .lock()
+            .unwrap()
+            .pending
+            .retain(|request| request.expected.as_str() != expected.as_str());
+    }
+}
+
+/// Close the request at `path`.
+pub(crate) async fn close_request(
+    connection: &zbus::azync::Connection,
+    path: OwnedObjectPath,
+) -> Result<(), Error> {
+    tracing::info!("Closing the request {}", path.as_str());
+    RequestProxy::new(connection, path.into_inner())
+        .await?
+        .close()
+        .await
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use byteorder::LE;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use serde::Serialize;
+    use zvariant::{EncodingContext, OwnedValue, Value};
+    use zvariant_derive::{DeserializeDict, TypeDict};
+
+    use super::{Response, ResponseError};
+
+    #[derive(DeserializeDict, TypeDict, Debug)]
+    struct Uris {
+        uris: Vec<String>,
+    }
+
+    fn encode<T: Serialize + zvariant::Type>(body: &T) -> Vec<u8> {
+        zvariant::to_bytes(EncodingContext::<LE>::new_dbus(0), body).unwrap()
+    }
+
+    fn decode(bytes: &[u8]) -> zvariant::Result<Response<Uris>> {
+        zvariant::from_slice(bytes, EncodingContext::<LE>::new_dbus(0))
+    }
+
+    #[test]
+    fn response_results() {
+        let mut results = HashMap::new();
+        results.insert("uris", Value::from(vec!["file:///tmp/a.txt"]));
+        let response = decode(&encode(&(0u32, &results))).unwrap();
+        assert!(matches!(response, Response::Ok(Uris { uris }) if uris == ["file:///tmp/a.txt"]));
+
+        let response = decode(&encode(&(1u32, &results))).unwrap();
+        assert!(matches!(response, Response::Err(ResponseError::Cancelled)));
+
+        let response = decode(&encode(&(2u32, &results))).unwrap();
+        match response {
+            Response::Err(ResponseError::Other(results)) => {
+                assert!(results.contains_key("uris"))
+            }
+            response => panic!("Expected ResponseError::Other, got {:?}", response),
+        }
+    }
+
+    #[test]
+    fn response_malformed() {
+        let empty = HashMap::<&str, Value>::new();
+        let mut wrong_type = HashMap::new();
+        wrong_type.insert("uris", Value::from(42u32));
+
+        // Unknown response code
+        assert!(decode(&encode(&(3u32, &empty))).is_err());
+        // Missing results
+        assert!(decode(&encode(&(0u32, &empty))).is_err());
+        assert!(decode(&encode(&(0u32, &wrong_type))).is_err());
+        assert!(decode(&encode(&0u32)).is_err());
+        assert!(decode(&[]).is_err());
+
+        // Truncated bodies
+        let mut results = HashMap::new();
+        results.insert("uris", Value::from(vec!["file:///tmp/a.txt"]));
+        let bytes = encode(&(0u32, &results));
+        for len in 0..bytes.len() {
+            assert!(decode(&bytes[..len]).is_err());
+        }
+    }
+
+    #[test]
+    fn response_random_bytes() {
+        let mut rng = StdRng::seed_from_u64(0x6173_6870_64);
+        let mut results = HashMap::new();
+        results.insert("uris", Value::from(vec!["file:///tmp/a.txt"]));
+        let valid = encode(&(0u32, &results));
+
+        for _ in 0..10_000 {
+            let mut bytes = if rng.gen_bool(0.5) {
+                valid.clone()
+            } else {
+                let len = rng.gen_range(0..64);
+                (0..len).map(|_| rng.gen()).collect()
+            };
+            // Flip a few bytes of the valid body to reach deeper in the parser
+            for _ in 0..rng.gen_range(0..4) {
+                if !bytes.is_empty() {
+                    let index = rng.gen_range(0..bytes.len());
+                    bytes[index] = rng.gen();
+                }
+            }
+            // Must not panic, whatever the outcome
+            let _ = decode(&bytes);
+            let _: zvariant::Result<Response<HashMap<String, OwnedValue>>> =
+                zvariant::from_slice(&bytes, EncodingContext::<LE>::new_dbus(0));
+        }
+    }
+}
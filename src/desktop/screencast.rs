@@ -4,9 +4,10 @@
 //! The portal is currently useless without PipeWire & Rust support.
 //!
 //! ```rust,no_run
-//! use ashpd::desktop::screencast::{CursorMode, ScreenCastProxy, SourceType};
+//! use ashpd::desktop::screencast::{
+//!     CursorMode, ScreenCastProxy, SelectSourcesOptions, SourceType,
+//! };
 //! use ashpd::WindowIdentifier;
-//! use enumflags2::BitFlags;
 //!
 //! async fn run() -> ashpd::Result<()> {
 //!     let connection = zbus::azync::Connection::session().await?;
@@ -17,15 +18,16 @@
 //!     proxy
 //!         .select_sources(
 //!             &session,
-//!             BitFlags::from(CursorMode::Metadata),
-//!             SourceType::Monitor | SourceType::Window,
-//!             true,
+//!             SelectSourcesOptions::default()
+//!                 .cursor_mode(CursorMode::Metadata.into())
+//!                 .types(SourceType::Monitor | SourceType::Window)
+//!                 .multiple(true),
 //!         )
 //!         .await?;
 //!
-//!     let streams = proxy.start(&session, &WindowIdentifier::default()).await?;
+//!     let response = proxy.start(&session, &WindowIdentifier::default()).await?;
 //!
-//!     streams.iter().for_each(|stream| {
+//!     response.streams().iter().for_each(|stream| {
 //!         println!("node id: {}", stream.pipe_wire_node_id());
 //!         println!("size: {:?}", stream.size());
 //!         println!("position: {:?}", stream.position());
@@ -33,6 +35,37 @@
 //!     Ok(())
 //! }
 //! ```
+//!
+//! How to restore a previous screen cast session without asking the user to
+//! select the sources again. The restore token is single use, the one returned
+//! by the latest [`ScreenCastProxy::start`] call has to be stored for the next
+//! run.
+//!
+//! ```rust,no_run
+//! use ashpd::desktop::screencast::{
+//!     CursorMode, PersistMode, ScreenCastProxy, SelectSourcesOptions, SourceType,
+//! };
+//! use ashpd::WindowIdentifier;
+//!
+//! async fn run(restore_token: Option<&str>) -> ashpd::Result<Option<String>> {
+//!     let connection = zbus::azync::Connection::session().await?;
+//!     let proxy = ScreenCastProxy::new(&connection).await?;
+//!
+//!     let session = proxy.create_session().await?;
+//!
+//!     let mut options = SelectSourcesOptions::default()
+//!         .cursor_mode(CursorMode::Embedded.into())
+//!         .types(SourceType::Monitor.into())
+//!         .persist_mode(PersistMode::ExplicitlyRevoked);
+//!     if let Some(restore_token) = restore_token {
+//!         options = options.restore_token(restore_token);
+//!     }
+//!     proxy.select_sources(&session, options).await?;
+//!
+//!     let response = proxy.start(&session, &WindowIdentifier::default()).await?;
+//!     Ok(response.restore_token().map(ToOwned::to_owned))
+//! }
+//! ```
 
 use std::{
     collections::HashMap,
@@ -47,9 +80,11 @@ use serde_repr::{Deserialize_repr, Serialize_repr};
 use zvariant::{Fd, Value};
 use zvariant_derive::{DeserializeDict, SerializeDict, Type, TypeDict};
 
-use super::{HandleToken, SessionProxy, DESTINATION, PATH};
+use super::{HandleToken, Request, SessionProxy, DESTINATION, PATH};
 use crate::{
-    helpers::{call_basic_response_method, call_method, call_request_method},
+    helpers::{
+        call_basic_response_method, call_method, call_request_method, get_property, require_version,
+    },
     Error, WindowIdentifier,
 };
 
@@ -78,6 +113,24 @@ pub enum CursorMode {
     Metadata = 4,
 }
 
+#[derive(Serialize_repr, Deserialize_repr, PartialEq, Eq, Debug, Copy, Clone, Type)]
+#[repr(u32)]
+/// How the permission granted by the user for a session should be persisted.
+pub enum PersistMode {
+    /// Do not persist, the user is asked on every session.
+    DoNot = 0,
+    /// Persist while the application is running.
+    Application = 1,
+    /// Persist until the permission is explicitly revoked by the user.
+    ExplicitlyRevoked = 2,
+}
+
+impl Default for PersistMode {
+    fn default() -> Self {
+        Self::DoNot
+    }
+}
+
 #[derive(SerializeDict, DeserializeDict, TypeDict, Debug, Default)]
 /// Specified options for a [`ScreenCastProxy::create_session`] request.
 struct CreateSessionOptions {
@@ -89,7 +142,7 @@ struct CreateSessionOptions {
 
 #[derive(SerializeDict, DeserializeDict, TypeDict, Debug, Default)]
 /// Specified options for a [`ScreenCastProxy::select_sources`] request.
-struct SelectSourcesOptions {
+pub struct SelectSourcesOptions {
     /// A string that will be used as the last element of the handle.
     handle_token: HandleToken,
     /// What types of content to record.
@@ -98,6 +151,10 @@ struct SelectSourcesOptions {
     multiple: Option<bool>,
     /// Determines how the cursor will be drawn in the screen cast stream.
     cursor_mode: Option<BitFlags<CursorMode>>,
+    /// The token returned by a previous session, used to restore it.
+    restore_token: Option<String>,
+    /// How the permission should be persisted.
+    persist_mode: Option<PersistMode>,
 }
 
 impl SelectSourcesOptions {
@@ -118,11 +175,48 @@ impl SelectSourcesOptions {
         self.types = Some(types);
         self
     }
+
+    /// Sets the token of a previous session to restore.
+    pub fn restore_token(mut self, restore_token: &str) -> Self {
+        self.restore_token = Some(restore_token.to_string());
+        self
+    }
+
+    /// Sets how the permission should be persisted.
+    pub fn persist_mode(mut self, persist_mode: PersistMode) -> Self {
+        self.persist_mode = Some(persist_mode);
+        self
+    }
+
+    /// Whether to allow selecting multiple sources.
+    pub fn get_multiple(&self) -> Option<bool> {
+        self.multiple
+    }
+
+    /// How the cursor will be drawn on the screen cast stream.
+    pub fn get_cursor_mode(&self) -> Option<BitFlags<CursorMode>> {
+        self.cursor_mode
+    }
+
+    /// The types of content to record.
+    pub fn get_types(&self) -> Option<BitFlags<SourceType>> {
+        self.types
+    }
+
+    /// The token of a previous session to restore.
+    pub fn get_restore_token(&self) -> Option<&str> {
+        self.restore_token.as_deref()
+    }
+
+    /// How the permission should be persisted.
+    pub fn get_persist_mode(&self) -> Option<PersistMode> {
+        self.persist_mode
+    }
 }
 
 #[derive(SerializeDict, DeserializeDict, TypeDict, Debug, Default)]
 /// Specified options for a [`ScreenCastProxy::start`] request.
-struct StartCastOptions {
+pub struct StartCastOptions {
     /// A string that will be used as the last element of the handle.
     handle_token: HandleToken,
 }
@@ -138,13 +232,45 @@ struct CreateSession {
 
 #[derive(SerializeDict, DeserializeDict, TypeDict)]
 /// A response to a [`ScreenCastProxy::start`] request.
-struct Streams {
-    pub streams: Vec<Stream>,
+pub struct Streams {
+    streams: Vec<Stream>,
+    restore_token: Option<String>,
+}
+
+impl Streams {
+    /// Create a new response from the started streams.
+    pub fn new(streams: Vec<Stream>) -> Self {
+        Self {
+            streams,
+            restore_token: None,
+        }
+    }
+
+    /// Sets the token that can be used to restore the session.
+    pub fn with_restore_token(mut self, restore_token: &str) -> Self {
+        self.restore_token = Some(restore_token.to_string());
+        self
+    }
+
+    /// The started streams.
+    pub fn streams(&self) -> &[Stream] {
+        &self.streams
+    }
+
+    /// The token to pass to [`ScreenCastProxy::select_sources`] to restore
+    /// the session, only set if a [`PersistMode`] other than
+    /// [`PersistMode::DoNot`] was requested and granted.
+    pub fn restore_token(&self) -> Option<&str> {
+        self.restore_token.as_deref()
+    }
 }
 
 impl Debug for Streams {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_list().entries(self.streams.iter()).finish()
+        f.debug_struct("Streams")
+            .field("streams", &self.streams)
+            .field("restore_token", &self.restore_token)
+            .finish()
     }
 }
 
@@ -153,6 +279,21 @@ impl Debug for Streams {
 pub struct Stream(u32, StreamProperties);
 
 impl Stream {
+    /// Create a new stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `pipe_wire_node_id` - The PipeWire stream Node ID.
+    /// * `position` - The position (x, y) in the compositor coordinate space.
+    /// * `size` - The size (width, height) in the compositor coordinate space.
+    pub fn new(
+        pipe_wire_node_id: u32,
+        position: Option<(i32, i32)>,
+        size: Option<(i32, i32)>,
+    ) -> Self {
+        Self(pipe_wire_node_id, StreamProperties { position, size })
+    }
+
     /// The PipeWire stream Node ID
     pub fn pipe_wire_node_id(&self) -> u32 {
         self.0
@@ -199,7 +340,7 @@ struct StreamProperties {
 /// Wrapper of the DBus interface: [`org.freedesktop.portal.ScreenCast`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.portal.ScreenCast).
 #[derive(Debug)]
 #[doc(alias = "org.freedesktop.portal.ScreenCast")]
-pub struct ScreenCastProxy<'a>(zbus::azync::Proxy<'a>);
+pub struct ScreenCastProxy<'a>(crate::helpers::Proxy<'a>);
 
 impl<'a> ScreenCastProxy<'a> {
     /// Create a new instance of [`ScreenCastProxy`].
@@ -210,7 +351,7 @@ impl<'a> ScreenCastProxy<'a> {
             .destination(DESTINATION)?
             .build()
             .await?;
-        Ok(Self(proxy))
+        Ok(Self(proxy.into()))
     }
 
     /// Get a reference to the underlying Proxy.
@@ -218,27 +359,33 @@ impl<'a> ScreenCastProxy<'a> {
         &self.0
     }
 
+    proxy_set_timeout!();
+
+    proxy_version!(ScreenCast);
+
     /// Create a screen cast session.
     ///
     /// # Specifications
     ///
     /// See also [`CreateSession`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-portal-ScreenCast.CreateSession).
     #[doc(alias = "CreateSession")]
-    pub async fn create_session(&self) -> Result<SessionProxy<'a>, Error> {
+    pub fn create_session(&self) -> Request<'_, SessionProxy<'a>> {
         let options = CreateSessionOptions::default();
-        let (session, proxy) = futures::try_join!(
-            call_request_method::<CreateSession, CreateSessionOptions>(
-                &self.0,
-                &options.handle_token,
-                "CreateSession",
-                &options
-            )
-            .into_future(),
-            SessionProxy::from_unique_name(self.0.connection(), &options.session_handle_token)
+        Request::new(move |call| async move {
+            let (session, proxy) = futures::try_join!(
+                call_request_method::<CreateSession, CreateSessionOptions>(
+                    &self.0,
+                    &call,
+                    &options.handle_token,
+                    "CreateSession",
+                    &options
+                )
                 .into_future(),
-        )?;
-        assert_eq!(proxy.inner().path().as_str(), &session.session_handle);
-        Ok(proxy)
+                SessionProxy::from_unique_name(self.0.connection(), &options.session_handle_token)
+                    .into_future(),
+            )?;
+            proxy.resolve(&session.session_handle).await
+        })
     }
 
     /// Open a file descriptor to the PipeWire remote where the screen cast
@@ -276,33 +423,39 @@ impl<'a> ScreenCastProxy<'a> {
     ///
     /// * `session` - A [`SessionProxy`], created with
     ///   [`create_session()`][`ScreenCastProxy::create_session`].
-    /// * `cursor_mode` - Sets how the cursor will be drawn on the screen cast
-    ///   stream.
-    /// * `types` - Sets the types of content to record.
-    /// * `multiple`- Sets whether to allow selecting multiple sources.
+    /// * `options` - [`SelectSourcesOptions`].
+    ///
+    /// **Note** the [`restore_token`][`SelectSourcesOptions::restore_token`] &
+    /// [`persist_mode`][`SelectSourcesOptions::persist_mode`] options require
+    /// version 4 of the interface, an [`Error::RequiresVersion`] is returned
+    /// otherwise. When the session was created by the
+    /// [`RemoteDesktopProxy`](crate::desktop::remote_desktop::RemoteDesktopProxy),
+    /// they have to be passed to
+    /// [`RemoteDesktopProxy::select_devices`](crate::desktop::remote_desktop::RemoteDesktopProxy::select_devices)
+    /// instead.
     ///
     /// # Specifications
     ///
     /// See also [`SelectSources`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-portal-ScreenCast.SelectSources).
     #[doc(alias = "SelectSources")]
-    pub async fn select_sources(
-        &self,
-        session: &SessionProxy<'_>,
-        cursor_mode: BitFlags<CursorMode>,
-        types: BitFlags<SourceType>,
-        multiple: bool,
-    ) -> Result<(), Error> {
-        let options = SelectSourcesOptions::default()
-            .cursor_mode(cursor_mode)
-            .multiple(multiple)
-            .types(types);
-        call_basic_response_method(
-            &self.0,
-            &options.handle_token,
-            "SelectSources",
-            &(session, &options),
-        )
-        .await
+    pub fn select_sources<'r>(
+        &'r self,
+        session: &'r SessionProxy<'_>,
+        options: SelectSourcesOptions,
+    ) -> Request<'r, ()> {
+        Request::new(move |call| async move {
+            if options.restore_token.is_some() || options.persist_mode.is_some() {
+                require_version(&self.0, 4).await?;
+            }
+            call_basic_response_method(
+                &self.0,
+                &call,
+                &options.handle_token,
+                "SelectSources",
+                &(session, &options),
+            )
+            .await
+        })
     }
 
     /// Start the screen cast session.
@@ -318,24 +471,31 @@ impl<'a> ScreenCastProxy<'a> {
     ///   [`create_session()`][`ScreenCastProxy::create_session`].
     /// * `identifier` - Identifier for the application window.
     ///
+    /// # Returns
+    ///
+    /// The started streams, along with a restore token if the session can be
+    /// restored later.
+    ///
     /// # Specifications
     ///
     /// See also [`Start`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-portal-ScreenCast.Start).
     #[doc(alias = "Start")]
-    pub async fn start(
-        &self,
-        session: &SessionProxy<'_>,
-        identifier: &WindowIdentifier,
-    ) -> Result<Vec<Stream>, Error> {
+    pub fn start<'r>(
+        &'r self,
+        session: &'r SessionProxy<'_>,
+        identifier: &'r WindowIdentifier,
+    ) -> Request<'r, Streams> {
         let options = StartCastOptions::default();
-        let streams: Streams = call_request_method(
-            &self.0,
-            &options.handle_token,
-            "Start",
-            &(session, &identifier, &options),
-        )
-        .await?;
-        Ok(streams.streams.to_vec())
+        Request::new(move |call| async move {
+            call_request_method(
+                &self.0,
+                &call,
+                &options.handle_token,
+                "Start",
+                &(session, &identifier, &options),
+            )
+            .await
+        })
     }
 
     /// Available cursor mode.
@@ -345,10 +505,7 @@ impl<'a> ScreenCastProxy<'a> {
     /// See also [`AvailableCursorModes`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-property-org-freedesktop-portal-ScreenCast.AvailableCursorModes).
     #[doc(alias = "AvailableCursorModes")]
     pub async fn available_cursor_modes(&self) -> Result<BitFlags<CursorMode>, Error> {
-        self.inner()
-            .get_property::<BitFlags<CursorMode>>("AvailableCursorModes")
-            .await
-            .map_err(From::from)
+        get_property::<BitFlags<CursorMode>>(&self.0, "AvailableCursorModes").await
     }
 
     /// Available source types.
@@ -358,9 +515,6 @@ impl<'a> ScreenCastProxy<'a> {
     /// See also [`AvailableSourceTypes`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-property-org-freedesktop-portal-ScreenCast.AvailableSourceTypes).
     #[doc(alias = "AvailableSourceTypes")]
     pub async fn available_source_types(&self) -> Result<BitFlags<SourceType>, Error> {
-        self.inner()
-            .get_property::<BitFlags<SourceType>>("AvailableSourceTypes")
-            .await
-            .map_err(From::from)
+        get_property::<BitFlags<SourceType>>(&self.0, "AvailableSourceTypes").await
     }
 }
@@ -64,12 +64,12 @@ use std::fmt::Debug;
 
 use zvariant_derive::{DeserializeDict, SerializeDict, TypeDict};
 
-use super::{HandleToken, DESTINATION, PATH};
+use super::{HandleToken, Request, DESTINATION, PATH};
 use crate::{helpers::call_request_method, Error, WindowIdentifier};
 
 #[derive(SerializeDict, DeserializeDict, TypeDict, Clone, Debug, Default)]
 /// Specified options for a [`ScreenshotProxy::screenshot`] request.
-struct ScreenshotOptions {
+pub struct ScreenshotOptions {
     /// A string that will be used as the last element of the handle.
     handle_token: HandleToken,
     /// Whether the dialog should be modal.
@@ -92,15 +92,43 @@ impl ScreenshotOptions {
         self.interactive = Some(interactive);
         self
     }
+
+    /// Whether the dialog should be modal.
+    pub fn get_modal(&self) -> Option<bool> {
+        self.modal
+    }
+
+    /// Whether the dialog should offer customization before a screenshot.
+    pub fn get_interactive(&self) -> Option<bool> {
+        self.interactive
+    }
 }
 
 #[derive(DeserializeDict, SerializeDict, Clone, TypeDict)]
 /// A response to a [`ScreenshotProxy::screenshot`] request.
-struct Screenshot {
+pub struct Screenshot {
     /// The screenshot uri.
     uri: String,
 }
 
+impl Screenshot {
+    /// Create a new screenshot response.
+    ///
+    /// # Arguments
+    ///
+    /// * `uri` - The screenshot uri.
+    pub fn new(uri: &str) -> Self {
+        Self {
+            uri: uri.to_string(),
+        }
+    }
+
+    /// The screenshot uri.
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+}
+
 impl Debug for Screenshot {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(&self.uri)
@@ -109,7 +137,7 @@ impl Debug for Screenshot {
 
 #[derive(SerializeDict, DeserializeDict, TypeDict, Clone, Debug, Default)]
 /// Specified options for a [`ScreenshotProxy::pick_color`] request.
-struct PickColorOptions {
+pub struct PickColorOptions {
     /// A string that will be used as the last element of the handle.
     handle_token: HandleToken,
 }
@@ -122,6 +150,13 @@ pub struct Color {
 }
 
 impl Color {
+    /// Create a new color from its normalized components.
+    pub fn new(red: f64, green: f64, blue: f64) -> Self {
+        Self {
+            color: [red, green, blue],
+        }
+    }
+
     /// Red.
     pub fn red(&self) -> f64 {
         self.color[0]
@@ -188,7 +223,7 @@ impl std::fmt::Display for Color {
 /// Wrapper of the DBus interface: [`org.freedesktop.portal.Screenshot`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.portal.Screenshot).
 #[derive(Debug)]
 #[doc(alias = "org.freedesktop.portal.Screenshot")]
-pub struct ScreenshotProxy<'a>(zbus::azync::Proxy<'a>);
+pub struct ScreenshotProxy<'a>(crate::helpers::Proxy<'a>);
 
 impl<'a> ScreenshotProxy<'a> {
     /// Create a new instance of [`ScreenshotProxy`].
@@ -199,7 +234,7 @@ impl<'a> ScreenshotProxy<'a> {
             .destination(DESTINATION)?
             .build()
             .await?;
-        Ok(Self(proxy))
+        Ok(Self(proxy.into()))
     }
 
     /// Get a reference to the underlying Proxy.
@@ -207,6 +242,10 @@ impl<'a> ScreenshotProxy<'a> {
         &self.0
     }
 
+    proxy_set_timeout!();
+
+    proxy_version!(Screenshot);
+
     /// Obtains the color of a single pixel.
     ///
     /// # Arguments
@@ -217,15 +256,18 @@ impl<'a> ScreenshotProxy<'a> {
     ///
     /// See also [`PickColor`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-portal-Screenshot.PickColor).
     #[doc(alias = "PickColor")]
-    pub async fn pick_color(&self, identifier: &WindowIdentifier) -> Result<Color, Error> {
+    pub fn pick_color<'r>(&'r self, identifier: &'r WindowIdentifier) -> Request<'r, Color> {
         let options = PickColorOptions::default();
-        call_request_method(
-            &self.0,
-            &options.handle_token,
-            "PickColor",
-            &(&identifier, &options),
-        )
-        .await
+        Request::new(move |call| async move {
+            call_request_method(
+                &self.0,
+                &call,
+                &options.handle_token,
+                "PickColor",
+                &(&identifier, &options),
+            )
+            .await
+        })
     }
 
     /// Takes a screenshot.
@@ -245,23 +287,26 @@ impl<'a> ScreenshotProxy<'a> {
     ///
     /// See also [`Screenshot`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-portal-Screenshot.Screenshot).
     #[doc(alias = "Screenshot")]
-    pub async fn screenshot(
-        &self,
-        identifier: &WindowIdentifier,
+    pub fn screenshot<'r>(
+        &'r self,
+        identifier: &'r WindowIdentifier,
         interactive: bool,
         modal: bool,
-    ) -> Result<String, Error> {
+    ) -> Request<'r, String> {
         let options = ScreenshotOptions::default()
             .interactive(interactive)
             .modal(modal);
-        let response: Screenshot = call_request_method(
-            &self.0,
-            &options.handle_token,
-            "Screenshot",
-            &(&identifier, &options),
-        )
-        .await?;
-        Ok(response.uri)
+        Request::new(move |call| async move {
+            let response: Screenshot = call_request_method(
+                &self.0,
+                &call,
+                &options.handle_token,
+                "Screenshot",
+                &(&identifier, &options),
+            )
+            .await?;
+            Ok(response.uri)
+        })
     }
 }
 
@@ -48,7 +48,7 @@ impl RetrieveOptions {
 /// Wrapper of the DBus interface: [`org.freedesktop.portal.Secret`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.portal.Secret).
 #[derive(Debug)]
 #[doc(alias = "org.freedesktop.portal.Secret")]
-pub struct SecretProxy<'a>(zbus::azync::Proxy<'a>);
+pub struct SecretProxy<'a>(crate::helpers::Proxy<'a>);
 
 impl<'a> SecretProxy<'a> {
     /// Create a new instance of [`SecretProxy`].
@@ -59,7 +59,7 @@ impl<'a> SecretProxy<'a> {
             .destination(DESTINATION)?
             .build()
             .await?;
-        Ok(Self(proxy))
+        Ok(Self(proxy.into()))
     }
 
     /// Get a reference to the underlying Proxy.
@@ -67,6 +67,10 @@ impl<'a> SecretProxy<'a> {
         &self.0
     }
 
+    proxy_set_timeout!();
+
+    proxy_version!(Secret);
+
     /// Retrieves a master secret for a sandboxed application.
     ///
     /// # Arguments
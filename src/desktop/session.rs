@@ -1,16 +1,58 @@
-use std::{collections::HashMap, convert::TryFrom, fmt::Debug};
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock,
+    },
+};
 
+use futures::{future::BoxFuture, Stream};
+use once_cell::sync::Lazy;
 use serde::{Serialize, Serializer};
-use zvariant::{ObjectPath, OwnedValue, Signature};
+use zvariant::{ObjectPath, OwnedObjectPath, OwnedValue, Signature};
 
 use crate::{
     desktop::{HandleToken, DESTINATION},
-    helpers::{call_method, receive_signal},
+    helpers::{call_method, receive_signal, receive_signal_stream},
     Error,
 };
 
 pub type SessionDetails = HashMap<String, OwnedValue>;
 
+type Spawner = dyn Fn(BoxFuture<'static, ()>) + Send + Sync;
+
+static SESSION_EXECUTOR: Lazy<RwLock<Option<Arc<Spawner>>>> = Lazy::new(Default::default);
+
+/// Sets the executor running the [`SessionProxy::close`] calls of the sessions
+/// dropped while still open, and the release of the dropped
+/// [`InhibitGuard`](crate::desktop::inhibit::InhibitGuard)s.
+///
+/// By default, each call runs on a new thread.
+///
+/// ```rust,no_run
+/// ashpd::desktop::set_session_executor(|future| {
+///     std::thread::spawn(move || futures::executor::block_on(future));
+/// });
+/// ```
+pub fn set_session_executor<F>(spawn: F)
+where
+    F: Fn(BoxFuture<'static, ()>) + Send + Sync + 'static,
+{
+    *SESSION_EXECUTOR.write().unwrap() = Some(Arc::new(spawn));
+}
+
+pub(crate) fn spawn(future: BoxFuture<'static, ()>) {
+    let executor = SESSION_EXECUTOR.read().unwrap().clone();
+    match executor {
+        Some(spawn) => spawn(future),
+        None => {
+            std::thread::spawn(move || async_io::block_on(future));
+        }
+    }
+}
+
 /// The Session interface is shared by all portal interfaces that involve long
 /// lived sessions. When a method that creates a session is called, if
 /// successful, the reply will include a session handle (i.e. object path) for a
@@ -21,9 +63,14 @@ pub type SessionDetails = HashMap<String, OwnedValue>;
 /// and a signal [`SessionProxy::receive_closed`]. Whether it is allowed to
 /// directly call [`SessionProxy::close`] depends on the interface.
 ///
+/// The sessions returned by the portals are closed when dropped, unless they
+/// were closed already or turned into their object path with
+/// [`SessionProxy::into_raw`]. As dropping can't wait, the call runs on the
+/// executor set with [`set_session_executor`].
+///
 /// Wrapper of the DBus interface: [`org.freedesktop.portal.Session`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.portal.Session).
 #[doc(alias = "org.freedesktop.portal.Session")]
-pub struct SessionProxy<'a>(zbus::azync::Proxy<'a>);
+pub struct SessionProxy<'a>(zbus::azync::Proxy<'a>, AtomicBool);
 
 impl<'a> SessionProxy<'a> {
     /// Create a new instance of [`SessionProxy`].
@@ -39,24 +86,45 @@ impl<'a> SessionProxy<'a> {
             .destination(DESTINATION)?
             .build()
             .await?;
-        Ok(Self(proxy))
+        Ok(Self(proxy, AtomicBool::new(false)))
     }
 
     pub(crate) async fn from_unique_name(
         connection: &zbus::azync::Connection,
         handle_token: &HandleToken,
     ) -> Result<SessionProxy<'a>, crate::Error> {
-        let unique_name = connection.unique_name().unwrap();
+        let unique_name = connection.unique_name().ok_or(Error::NoUniqueName)?;
         let unique_identifier = unique_name.trim_start_matches(':').replace('.', "_");
         let path = zvariant::ObjectPath::try_from(format!(
             "/org/freedesktop/portal/desktop/session/{}/{}",
             unique_identifier, handle_token
-        ))
-        .unwrap();
+        ))?;
         tracing::info!("Creating a org.freedesktop.portal.Session {}", path);
         SessionProxy::new(connection, path).await
     }
 
+    /// The session at `path`, the session object path returned by the portal.
+    ///
+    /// Portals older than 0.9 ignore the session handle token and create the
+    /// session at another path than the one computed by
+    /// [`SessionProxy::from_unique_name`].
+    pub(crate) async fn resolve(self, path: &str) -> Result<SessionProxy<'a>, Error> {
+        let session = if self.inner().path().as_str() == path {
+            self
+        } else {
+            tracing::warn!(
+                "Expected the session at {}, the portal returned {}",
+                self.inner().path(),
+                path
+            );
+            let path = ObjectPath::try_from(path.to_owned())?;
+            SessionProxy::new(self.inner().connection(), path).await?
+        };
+        // The session is created, close it once dropped.
+        session.1.store(true, Ordering::SeqCst);
+        Ok(session)
+    }
+
     /// Get a reference to the underlying Proxy.
     pub fn inner(&self) -> &zbus::azync::Proxy<'_> {
         &self.0
@@ -72,6 +140,22 @@ impl<'a> SessionProxy<'a> {
         receive_signal(&self.0, "Closed").await
     }
 
+    /// Emitted when a session is closed.
+    ///
+    /// Unlike
+    /// [`receive_closed()`][`SessionProxy::receive_closed`],
+    /// the returned stream stays subscribed and yields every emitted signal.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`Closed`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-signal-org-freedesktop-portal-Session.Closed).
+    #[doc(alias = "Closed")]
+    pub async fn receive_closed_stream(
+        &self,
+    ) -> Result<impl Stream<Item = SessionDetails> + '_, Error> {
+        receive_signal_stream(&self.0, "Closed").await
+    }
+
     /// Closes the portal session to which this object refers and ends all
     /// related user interaction (dialogs, etc).
     ///
@@ -80,7 +164,52 @@ impl<'a> SessionProxy<'a> {
     /// See also [`Close`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-portal-Session.Close).
     #[doc(alias = "Close")]
     pub async fn close(&self) -> Result<(), Error> {
-        call_method(&self.0, "Close", &()).await
+        call_method(&self.0, "Close", &()).await?;
+        self.1.store(false, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Wait for the session to be closed, by the portal or the user, and
+    /// return the details of the [`Closed`](SessionProxy::receive_closed)
+    /// signal.
+    ///
+    /// The session isn't closed again when dropped afterwards.
+    pub async fn closed(&self) -> Result<SessionDetails, Error> {
+        let details = self.receive_closed().await?;
+        self.1.store(false, Ordering::SeqCst);
+        Ok(details)
+    }
+
+    /// Consume the proxy without closing the session, returning its object
+    /// path.
+    ///
+    /// The session stays open until closed by the portal, the user or with a
+    /// new `org.freedesktop.portal.Session` proxy at the returned path.
+    pub fn into_raw(self) -> OwnedObjectPath {
+        self.1.store(false, Ordering::SeqCst);
+        self.0.path().clone().into()
+    }
+}
+
+impl<'a> Drop for SessionProxy<'a> {
+    fn drop(&mut self) {
+        if !*self.1.get_mut() {
+            return;
+        }
+        let connection = self.0.connection().clone();
+        let path = OwnedObjectPath::from(self.0.path().clone());
+        tracing::info!("Closing the dropped session {}", path.as_str());
+        spawn(Box::pin(async move {
+            let closed = async {
+                SessionProxy::new(&connection, path.into_inner())
+                    .await?
+                    .close()
+                    .await
+            };
+            if let Err(err) = closed.await {
+                tracing::warn!("Failed to close the dropped session: {}", err);
+            }
+        }));
     }
 }
 
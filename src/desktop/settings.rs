@@ -1,5 +1,6 @@
 //! ```rust,no_run
 //! use ashpd::desktop::settings::SettingsProxy;
+//! use futures::StreamExt;
 //!
 //! async fn run() -> ashpd::Result<()> {
 //!     let connection = zbus::azync::Connection::session().await?;
@@ -20,19 +21,26 @@
 //!     println!("{}", setting.key());
 //!     println!("{:#?}", setting.value());
 //!
+//!     let changes = proxy.receive_setting_changed_stream().await?;
+//!     futures::pin_mut!(changes);
+//!     while let Some(setting) = changes.next().await {
+//!         println!("{} changed", setting.key());
+//!     }
+//!
 //!     Ok(())
 //! }
 //! ```
 
 use std::{collections::HashMap, convert::TryFrom, fmt::Debug};
 
+use futures::Stream;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use zvariant::OwnedValue;
 use zvariant_derive::Type;
 
 use super::{DESTINATION, PATH};
 use crate::{
-    helpers::{call_method, receive_signal},
+    helpers::{call_method, receive_signal, receive_signal_stream},
     Error,
 };
 
@@ -44,6 +52,17 @@ pub type Namespace = HashMap<String, OwnedValue>;
 pub struct Setting(String, String, OwnedValue);
 
 impl Setting {
+    /// Create a new setting.
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - The setting namespace.
+    /// * `key` - The setting key.
+    /// * `value` - The setting value.
+    pub fn new(namespace: &str, key: &str, value: OwnedValue) -> Self {
+        Self(namespace.to_string(), key.to_string(), value)
+    }
+
     /// The setting namespace.
     pub fn namespace(&self) -> &str {
         &self.0
@@ -77,7 +96,7 @@ impl std::fmt::Debug for Setting {
 /// Wrapper of the DBus interface: [`org.freedesktop.portal.Settings`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.portal.Settings).
 #[derive(Debug)]
 #[doc(alias = "org.freedesktop.portal.Settings")]
-pub struct SettingsProxy<'a>(zbus::azync::Proxy<'a>);
+pub struct SettingsProxy<'a>(crate::helpers::Proxy<'a>);
 
 impl<'a> SettingsProxy<'a> {
     /// Create a new instance of [`SettingsProxy`].
@@ -88,7 +107,7 @@ impl<'a> SettingsProxy<'a> {
             .destination(DESTINATION)?
             .build()
             .await?;
-        Ok(Self(proxy))
+        Ok(Self(proxy.into()))
     }
 
     /// Get a reference to the underlying Proxy.
@@ -96,6 +115,10 @@ impl<'a> SettingsProxy<'a> {
         &self.0
     }
 
+    proxy_set_timeout!();
+
+    proxy_version!(Settings);
+
     /// Reads a single value. Returns an error on any unknown namespace or key.
     ///
     /// # Arguments
@@ -152,4 +175,20 @@ impl<'a> SettingsProxy<'a> {
     pub async fn receive_setting_changed(&self) -> Result<Setting, Error> {
         receive_signal(&self.0, "SettingChanged").await
     }
+
+    /// Signal emitted when a setting changes.
+    ///
+    /// Unlike
+    /// [`receive_setting_changed()`][`SettingsProxy::receive_setting_changed`],
+    /// the returned stream stays subscribed and yields every emitted signal.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`SettingChanged`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-signal-org-freedesktop-portal-Settings.SettingChanged).
+    #[doc(alias = "SettingChanged")]
+    pub async fn receive_setting_changed_stream(
+        &self,
+    ) -> Result<impl Stream<Item = Setting> + '_, Error> {
+        receive_signal_stream(&self.0, "SettingChanged").await
+    }
 }
@@ -52,7 +52,7 @@ enum TrashStatus {
 /// Wrapper of the DBus interface: [`org.freedesktop.portal.Trash`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.portal.Trash).
 #[derive(Debug)]
 #[doc(alias = "org.freedesktop.portal.Trash")]
-pub struct TrashProxy<'a>(zbus::azync::Proxy<'a>);
+pub struct TrashProxy<'a>(crate::helpers::Proxy<'a>);
 
 impl<'a> TrashProxy<'a> {
     /// Create a new instance of [`TrashProxy`].
@@ -63,7 +63,7 @@ impl<'a> TrashProxy<'a> {
             .destination(DESTINATION)?
             .build()
             .await?;
-        Ok(Self(proxy))
+        Ok(Self(proxy.into()))
     }
 
     /// Get a reference to the underlying Proxy.
@@ -71,6 +71,10 @@ impl<'a> TrashProxy<'a> {
         &self.0
     }
 
+    proxy_set_timeout!();
+
+    proxy_version!(Trash);
+
     /// Sends a file to the trashcan.
     /// Applications are allowed to trash a file if they can open it in
     /// read/write mode.
@@ -89,7 +93,9 @@ impl<'a> TrashProxy<'a> {
     {
         let status = call_method(&self.0, "TrashFile", &(Fd::from(fd.as_raw_fd()))).await?;
         match status {
-            TrashStatus::Failed => Err(Error::Portal(PortalError::Failed)),
+            TrashStatus::Failed => Err(Error::Portal(PortalError::Failed(
+                "Failed to trash the file".to_owned(),
+            ))),
             TrashStatus::Succeeded => Ok(()),
         }
     }
@@ -75,7 +75,7 @@ use zvariant::{Fd, Signature, Type};
 use zvariant_derive::{DeserializeDict, SerializeDict, TypeDict};
 
 use crate::{
-    desktop::{HandleToken, DESTINATION, PATH},
+    desktop::{HandleToken, Request, DESTINATION, PATH},
     helpers::call_basic_response_method,
     Error, WindowIdentifier,
 };
@@ -144,7 +144,7 @@ impl WallpaperOptions {
 /// Wrapper of the DBus interface: [`org.freedesktop.portal.Wallpaper`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.portal.Wallpaper).
 #[derive(Debug)]
 #[doc(alias = "org.freedesktop.portal.Wallpaper")]
-pub struct WallpaperProxy<'a>(zbus::azync::Proxy<'a>);
+pub struct WallpaperProxy<'a>(crate::helpers::Proxy<'a>);
 
 impl<'a> WallpaperProxy<'a> {
     /// Create a new instance of [`WallpaperProxy`].
@@ -155,7 +155,7 @@ impl<'a> WallpaperProxy<'a> {
             .destination(DESTINATION)?
             .build()
             .await?;
-        Ok(Self(proxy))
+        Ok(Self(proxy.into()))
     }
 
     /// Get a reference to the underlying Proxy.
@@ -163,6 +163,10 @@ impl<'a> WallpaperProxy<'a> {
         &self.0
     }
 
+    proxy_set_timeout!();
+
+    proxy_version!(Wallpaper);
+
     /// Sets the lock-screen, background or both wallpaper's from a file
     /// descriptor.
     ///
@@ -177,26 +181,30 @@ impl<'a> WallpaperProxy<'a> {
     ///
     /// See also [`SetWallpaperFile`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-portal-Wallpaper.SetWallpaperFile).
     #[doc(alias = "SetWallpaperFile")]
-    pub async fn set_wallpaper_file<F>(
-        &self,
-        identifier: &WindowIdentifier,
+    pub fn set_wallpaper_file<'r, F>(
+        &'r self,
+        identifier: &'r WindowIdentifier,
         file: &F,
         show_preview: bool,
         set_on: SetOn,
-    ) -> Result<(), Error>
+    ) -> Request<'r, ()>
     where
         F: AsRawFd,
     {
         let options = WallpaperOptions::default()
             .show_preview(show_preview)
             .set_on(set_on);
-        call_basic_response_method(
-            &self.0,
-            &options.handle_token,
-            "SetWallpaperFile",
-            &(&identifier, Fd::from(file.as_raw_fd()), &options),
-        )
-        .await
+        let fd = Fd::from(file.as_raw_fd());
+        Request::new(move |call| async move {
+            call_basic_response_method(
+                &self.0,
+                &call,
+                &options.handle_token,
+                "SetWallpaperFile",
+                &(&identifier, fd, &options),
+            )
+            .await
+        })
     }
 
     /// Sets the lock-screen, background or both wallpaper's from an URI.
@@ -212,23 +220,26 @@ impl<'a> WallpaperProxy<'a> {
     ///
     /// See also [`SetWallpaperURI`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-portal-Wallpaper.SetWallpaperURI).
     #[doc(alias = "SetWallpaperURI")]
-    pub async fn set_wallpaper_uri(
-        &self,
-        identifier: &WindowIdentifier,
-        uri: &str,
+    pub fn set_wallpaper_uri<'r>(
+        &'r self,
+        identifier: &'r WindowIdentifier,
+        uri: &'r str,
         show_preview: bool,
         set_on: SetOn,
-    ) -> Result<(), Error> {
+    ) -> Request<'r, ()> {
         let options = WallpaperOptions::default()
             .show_preview(show_preview)
             .set_on(set_on);
-        call_basic_response_method(
-            &self.0,
-            &options.handle_token,
-            "SetWallpaperURI",
-            &(&identifier, uri, &options),
-        )
-        .await
+        Request::new(move |call| async move {
+            call_basic_response_method(
+                &self.0,
+                &call,
+                &options.handle_token,
+                "SetWallpaperURI",
+                &(&identifier, uri, &options),
+            )
+            .await
+        })
     }
 }
 
@@ -26,12 +26,13 @@
 
 use std::{collections::HashMap, os::unix::prelude::AsRawFd};
 
+use futures::Stream;
 use zvariant::{Fd, Value};
 use zvariant_derive::{DeserializeDict, SerializeDict, TypeDict};
 
 use super::{DESTINATION, PATH};
 use crate::{
-    helpers::{call_method, receive_signal},
+    helpers::{call_method, receive_signal, receive_signal_stream},
     Error,
 };
 
@@ -78,7 +79,7 @@ impl TransferOptions {
 /// Wrapper of the DBus interface: [`org.freedesktop.portal.FileTransfer`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.portal.FileTransfer).
 #[derive(Debug)]
 #[doc(alias = "org.freedesktop.portal.FileTransfer")]
-pub struct FileTransferProxy<'a>(zbus::azync::Proxy<'a>);
+pub struct FileTransferProxy<'a>(crate::helpers::Proxy<'a>);
 
 impl<'a> FileTransferProxy<'a> {
     /// Create a new instance of [`FileTransferProxy`].
@@ -89,7 +90,7 @@ impl<'a> FileTransferProxy<'a> {
             .destination(DESTINATION)?
             .build()
             .await?;
-        Ok(Self(proxy))
+        Ok(Self(proxy.into()))
     }
 
     /// Get a reference to the underlying Proxy.
@@ -97,6 +98,10 @@ impl<'a> FileTransferProxy<'a> {
         &self.0
     }
 
+    proxy_set_timeout!();
+
+    proxy_version!(FileTransfer);
+
     /// Adds files to a session. This method can be called multiple times on a
     /// given session. **Note** only regular files (not directories) can be
     /// added.
@@ -204,4 +209,25 @@ impl<'a> FileTransferProxy<'a> {
     pub async fn transfer_closed(&self) -> Result<String, Error> {
         receive_signal(&self.0, "TransferClosed").await
     }
+
+    /// Emitted when the transfer is closed.
+    ///
+    /// Unlike
+    /// [`transfer_closed()`][`FileTransferProxy::transfer_closed`],
+    /// the returned stream stays subscribed and yields every emitted signal.
+    ///
+    /// # Returns
+    ///
+    /// * The key returned by
+    ///   [`start_transfer()`][`FileTransferProxy::start_transfer`].
+    ///
+    /// # Specifications
+    ///
+    /// See also [`TransferClosed`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-signal-org-freedesktop-portal-FileTransfer.TransferClosed).
+    #[doc(alias = "TransferClosed")]
+    pub async fn receive_transfer_closed_stream(
+        &self,
+    ) -> Result<impl Stream<Item = String> + '_, Error> {
+        receive_signal_stream(&self.0, "TransferClosed").await
+    }
 }
@@ -46,7 +46,10 @@ use std::{
 };
 
 use enumflags2::BitFlags;
-use serde::{de::Deserializer, Deserialize, Serialize, Serializer};
+use serde::{
+    de::{self, Deserializer},
+    Deserialize, Serialize, Serializer,
+};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use strum_macros::{AsRefStr, EnumString, IntoStaticStr, ToString};
 use zvariant::{Fd, Signature};
@@ -111,7 +114,10 @@ impl<'de> Deserialize<'de> for Permission {
     where
         D: Deserializer<'de>,
     {
-        Ok(Permission::from_str(&String::deserialize(deserializer)?).expect("invalid permission"))
+        let permission = String::deserialize(deserializer)?;
+        Permission::from_str(&permission).map_err(|_| {
+            de::Error::invalid_value(de::Unexpected::Str(&permission), &"a document permission")
+        })
     }
 }
 
@@ -135,7 +141,7 @@ impl<'de> Deserialize<'de> for Permission {
 /// Wrapper of the DBus interface: [`org.freedesktop.portal.Documents`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.portal.Documents).
 #[derive(Debug)]
 #[doc(alias = "org.freedesktop.portal.Documents")]
-pub struct DocumentsProxy<'a>(zbus::azync::Proxy<'a>);
+pub struct DocumentsProxy<'a>(crate::helpers::Proxy<'a>);
 
 impl<'a> DocumentsProxy<'a> {
     /// Create a new instance of [`DocumentsProxy`].
@@ -146,7 +152,7 @@ impl<'a> DocumentsProxy<'a> {
             .destination(DESTINATION)?
             .build()
             .await?;
-        Ok(Self(proxy))
+        Ok(Self(proxy.into()))
     }
 
     /// Get a reference to the underlying Proxy.
@@ -154,6 +160,10 @@ impl<'a> DocumentsProxy<'a> {
         &self.0
     }
 
+    proxy_set_timeout!();
+
+    proxy_version!(Documents);
+
     /// Adds a file to the document store.
     /// The file is passed in the form of an open file descriptor
     /// to prove that the caller has access to the file.
@@ -485,3 +495,31 @@ impl<'a> DocumentsProxy<'a> {
 mod file_transfer;
 
 pub use file_transfer::FileTransferProxy;
+
+/// Interact with `org.freedesktop.impl.portal.PermissionStore` interface.
+mod permission_store;
+
+pub use permission_store::{
+    AppPermissions, PermissionChange, PermissionStoreProxy, StorePermission, Table,
+};
+
+#[cfg(test)]
+mod test {
+    use byteorder::LE;
+    use zvariant::EncodingContext;
+
+    use super::Permission;
+
+    #[test]
+    fn permission_malformed() {
+        let ctxt = EncodingContext::<LE>::new_dbus(0);
+        for permission in &["read", "write", "grant-permissions", "delete"] {
+            let bytes = zvariant::to_bytes(ctxt, permission).unwrap();
+            assert!(zvariant::from_slice::<_, Permission>(&bytes, ctxt).is_ok());
+        }
+        for permission in &["", "Read", "execute", "grant_permissions"] {
+            let bytes = zvariant::to_bytes(ctxt, permission).unwrap();
+            assert!(zvariant::from_slice::<_, Permission>(&bytes, ctxt).is_err());
+        }
+    }
+}
@@ -0,0 +1,478 @@
+//! # Examples
+//!
+//! ```rust,no_run
+//! use ashpd::documents::{PermissionStoreProxy, StorePermission, Table};
+//!
+//! async fn run() -> ashpd::Result<()> {
+//!     let connection = zbus::azync::Connection::session().await?;
+//!     let proxy = PermissionStoreProxy::new(&connection).await?;
+//!
+//!     let permissions = proxy
+//!         .permission(&Table::Background, "background", "org.gnome.Maps")
+//!         .await?;
+//!     if permissions.contains(&StorePermission::No) {
+//!         println!("Running in the background was denied");
+//!     }
+//!
+//!     proxy
+//!         .set_permission(
+//!             &Table::Notifications,
+//!             true,
+//!             "notification",
+//!             "org.gnome.Maps",
+//!             &[StorePermission::Yes],
+//!         )
+//!         .await?;
+//!
+//!     for id in proxy.list(&Table::Devices).await? {
+//!         let (permissions, _data) = proxy.lookup(&Table::Devices, &id).await?;
+//!         println!("{}: {:#?}", id, permissions);
+//!     }
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use std::{collections::HashMap, fmt};
+
+use futures::Stream;
+use serde::{de::Deserializer, Deserialize, Serialize, Serializer};
+use zvariant::{OwnedValue, Signature, Value};
+use zvariant_derive::Type;
+
+use crate::{
+    helpers::{call_method, receive_signal, receive_signal_stream},
+    Error,
+};
+
+const DESTINATION: &str = "org.freedesktop.impl.portal.PermissionStore";
+const PATH: &str = "/org/freedesktop/impl/portal/PermissionStore";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// A table of the permission store.
+///
+/// Each portal stores the permissions it handles in its own table.
+pub enum Table {
+    /// Running in the background.
+    Background,
+    /// Access to the camera, microphone & speakers.
+    Devices,
+    /// Exported documents.
+    Documents,
+    /// Enabling GameMode.
+    GameMode,
+    /// Inhibiting the session.
+    Inhibit,
+    /// Access to the user's location.
+    Location,
+    /// Sending notifications.
+    Notifications,
+    /// Taking screenshots.
+    Screenshot,
+    /// Setting the wallpaper.
+    Wallpaper,
+    /// Any other table.
+    Other(String),
+}
+
+impl Table {
+    /// The name of the table.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Background => "background",
+            Self::Devices => "devices",
+            Self::Documents => "documents",
+            Self::GameMode => "gamemode",
+            Self::Inhibit => "inhibit",
+            Self::Location => "location",
+            Self::Notifications => "notifications",
+            Self::Screenshot => "screenshot",
+            Self::Wallpaper => "wallpaper",
+            Self::Other(table) => table,
+        }
+    }
+}
+
+impl From<&str> for Table {
+    fn from(table: &str) -> Self {
+        match table {
+            "background" => Self::Background,
+            "devices" => Self::Devices,
+            "documents" => Self::Documents,
+            "gamemode" => Self::GameMode,
+            "inhibit" => Self::Inhibit,
+            "location" => Self::Location,
+            "notifications" => Self::Notifications,
+            "screenshot" => Self::Screenshot,
+            "wallpaper" => Self::Wallpaper,
+            table => Self::Other(table.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl zvariant::Type for Table {
+    fn signature() -> Signature<'static> {
+        String::signature()
+    }
+}
+
+impl Serialize for Table {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        str::serialize(self.as_str(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Table {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Table::from(String::deserialize(deserializer)?.as_str()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// A permission stored for an application.
+pub enum StorePermission {
+    /// The access was granted.
+    Yes,
+    /// The access was denied.
+    No,
+    /// The user has to be asked.
+    Ask,
+    /// Any other value, e.g. a document permission or a location accuracy.
+    Other(String),
+}
+
+impl StorePermission {
+    /// The stored value of the permission.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Yes => "yes",
+            Self::No => "no",
+            Self::Ask => "ask",
+            Self::Other(permission) => permission,
+        }
+    }
+}
+
+impl From<&str> for StorePermission {
+    fn from(permission: &str) -> Self {
+        match permission {
+            "yes" => Self::Yes,
+            "no" => Self::No,
+            "ask" => Self::Ask,
+            permission => Self::Other(permission.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for StorePermission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl zvariant::Type for StorePermission {
+    fn signature() -> Signature<'static> {
+        String::signature()
+    }
+}
+
+impl Serialize for StorePermission {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        str::serialize(self.as_str(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for StorePermission {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(StorePermission::from(
+            String::deserialize(deserializer)?.as_str(),
+        ))
+    }
+}
+
+/// A [`HashMap`] mapping application IDs to the permissions stored for that
+/// application.
+pub type AppPermissions = HashMap<String, Vec<StorePermission>>;
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+/// A change of a permission store entry, received with the `Changed` signal.
+pub struct PermissionChange(Table, String, bool, OwnedValue, AppPermissions);
+
+impl PermissionChange {
+    /// The table of the changed entry.
+    pub fn table(&self) -> &Table {
+        &self.0
+    }
+
+    /// The ID of the changed entry.
+    pub fn id(&self) -> &str {
+        &self.1
+    }
+
+    /// Whether the entry was deleted.
+    pub fn deleted(&self) -> bool {
+        self.2
+    }
+
+    /// The data associated with the entry.
+    pub fn data(&self) -> &OwnedValue {
+        &self.3
+    }
+
+    /// The permissions of the entry.
+    pub fn permissions(&self) -> &AppPermissions {
+        &self.4
+    }
+}
+
+/// The permission store can be used by portals to store permissions that
+/// sandboxed applications have to various resources, such as files outside the
+/// sandbox.
+///
+/// Since the resources managed by portals can be varied, the permission store
+/// is fairly free-form: there can be multiple tables; resources are identified
+/// by an ID, as are applications, and permissions are stored as string arrays.
+/// None of these strings are interpreted by the permission store in any way.
+///
+/// In addition, the permission store allows to associate extra data (in the
+/// form of a variant) with each resource.
+///
+/// Wrapper of the DBus interface: [`org.freedesktop.impl.portal.PermissionStore`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.impl.portal.PermissionStore).
+#[derive(Debug)]
+#[doc(alias = "org.freedesktop.impl.portal.PermissionStore")]
+pub struct PermissionStoreProxy<'a>(crate::helpers::Proxy<'a>);
+
+impl<'a> PermissionStoreProxy<'a> {
+    /// Create a new instance of [`PermissionStoreProxy`].
+    pub async fn new(
+        connection: &zbus::azync::Connection,
+    ) -> Result<PermissionStoreProxy<'a>, Error> {
+        let proxy = zbus::azync::ProxyBuilder::new_bare(connection)
+            .interface("org.freedesktop.impl.portal.PermissionStore")?
+            .path(PATH)?
+            .destination(DESTINATION)?
+            .build()
+            .await?;
+        Ok(Self(proxy.into()))
+    }
+
+    /// Get a reference to the underlying Proxy.
+    pub fn inner(&self) -> &zbus::azync::Proxy<'_> {
+        &self.0
+    }
+
+    proxy_set_timeout!();
+
+    proxy_version!("org-freedesktop-impl-portal-", PermissionStore);
+
+    /// Signal emitted when an entry of the permission store changes.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`Changed`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-signal-org-freedesktop-impl-portal-PermissionStore.Changed).
+    #[doc(alias = "Changed")]
+    pub async fn receive_changed(&self) -> Result<PermissionChange, Error> {
+        receive_signal(&self.0, "Changed").await
+    }
+
+    /// Signal emitted when an entry of the permission store changes.
+    ///
+    /// Unlike [`receive_changed()`][`PermissionStoreProxy::receive_changed`],
+    /// the returned stream stays subscribed and yields every emitted signal.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`Changed`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-signal-org-freedesktop-impl-portal-PermissionStore.Changed).
+    #[doc(alias = "Changed")]
+    pub async fn receive_changed_stream(
+        &self,
+    ) -> Result<impl Stream<Item = PermissionChange> + '_, Error> {
+        receive_signal_stream(&self.0, "Changed").await
+    }
+
+    /// Removes an entry from the permission store.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - The table.
+    /// * `id` - The resource ID.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`Delete`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-impl-portal-PermissionStore.Delete).
+    #[doc(alias = "Delete")]
+    pub async fn delete(&self, table: &Table, id: &str) -> Result<(), Error> {
+        call_method(&self.0, "Delete", &(table, id)).await
+    }
+
+    /// Removes the permissions of an application from an entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - The table.
+    /// * `id` - The resource ID.
+    /// * `app` - The application ID.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`DeletePermission`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-impl-portal-PermissionStore.DeletePermission).
+    #[doc(alias = "DeletePermission")]
+    pub async fn delete_permission(&self, table: &Table, id: &str, app: &str) -> Result<(), Error> {
+        call_method(&self.0, "DeletePermission", &(table, id, app)).await
+    }
+
+    /// Lists the resource IDs of a table.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - The table.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`List`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-impl-portal-PermissionStore.List).
+    #[doc(alias = "List")]
+    pub async fn list(&self, table: &Table) -> Result<Vec<String>, Error> {
+        call_method(&self.0, "List", &(table)).await
+    }
+
+    /// Looks up the entry of a resource.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - The table.
+    /// * `id` - The resource ID.
+    ///
+    /// # Returns
+    ///
+    /// The permissions of every application along with the data associated
+    /// with the entry.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`Lookup`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-impl-portal-PermissionStore.Lookup).
+    #[doc(alias = "Lookup")]
+    pub async fn lookup(
+        &self,
+        table: &Table,
+        id: &str,
+    ) -> Result<(AppPermissions, OwnedValue), Error> {
+        call_method(&self.0, "Lookup", &(table, id)).await
+    }
+
+    /// Reads the permissions of an application for a resource.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - The table.
+    /// * `id` - The resource ID.
+    /// * `app` - The application ID.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`GetPermission`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-impl-portal-PermissionStore.GetPermission).
+    #[doc(alias = "GetPermission")]
+    pub async fn permission(
+        &self,
+        table: &Table,
+        id: &str,
+        app: &str,
+    ) -> Result<Vec<StorePermission>, Error> {
+        call_method(&self.0, "GetPermission", &(table, id, app)).await
+    }
+
+    /// Writes the entry of a resource.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - The table.
+    /// * `create` - Whether to create the table if it does not exist.
+    /// * `id` - The resource ID.
+    /// * `app_permissions` - The permissions of every application.
+    /// * `data` - The data to associate with the entry.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`Set`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-impl-portal-PermissionStore.Set).
+    #[doc(alias = "Set")]
+    pub async fn set(
+        &self,
+        table: &Table,
+        create: bool,
+        id: &str,
+        app_permissions: &AppPermissions,
+        data: Value<'_>,
+    ) -> Result<(), Error> {
+        call_method(&self.0, "Set", &(table, create, id, app_permissions, data)).await
+    }
+
+    /// Writes the permissions of an application for a resource.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - The table.
+    /// * `create` - Whether to create the table if it does not exist.
+    /// * `id` - The resource ID.
+    /// * `app` - The application ID.
+    /// * `permissions` - The permissions to store.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`SetPermission`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-impl-portal-PermissionStore.SetPermission).
+    #[doc(alias = "SetPermission")]
+    pub async fn set_permission(
+        &self,
+        table: &Table,
+        create: bool,
+        id: &str,
+        app: &str,
+        permissions: &[StorePermission],
+    ) -> Result<(), Error> {
+        call_method(
+            &self.0,
+            "SetPermission",
+            &(table, create, id, app, permissions),
+        )
+        .await
+    }
+
+    /// Sets the data associated with a resource.
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - The table.
+    /// * `create` - Whether to create the table if it does not exist.
+    /// * `id` - The resource ID.
+    /// * `data` - The data to associate with the entry.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`SetValue`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-method-org-freedesktop-impl-portal-PermissionStore.SetValue).
+    #[doc(alias = "SetValue")]
+    pub async fn set_value(
+        &self,
+        table: &Table,
+        create: bool,
+        id: &str,
+        data: Value<'_>,
+    ) -> Result<(), Error> {
+        call_method(&self.0, "SetValue", &(table, create, id, data)).await
+    }
+}
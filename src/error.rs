@@ -1,8 +1,13 @@
-use crate::desktop::request::ResponseError;
+use crate::{
+    desktop::{request::ResponseError, HandleInvalidCharacter},
+    flatpak::SupportsFlags,
+};
 use zbus_macros::DBusError;
 
 /// An error type that describes the various DBus errors.
 ///
+/// Each variant carries the message sent by the portal along with the error.
+///
 /// See <https://github.com/flatpak/xdg-desktop-portal/blob/master/src/xdp-utils.h#L119-L127>.
 #[allow(missing_docs)]
 #[derive(DBusError, Debug)]
@@ -11,7 +16,7 @@ pub enum PortalError {
     /// ZBus specific error.
     ZBus(zbus::Error),
     /// Request failed.
-    Failed,
+    Failed(String),
     /// Invalid arguments passed.
     InvalidArgument(String),
     /// Not found.
@@ -26,6 +31,24 @@ pub enum PortalError {
     WindowDestroyed(String),
 }
 
+impl PortalError {
+    /// Map an error name of the `org.freedesktop.portal.Error` domain to its
+    /// variant.
+    fn from_name(name: &str, message: String) -> Option<Self> {
+        let error = match name.strip_prefix("org.freedesktop.portal.Error.")? {
+            "Failed" => Self::Failed(message),
+            "InvalidArgument" => Self::InvalidArgument(message),
+            "NotFound" => Self::NotFound(message),
+            "Exist" => Self::Exist(message),
+            "NotAllowed" => Self::NotAllowed(message),
+            "Cancelled" => Self::Cancelled(message),
+            "WindowDestroyed" => Self::WindowDestroyed(message),
+            _ => return None,
+        };
+        Some(error)
+    }
+}
+
 #[derive(Debug)]
 /// The error type for ashpd.
 pub enum Error {
@@ -35,11 +58,65 @@ pub enum Error {
     Portal(PortalError),
     /// A zbus::fdo specific error.
     Zbus(zbus::fdo::Error),
-    /// A signal returned no response.
-    NoResponse,
+    /// The connection to the bus was closed before the response of a portal
+    /// request was received.
+    ConnectionClosed,
+    /// The signal stream ended before the expected signal was received.
+    SignalStreamEnded,
+    /// The portal request was closed with
+    /// [`AbortToken::abort`](crate::desktop::AbortToken::abort) before a
+    /// response was received.
+    RequestClosed,
+    /// The session was closed, either by the application, the portal or the
+    /// user, before the method was called.
+    SessionClosed,
+    /// No `xdg-desktop-portal` is running on the session bus nor can be
+    /// activated.
+    PortalNotInstalled,
+    /// The portal doesn't implement the interface, it is either too old or
+    /// its backend doesn't provide it. Contains the message of the bus.
+    InterfaceNotFound(String),
+    /// The portal didn't reply in time, see the `set_timeout()` method of the
+    /// proxies & [`Request::timeout`](crate::desktop::Request::timeout).
+    Timeout,
+    /// The handle token contains a character not allowed in an object path.
+    InvalidHandleToken(HandleInvalidCharacter),
+    /// The reply or the signal sent by the portal couldn't be parsed.
+    ParseError(zvariant::Error),
+    /// The connection has no unique name, it is not a message bus connection.
+    NoUniqueName,
+    /// An I/O error.
+    IO(std::io::Error),
+    /// The method requires a newer version of the portal interface than the
+    /// one that is installed.
+    RequiresVersion {
+        /// The name of the interface.
+        interface: String,
+        /// The version required by the method.
+        required: u32,
+        /// The version of the installed interface.
+        found: u32,
+    },
+    /// The portal doesn't support the feature, see
+    /// [`FlatpakProxy::supports`](crate::flatpak::FlatpakProxy::supports).
+    RequiresSupport(SupportsFlags),
 }
 
-impl std::error::Error for Error {}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Response(e) => Some(e),
+            Self::Portal(PortalError::ZBus(e)) => Some(e),
+            Self::Portal(e) => Some(e),
+            Self::Zbus(zbus::fdo::Error::ZBus(e)) => Some(e),
+            Self::Zbus(e) => Some(e),
+            Self::InvalidHandleToken(e) => Some(e),
+            Self::ParseError(e) => Some(e),
+            Self::IO(e) => Some(e),
+            _ => None,
+        }
+    }
+}
 
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -47,10 +124,61 @@ impl std::fmt::Display for Error {
             Self::Response(e) => f.write_str(&format!("Portal request didn't succeed: {}", e)),
             Self::Zbus(e) => f.write_str(&format!("ZBus Error: {}", e)),
             Self::Portal(e) => f.write_str(&format!("Portal request failed: {}", e)),
-            Self::NoResponse => f.write_str("Portal error: no response"),
+            Self::ConnectionClosed => f.write_str("The connection to the bus was closed"),
+            Self::SignalStreamEnded => f.write_str("The signal stream ended"),
+            Self::RequestClosed => f.write_str("Portal request was closed"),
+            Self::SessionClosed => f.write_str("Portal session was closed"),
+            Self::PortalNotInstalled => f.write_str("xdg-desktop-portal is not installed"),
+            Self::InterfaceNotFound(e) => {
+                f.write_str(&format!("Portal interface not found: {}", e))
+            }
+            Self::Timeout => f.write_str("Portal request timed out"),
+            Self::InvalidHandleToken(e) => f.write_str(&format!("Invalid handle token: {}", e)),
+            Self::ParseError(e) => f.write_str(&format!("Failed to parse the portal reply: {}", e)),
+            Self::NoUniqueName => f.write_str("The connection has no unique name"),
+            Self::IO(e) => f.write_str(&format!("IO: {}", e)),
+            Self::RequiresVersion {
+                interface,
+                required,
+                found,
+            } => f.write_str(&format!(
+                "{} version {} is required, found version {}",
+                interface, required, found
+            )),
+            Self::RequiresSupport(feature) => f.write_str(&format!(
+                "The portal doesn't support the {:?} feature",
+                feature
+            )),
+        }
+    }
+}
+
+impl Error {
+    /// Map the errors of the bus daemon & of GDBus to a dedicated variant.
+    fn from_bus_error(name: &str, message: &str) -> Option<Self> {
+        match name {
+            "org.freedesktop.DBus.Error.ServiceUnknown"
+            | "org.freedesktop.DBus.Error.NameHasNoOwner" => Some(Self::PortalNotInstalled),
+            "org.freedesktop.DBus.Error.UnknownInterface" => {
+                Some(Self::InterfaceNotFound(message.to_owned()))
+            }
+            // GDBus replies with UnknownMethod, or InvalidArgs for the
+            // properties, to calls on an interface the object doesn't export.
+            "org.freedesktop.DBus.Error.UnknownMethod"
+            | "org.freedesktop.DBus.Error.InvalidArgs"
+                if message.starts_with("No such interface") =>
+            {
+                Some(Self::InterfaceNotFound(message.to_owned()))
+            }
+            // xdg-desktop-portal's reply to calls using a closed session.
+            "org.freedesktop.DBus.Error.AccessDenied" if message == "Invalid session" => {
+                Some(Self::SessionClosed)
+            }
+            _ => None,
         }
     }
 }
+
 impl From<ResponseError> for Error {
     fn from(e: ResponseError) -> Self {
         Self::Response(e)
@@ -65,12 +193,163 @@ impl From<PortalError> for Error {
 
 impl From<zbus::Error> for Error {
     fn from(e: zbus::Error) -> Self {
-        Self::Portal(PortalError::ZBus(e))
+        match e {
+            zbus::Error::Variant(e) => Self::ParseError(e),
+            zbus::Error::MethodError(ref name, ref message, _) => {
+                let message = message.clone().unwrap_or_default();
+                if let Some(error) = Self::from_bus_error(name.as_str(), &message) {
+                    return error;
+                }
+                match PortalError::from_name(name.as_str(), message) {
+                    Some(error) => Self::Portal(error),
+                    None => Self::Portal(PortalError::ZBus(e)),
+                }
+            }
+            e => Self::Portal(PortalError::ZBus(e)),
+        }
+    }
+}
+
+impl From<zvariant::Error> for Error {
+    fn from(e: zvariant::Error) -> Self {
+        Self::ParseError(e)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Self::IO(e)
+    }
+}
+
+impl From<HandleInvalidCharacter> for Error {
+    fn from(e: HandleInvalidCharacter) -> Self {
+        Self::InvalidHandleToken(e)
     }
 }
 
 impl From<zbus::fdo::Error> for Error {
     fn from(e: zbus::fdo::Error) -> Self {
-        Self::Zbus(e)
+        use zbus::fdo::Error as FdoError;
+
+        match e {
+            FdoError::ZBus(e) => e.into(),
+            FdoError::ServiceUnknown(_) | FdoError::NameHasNoOwner(_) => Self::PortalNotInstalled,
+            FdoError::UnknownInterface(message) => Self::InterfaceNotFound(message),
+            FdoError::UnknownMethod(ref message) | FdoError::InvalidArgs(ref message)
+                if message.starts_with("No such interface") =>
+            {
+                Self::InterfaceNotFound(message.clone())
+            }
+            FdoError::AccessDenied(ref message) if message == "Invalid session" => {
+                Self::SessionClosed
+            }
+            e => Self::Zbus(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::convert::TryFrom;
+
+    use super::{Error, PortalError};
+    use crate::desktop::HandleToken;
+
+    #[test]
+    fn invalid_handle_token() {
+        let error = Error::from(HandleToken::try_from("ashpd-token").unwrap_err());
+        assert!(matches!(
+            error,
+            Error::InvalidHandleToken(e) if e.character() == '-'
+        ));
+    }
+
+    #[test]
+    fn bus_errors() {
+        assert!(matches!(
+            Error::from_bus_error("org.freedesktop.DBus.Error.ServiceUnknown", ""),
+            Some(Error::PortalNotInstalled)
+        ));
+        assert!(matches!(
+            Error::from_bus_error("org.freedesktop.DBus.Error.NameHasNoOwner", ""),
+            Some(Error::PortalNotInstalled)
+        ));
+        assert!(matches!(
+            Error::from_bus_error(
+                "org.freedesktop.DBus.Error.UnknownInterface",
+                "No Camera"
+            ),
+            Some(Error::InterfaceNotFound(message)) if message == "No Camera"
+        ));
+        assert!(matches!(
+            Error::from_bus_error(
+                "org.freedesktop.DBus.Error.UnknownMethod",
+                "No such interface “org.freedesktop.portal.Camera”"
+            ),
+            Some(Error::InterfaceNotFound(_))
+        ));
+        assert!(matches!(
+            Error::from_bus_error(
+                "org.freedesktop.DBus.Error.InvalidArgs",
+                "No such interface “org.freedesktop.portal.Camera”"
+            ),
+            Some(Error::InterfaceNotFound(_))
+        ));
+        assert!(matches!(
+            Error::from_bus_error("org.freedesktop.DBus.Error.AccessDenied", "Invalid session"),
+            Some(Error::SessionClosed)
+        ));
+
+        // Other messages are left to the generic handling
+        assert!(Error::from_bus_error("org.freedesktop.DBus.Error.UnknownMethod", "").is_none());
+        assert!(Error::from_bus_error("org.freedesktop.DBus.Error.InvalidArgs", "").is_none());
+        assert!(Error::from_bus_error("org.freedesktop.DBus.Error.AccessDenied", "").is_none());
+        assert!(Error::from_bus_error("org.freedesktop.portal.Error.Failed", "").is_none());
+    }
+
+    #[test]
+    fn portal_errors() {
+        assert!(matches!(
+            PortalError::from_name("org.freedesktop.portal.Error.Failed", "oops".to_owned()),
+            Some(PortalError::Failed(message)) if message == "oops"
+        ));
+        assert!(matches!(
+            PortalError::from_name(
+                "org.freedesktop.portal.Error.InvalidArgument",
+                String::new()
+            ),
+            Some(PortalError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            PortalError::from_name("org.freedesktop.portal.Error.NotFound", String::new()),
+            Some(PortalError::NotFound(_))
+        ));
+        assert!(matches!(
+            PortalError::from_name("org.freedesktop.portal.Error.Exist", String::new()),
+            Some(PortalError::Exist(_))
+        ));
+        assert!(matches!(
+            PortalError::from_name("org.freedesktop.portal.Error.NotAllowed", String::new()),
+            Some(PortalError::NotAllowed(_))
+        ));
+        assert!(matches!(
+            PortalError::from_name("org.freedesktop.portal.Error.Cancelled", String::new()),
+            Some(PortalError::Cancelled(_))
+        ));
+        assert!(matches!(
+            PortalError::from_name(
+                "org.freedesktop.portal.Error.WindowDestroyed",
+                String::new()
+            ),
+            Some(PortalError::WindowDestroyed(_))
+        ));
+
+        assert!(
+            PortalError::from_name("org.freedesktop.portal.Error.Unknown", String::new()).is_none()
+        );
+        assert!(
+            PortalError::from_name("org.freedesktop.DBus.Error.Failed", String::new()).is_none()
+        );
     }
 }
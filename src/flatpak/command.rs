@@ -0,0 +1,451 @@
+use std::{
+    collections::HashMap,
+    ffi::{OsStr, OsString},
+    fs::{File, OpenOptions},
+    os::unix::{
+        io::{AsRawFd, FromRawFd, IntoRawFd, RawFd},
+        net::UnixStream,
+    },
+    path::{Path, PathBuf},
+};
+
+use async_io::Async;
+use enumflags2::BitFlags;
+use futures::AsyncReadExt;
+use zvariant::{Fd, OwnedFd};
+
+use super::{
+    development::{DevelopmentProxy, HostCommandFlags},
+    ExitStatus, FlatpakProxy, SandboxFlags, SpawnFlags, SpawnOptions, SpawnedProcess,
+};
+use crate::Error;
+
+/// What to connect a standard stream of a [`Command`] to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stdio {
+    /// The stream of the caller.
+    Inherit,
+    /// `/dev/null`.
+    Null,
+    /// A new pipe, available in the matching field of the [`Child`].
+    Piped,
+}
+
+/// A builder for the processes spawned with [`FlatpakProxy::spawn`], or on
+/// the host with [`DevelopmentProxy::host_command`], similar to
+/// [`std::process::Command`].
+///
+/// The sandbox options, such as [`Command::expose`], don't apply to the
+/// commands run on the host.
+///
+/// ```rust,no_run
+/// use ashpd::flatpak::{Command, FlatpakProxy};
+///
+/// async fn run() -> ashpd::Result<()> {
+///     let connection = zbus::azync::Connection::session().await?;
+///     let proxy = FlatpakProxy::new(&connection).await?;
+///
+///     let output = Command::new("ls")
+///         .arg("-l")
+///         .current_dir("/app")
+///         .no_network()
+///         .output(&proxy)
+///         .await?;
+///     println!("{}", String::from_utf8_lossy(&output.stdout));
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Command {
+    program: OsString,
+    args: Vec<OsString>,
+    envs: HashMap<String, String>,
+    forward_env: bool,
+    current_dir: Option<PathBuf>,
+    stdin: Option<Stdio>,
+    stdout: Option<Stdio>,
+    stderr: Option<Stdio>,
+    fds: HashMap<u32, OwnedFd>,
+    expose: Vec<String>,
+    expose_ro: Vec<String>,
+    sandbox_flags: Option<BitFlags<SandboxFlags>>,
+    flags: BitFlags<SpawnFlags>,
+}
+
+impl Command {
+    /// Create a new [`Command`] running `program`, looked up in the `PATH` of
+    /// the new sandbox.
+    pub fn new<S: AsRef<OsStr>>(program: S) -> Self {
+        Self {
+            program: program.as_ref().to_owned(),
+            args: Vec::new(),
+            envs: HashMap::new(),
+            forward_env: false,
+            current_dir: None,
+            stdin: None,
+            stdout: None,
+            stderr: None,
+            fds: HashMap::new(),
+            expose: Vec::new(),
+            expose_ro: Vec::new(),
+            sandbox_flags: None,
+            flags: BitFlags::empty(),
+        }
+    }
+
+    /// Adds an argument to pass to the program.
+    pub fn arg<S: AsRef<OsStr>>(&mut self, arg: S) -> &mut Self {
+        self.args.push(arg.as_ref().to_owned());
+        self
+    }
+
+    /// Adds multiple arguments to pass to the program.
+    pub fn args<I, S>(&mut self, args: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        self.args
+            .extend(args.into_iter().map(|arg| arg.as_ref().to_owned()));
+        self
+    }
+
+    /// Sets the environment variable `key` of the process.
+    pub fn env<K: AsRef<str>, V: AsRef<str>>(&mut self, key: K, value: V) -> &mut Self {
+        self.envs
+            .insert(key.as_ref().to_owned(), value.as_ref().to_owned());
+        self
+    }
+
+    /// Clears the environment of the process, only the variables set with
+    /// [`Command::env`] are passed.
+    pub fn env_clear(&mut self) -> &mut Self {
+        self.envs.clear();
+        self.forward_env = false;
+        self.flags |= SpawnFlags::ClearEnv;
+        self
+    }
+
+    /// Passes the environment of the caller to the process, like
+    /// `flatpak-spawn --env` for each variable. The variables set with
+    /// [`Command::env`] take precedence.
+    ///
+    /// The environment isn't forwarded by default, the process starts with
+    /// the one set up by the portal, or by the session helper on the host: the
+    /// values inside the sandbox, like `PATH=/app/bin:/usr/bin`, often don't
+    /// make sense on the host. Variables that aren't valid UTF-8 are skipped.
+    pub fn forward_env(&mut self) -> &mut Self {
+        self.forward_env = true;
+        self
+    }
+
+    /// Sets the working directory of the process, the current directory of
+    /// the caller by default.
+    pub fn current_dir<P: AsRef<Path>>(&mut self, dir: P) -> &mut Self {
+        self.current_dir = Some(dir.as_ref().to_owned());
+        self
+    }
+
+    /// Sets the standard input of the process, inherited by default.
+    pub fn stdin(&mut self, stdin: Stdio) -> &mut Self {
+        self.stdin = Some(stdin);
+        self
+    }
+
+    /// Sets the standard output of the process, inherited by default.
+    pub fn stdout(&mut self, stdout: Stdio) -> &mut Self {
+        self.stdout = Some(stdout);
+        self
+    }
+
+    /// Sets the standard error of the process, inherited by default.
+    pub fn stderr(&mut self, stderr: Stdio) -> &mut Self {
+        self.stderr = Some(stderr);
+        self
+    }
+
+    /// Passes `fd` as the file descriptor `target` of the process.
+    ///
+    /// The command takes the ownership of `fd` and closes it once dropped.
+    /// Passing the file descriptor `0`, `1` or `2` overrides the matching
+    /// [`Stdio`].
+    pub fn fd<F: IntoRawFd>(&mut self, target: u32, fd: F) -> &mut Self {
+        // `into_raw_fd` hands over the ownership of the file descriptor.
+        let fd = unsafe { OwnedFd::from_raw_fd(fd.into_raw_fd()) };
+        self.fds.insert(target, fd);
+        self
+    }
+
+    /// Exposes the file `name`, inside the sandbox, to the new sandbox for
+    /// reading & writing.
+    ///
+    /// Spawns the process in a new sandbox, see [`SpawnFlags::Sandbox`].
+    /// **Note** absolute paths or subdirectories are not allowed.
+    pub fn expose<S: AsRef<str>>(&mut self, name: S) -> &mut Self {
+        self.expose.push(name.as_ref().to_owned());
+        self.flags |= SpawnFlags::Sandbox;
+        self
+    }
+
+    /// Exposes the file `name`, inside the sandbox, to the new sandbox,
+    /// read-only.
+    ///
+    /// Spawns the process in a new sandbox, see [`SpawnFlags::Sandbox`].
+    /// **Note** absolute paths or subdirectories are not allowed.
+    pub fn expose_ro<S: AsRef<str>>(&mut self, name: S) -> &mut Self {
+        self.expose_ro.push(name.as_ref().to_owned());
+        self.flags |= SpawnFlags::Sandbox;
+        self
+    }
+
+    /// Sets the permissions of the new sandbox.
+    ///
+    /// Spawns the process in a new sandbox, see [`SpawnFlags::Sandbox`].
+    pub fn sandbox_flags(&mut self, sandbox_flags: BitFlags<SandboxFlags>) -> &mut Self {
+        self.sandbox_flags = Some(sandbox_flags);
+        self.flags |= SpawnFlags::Sandbox;
+        self
+    }
+
+    /// Spawns the process without network access.
+    pub fn no_network(&mut self) -> &mut Self {
+        self.flags |= SpawnFlags::NoNetwork;
+        self
+    }
+
+    /// Spawns the latest version of the application.
+    pub fn latest(&mut self) -> &mut Self {
+        self.flags |= SpawnFlags::Latest;
+        self
+    }
+
+    /// Adds `flags` to the flags passed to [`FlatpakProxy::spawn`].
+    pub fn flags(&mut self, flags: BitFlags<SpawnFlags>) -> &mut Self {
+        self.flags |= flags;
+        self
+    }
+
+    /// Spawns the process.
+    pub async fn spawn<'a>(&self, proxy: &'a FlatpakProxy<'a>) -> Result<Child<'a>, Error> {
+        self.spawn_with(Target::Sandbox(proxy), Stdio::Inherit, Stdio::Inherit)
+            .await
+    }
+
+    /// Spawns the process, waits for it to exit and collects its output.
+    ///
+    /// Unless set otherwise, the standard input is `/dev/null`, the standard
+    /// output and error are captured.
+    pub async fn output<'a>(&self, proxy: &'a FlatpakProxy<'a>) -> Result<Output, Error> {
+        self.spawn_with(Target::Sandbox(proxy), Stdio::Null, Stdio::Piped)
+            .await?
+            .wait_with_output()
+            .await
+    }
+
+    /// Runs the process on the host, like `flatpak-spawn --host`.
+    ///
+    /// [`SpawnFlags::ClearEnv`] & [`SpawnFlags::Kill`] map to the matching
+    /// [`HostCommandFlags`], the other flags are ignored.
+    pub async fn spawn_on_host<'a>(
+        &self,
+        proxy: &'a DevelopmentProxy<'a>,
+    ) -> Result<Child<'a>, Error> {
+        self.spawn_with(Target::Host(proxy), Stdio::Inherit, Stdio::Inherit)
+            .await
+    }
+
+    /// Runs the process on the host, waits for it to exit and collects its
+    /// output, see [`Command::output`].
+    pub async fn output_on_host<'a>(
+        &self,
+        proxy: &'a DevelopmentProxy<'a>,
+    ) -> Result<Output, Error> {
+        self.spawn_with(Target::Host(proxy), Stdio::Null, Stdio::Piped)
+            .await?
+            .wait_with_output()
+            .await
+    }
+
+    async fn spawn_with<'a>(
+        &self,
+        target: Target<'a>,
+        default_stdin: Stdio,
+        default_output: Stdio,
+    ) -> Result<Child<'a>, Error> {
+        let stdio = [
+            self.stdin.unwrap_or(default_stdin),
+            self.stdout.unwrap_or(default_output),
+            self.stderr.unwrap_or(default_output),
+        ];
+        let mut fds = HashMap::new();
+        // The ends passed to the process, kept open until it is spawned.
+        let mut null: Option<File> = None;
+        let mut remote_ends = Vec::new();
+        let mut pipes = Vec::new();
+        for (target, stdio) in stdio.iter().enumerate() {
+            let pipe = match stdio {
+                Stdio::Inherit => {
+                    fds.insert(target as u32, Fd::from(target as RawFd));
+                    None
+                }
+                Stdio::Null => {
+                    if null.is_none() {
+                        null = Some(
+                            OpenOptions::new()
+                                .read(true)
+                                .write(true)
+                                .open("/dev/null")?,
+                        );
+                    }
+                    let fd = null.as_ref().unwrap().as_raw_fd();
+                    fds.insert(target as u32, Fd::from(fd));
+                    None
+                }
+                Stdio::Piped => {
+                    let (local, remote) = UnixStream::pair()?;
+                    fds.insert(target as u32, Fd::from(remote.as_raw_fd()));
+                    remote_ends.push(remote);
+                    Some(Async::new(local)?)
+                }
+            };
+            pipes.push(pipe);
+        }
+        fds.extend(
+            self.fds
+                .iter()
+                .map(|(target, fd)| (*target, Fd::from(fd.as_raw_fd()))),
+        );
+
+        let current_dir = match self.current_dir {
+            Some(ref dir) => dir.clone(),
+            None => std::env::current_dir()?,
+        };
+        let argv = std::iter::once(&self.program)
+            .chain(&self.args)
+            .map(Path::new)
+            .collect::<Vec<_>>();
+        let mut env_vars = HashMap::new();
+        if self.forward_env {
+            env_vars.extend(std::env::vars_os().filter_map(|(key, value)| {
+                Some((key.into_string().ok()?, value.into_string().ok()?))
+            }));
+        }
+        env_vars.extend(self.envs.clone());
+        let envs = env_vars
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect();
+
+        let process = match target {
+            Target::Sandbox(proxy) => {
+                let mut options = SpawnOptions::default();
+                if !self.expose.is_empty() {
+                    options = options.sandbox_expose(&self.expose);
+                }
+                if !self.expose_ro.is_empty() {
+                    options = options.sandbox_expose_ro(&self.expose_ro);
+                }
+                if let Some(sandbox_flags) = self.sandbox_flags {
+                    options = options.sandbox_flags(sandbox_flags);
+                }
+                proxy
+                    .spawn_paths(&current_dir, &argv, fds, envs, self.flags, options)
+                    .await?
+            }
+            Target::Host(proxy) => {
+                let mut flags = BitFlags::empty();
+                if self.flags.contains(SpawnFlags::ClearEnv) {
+                    flags |= HostCommandFlags::ClearEnv;
+                }
+                if self.flags.contains(SpawnFlags::Kill) {
+                    flags |= HostCommandFlags::WatchBus;
+                }
+                proxy
+                    .host_command_paths(&current_dir, &argv, fds, envs, flags)
+                    .await?
+            }
+        };
+        drop(remote_ends);
+        drop(null);
+
+        let mut pipes = pipes.into_iter();
+        Ok(Child {
+            stdin: pipes.next().flatten(),
+            stdout: pipes.next().flatten(),
+            stderr: pipes.next().flatten(),
+            process,
+        })
+    }
+}
+
+/// Where a [`Command`] runs.
+enum Target<'a> {
+    Sandbox(&'a FlatpakProxy<'a>),
+    Host(&'a DevelopmentProxy<'a>),
+}
+
+/// A process spawned with [`Command::spawn`] or [`Command::spawn_on_host`].
+#[derive(Debug)]
+pub struct Child<'a> {
+    /// The standard input of the process, if [`Stdio::Piped`].
+    pub stdin: Option<Async<UnixStream>>,
+    /// The standard output of the process, if [`Stdio::Piped`].
+    pub stdout: Option<Async<UnixStream>>,
+    /// The standard error of the process, if [`Stdio::Piped`].
+    pub stderr: Option<Async<UnixStream>>,
+    process: SpawnedProcess<'a>,
+}
+
+impl<'a> Child<'a> {
+    /// The PID of the process, as seen from the caller.
+    pub fn pid(&self) -> u32 {
+        self.process.pid()
+    }
+
+    /// The handle of the process, to signal it for example.
+    pub fn process(&mut self) -> &mut SpawnedProcess<'a> {
+        &mut self.process
+    }
+
+    /// Closes the standard input, if piped, and waits for the process to
+    /// exit.
+    pub async fn wait(&mut self) -> Result<ExitStatus, Error> {
+        drop(self.stdin.take());
+        self.process.wait().await
+    }
+
+    /// Closes the standard input, if piped, waits for the process to exit and
+    /// collects its piped output.
+    pub async fn wait_with_output(mut self) -> Result<Output, Error> {
+        drop(self.stdin.take());
+        let (stdout, stderr, status) = futures::try_join!(
+            read_to_end(self.stdout.take()),
+            read_to_end(self.stderr.take()),
+            self.process.wait(),
+        )?;
+        Ok(Output {
+            status,
+            stdout,
+            stderr,
+        })
+    }
+}
+
+/// The output of a process, see [`Command::output`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Output {
+    /// The exit status of the process.
+    pub status: ExitStatus,
+    /// The captured standard output.
+    pub stdout: Vec<u8>,
+    /// The captured standard error.
+    pub stderr: Vec<u8>,
+}
+
+async fn read_to_end(pipe: Option<Async<UnixStream>>) -> Result<Vec<u8>, Error> {
+    let mut content = Vec::new();
+    if let Some(mut pipe) = pipe {
+        pipe.read_to_end(&mut content).await?;
+    }
+    Ok(content)
+}
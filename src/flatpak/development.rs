@@ -0,0 +1,202 @@
+//! # Examples
+//!
+//! Run a command on the host, only works in a Flatpak with the
+//! `--talk-name=org.freedesktop.Flatpak` permission.
+//!
+//! ```rust,no_run
+//! use ashpd::flatpak::development::DevelopmentProxy;
+//! use std::collections::HashMap;
+//!
+//! async fn run() -> ashpd::Result<()> {
+//!     let connection = zbus::azync::Connection::session().await?;
+//!     let proxy = DevelopmentProxy::new(&connection).await?;
+//!
+//!     let mut process = proxy
+//!         .host_command(
+//!             "/",
+//!             &["flatpak", "list"],
+//!             HashMap::new(),
+//!             HashMap::new(),
+//!             Default::default(),
+//!         )
+//!         .await?;
+//!     println!("flatpak exited with {}", process.wait().await?);
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use std::{collections::HashMap, path::Path};
+
+use enumflags2::BitFlags;
+use futures::Stream;
+use serde_repr::{Deserialize_repr, Serialize_repr};
+use zvariant::Fd;
+use zvariant_derive::Type;
+
+use super::{c_path, SpawnedProcess};
+use crate::{
+    helpers::{call_method, get_property, receive_signal, receive_signal_stream},
+    Error,
+};
+
+const DESTINATION: &str = "org.freedesktop.Flatpak";
+const PATH: &str = "/org/freedesktop/Flatpak/Development";
+
+#[derive(Serialize_repr, Deserialize_repr, PartialEq, Copy, Clone, BitFlags, Debug, Type)]
+#[repr(u32)]
+/// Flags affecting the command run on the host.
+pub enum HostCommandFlags {
+    /// Clear the environment.
+    ClearEnv = 1,
+    /// Kill the process when the caller disappears from the session bus.
+    WatchBus = 2,
+}
+
+/// The interface lets sandboxed development tools run commands on the host,
+/// like `flatpak-spawn --host`.
+///
+/// It is implemented by the Flatpak session helper rather than the portal and
+/// requires the `--talk-name=org.freedesktop.Flatpak` permission.
+///
+/// Wrapper of the DBus interface: `org.freedesktop.Flatpak.Development`.
+#[derive(Debug)]
+#[doc(alias = "org.freedesktop.Flatpak.Development")]
+pub struct DevelopmentProxy<'a>(crate::helpers::Proxy<'a>);
+
+impl<'a> DevelopmentProxy<'a> {
+    /// Create a new instance of [`DevelopmentProxy`].
+    pub async fn new(connection: &zbus::azync::Connection) -> Result<DevelopmentProxy<'a>, Error> {
+        let proxy = zbus::azync::ProxyBuilder::new_bare(connection)
+            .interface("org.freedesktop.Flatpak.Development")?
+            .path(PATH)?
+            .destination(DESTINATION)?
+            .build()
+            .await?;
+        Ok(Self(proxy.into()))
+    }
+
+    /// Get a reference to the underlying Proxy.
+    pub fn inner(&self) -> &zbus::azync::Proxy<'_> {
+        &self.0
+    }
+
+    proxy_set_timeout!();
+
+    /// The version of the interface implemented by the session helper.
+    #[doc(alias = "version")]
+    pub async fn version(&self) -> Result<u32, Error> {
+        get_property::<u32>(&self.0, "version").await
+    }
+
+    /// Emitted when a process started by
+    /// [`host_command()`][`DevelopmentProxy::host_command`] exits.
+    #[doc(alias = "HostCommandExited")]
+    pub async fn receive_host_command_exited(&self) -> Result<(u32, u32), Error> {
+        receive_signal(&self.0, "HostCommandExited").await
+    }
+
+    /// Emitted when a process started by
+    /// [`host_command()`][`DevelopmentProxy::host_command`] exits.
+    ///
+    /// Unlike
+    /// [`receive_host_command_exited()`][`DevelopmentProxy::receive_host_command_exited`],
+    /// the returned stream stays subscribed and yields every emitted signal.
+    #[doc(alias = "HostCommandExited")]
+    pub async fn receive_host_command_exited_stream(
+        &self,
+    ) -> Result<impl Stream<Item = (u32, u32)> + '_, Error> {
+        receive_signal_stream(&self.0, "HostCommandExited").await
+    }
+
+    /// Runs a command on the host, outside of the sandbox.
+    ///
+    /// # Arguments
+    ///
+    /// * `cwd_path` - The working directory for the new process, on the host.
+    /// * `argv` - The argv for the new process, starting with the executable to
+    ///   launch.
+    /// * `fds` - File descriptors to pass to the new process, by their number
+    ///   in the new process.
+    /// * `envs` - Variable/value pairs to add to the environment of the new
+    ///   process.
+    /// * `flags` - A [`HostCommandFlags`].
+    ///
+    /// # Returns
+    ///
+    /// A [`SpawnedProcess`] handle to wait for, or signal, the new process.
+    #[doc(alias = "HostCommand")]
+    pub async fn host_command<C: AsRef<Path>, S: AsRef<Path>>(
+        &self,
+        cwd_path: C,
+        argv: &[S],
+        fds: HashMap<u32, Fd>,
+        envs: HashMap<&str, &str>,
+        flags: BitFlags<HostCommandFlags>,
+    ) -> Result<SpawnedProcess<'_>, Error> {
+        let argv = argv.iter().map(AsRef::as_ref).collect::<Vec<_>>();
+        self.host_command_paths(cwd_path.as_ref(), &argv, fds, envs, flags)
+            .await
+    }
+
+    pub(crate) async fn host_command_paths(
+        &self,
+        cwd_path: &Path,
+        argv: &[&Path],
+        fds: HashMap<u32, Fd>,
+        envs: HashMap<&str, &str>,
+        flags: BitFlags<HostCommandFlags>,
+    ) -> Result<SpawnedProcess<'_>, Error> {
+        let cwd_path = c_path(cwd_path)?;
+        let argv = argv
+            .iter()
+            .map(|arg| c_path(arg))
+            .collect::<Result<Vec<_>, _>>()?;
+        // Subscribe before spawning so a process exiting right away is seen.
+        let exited = Box::pin(receive_signal_stream(&self.0, "HostCommandExited").await?);
+        let pid = call_method(
+            &self.0,
+            "HostCommand",
+            &(
+                cwd_path.as_bytes_with_nul(),
+                argv.iter()
+                    .map(|c| c.as_bytes_with_nul())
+                    .collect::<Vec<_>>(),
+                fds,
+                envs,
+                flags,
+            ),
+        )
+        .await?;
+        Ok(SpawnedProcess::new(
+            &self.0,
+            "HostCommandSignal",
+            pid,
+            exited,
+            None,
+        ))
+    }
+
+    /// Sends the Unix signal `signal` to a process started with
+    /// [`host_command()`][`DevelopmentProxy::host_command`].
+    ///
+    /// # Arguments
+    ///
+    /// * `pid` - The PID of the process to send the signal to.
+    /// * `signal` - The signal to send.
+    /// * `to_process_group` - Whether to send the signal to the process group.
+    #[doc(alias = "HostCommandSignal")]
+    pub async fn host_command_signal(
+        &self,
+        pid: u32,
+        signal: u32,
+        to_process_group: bool,
+    ) -> Result<(), Error> {
+        call_method(
+            &self.0,
+            "HostCommandSignal",
+            &(pid, signal, to_process_group),
+        )
+        .await
+    }
+}
@@ -11,7 +11,7 @@
 //!     let connection = zbus::azync::Connection::session().await?;
 //!     let proxy = FlatpakProxy::new(&connection).await?;
 //!
-//!     proxy
+//!     let mut process = proxy
 //!         .spawn(
 //!             "/",
 //!             &["contrast"],
@@ -22,6 +22,9 @@
 //!         )
 //!         .await?;
 //!
+//!     let status = process.wait().await?;
+//!     println!("contrast exited with {}", status);
+//!
 //!     Ok(())
 //! }
 //! ```
@@ -30,6 +33,7 @@ pub(crate) const DESTINATION: &str = "org.freedesktop.portal.Flatpak";
 pub(crate) const PATH: &str = "/org/freedesktop/portal/Flatpak";
 
 use enumflags2::BitFlags;
+use futures::Stream;
 use serde::Serialize;
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use std::os::unix::ffi::OsStrExt;
@@ -38,7 +42,7 @@ use zvariant::Fd;
 use zvariant_derive::{DeserializeDict, SerializeDict, Type, TypeDict};
 
 use crate::{
-    helpers::{call_method, receive_signal},
+    helpers::{call_method, get_property, receive_signal, receive_signal_stream, require_version},
     Error,
 };
 
@@ -75,17 +79,33 @@ pub enum SpawnFlags {
     Kill = 16,
     /// Expose the sandbox pids in the callers sandbox, only supported if using
     /// user namespaces for containers (not setuid), see the support property.
+    ///
+    /// Requires version 3 of the interface.
     Expose = 32,
     /// Emit a SpawnStarted signal once the sandboxed process has been fully
     /// started.
+    ///
+    /// Requires version 4 of the interface.
     Emit = 64,
+    /// Share the process ID namespace of the caller with the new sandbox,
+    /// only supported if using user namespaces for containers (not setuid),
+    /// see the support property.
+    ///
+    /// Requires version 5 of the interface.
+    SharePids = 128,
+    /// Don't provide app files at `/app` in the new sandbox, or provide the
+    /// ones of [`SpawnOptions::app_fd`].
+    ///
+    /// Requires version 6 of the interface.
+    EmptyApp = 256,
 }
 
 #[derive(Serialize_repr, Deserialize_repr, PartialEq, Copy, Clone, BitFlags, Debug, Type)]
 #[repr(u32)]
 /// Flags marking what optional features are available.
 pub enum SupportsFlags {
-    /// Supports the expose sandbox pids flag of Spawn.
+    /// Supports the [`SpawnFlags::Expose`] & [`SpawnFlags::SharePids`] flags
+    /// of Spawn.
     ExposePids = 1,
 }
 
@@ -94,18 +114,33 @@ pub enum SupportsFlags {
 pub struct SpawnOptions {
     /// A list of filenames for files inside the sandbox that will be exposed to
     /// the new sandbox, for reading and writing.
+    #[zvariant(rename = "sandbox-expose")]
     sandbox_expose: Option<Vec<String>>,
     /// A list of filenames for files inside the sandbox that will be exposed to
     /// the new sandbox, read-only.
+    #[zvariant(rename = "sandbox-expose-ro")]
     sandbox_expose_ro: Option<Vec<String>>,
     /// A list of file descriptor for files inside the sandbox that will be
     /// exposed to the new sandbox, for reading and writing.
+    #[zvariant(rename = "sandbox-expose-fd")]
     sandbox_expose_fd: Option<Vec<Fd>>,
     /// A list of file descriptor for files inside the sandbox that will be
     /// exposed to the new sandbox, read-only.
+    #[zvariant(rename = "sandbox-expose-fd-ro")]
     sandbox_expose_fd_ro: Option<Vec<Fd>>,
     /// Flags affecting the created sandbox.
+    #[zvariant(rename = "sandbox-flags")]
     sandbox_flags: Option<BitFlags<SandboxFlags>>,
+    /// The directory mounted at `/usr` in the new sandbox.
+    #[zvariant(rename = "usr-fd")]
+    usr_fd: Option<Fd>,
+    /// The directory mounted at `/app` in the new sandbox.
+    #[zvariant(rename = "app-fd")]
+    app_fd: Option<Fd>,
+    /// The well-known names the new sandbox is allowed to own on the
+    /// accessibility bus.
+    #[zvariant(rename = "sandbox-a11y-own-names")]
+    sandbox_a11y_own_names: Option<Vec<String>>,
 }
 
 impl SpawnOptions {
@@ -141,6 +176,8 @@ impl SpawnOptions {
     }
 
     /// Sets the list of file descriptors of files to expose the new sandbox.
+    ///
+    /// Requires version 3 of the interface.
     pub fn sandbox_expose_fd<F: AsRawFd>(mut self, sandbox_expose_fd: &[&F]) -> Self {
         self.sandbox_expose_fd = Some(
             sandbox_expose_fd
@@ -153,6 +190,8 @@ impl SpawnOptions {
 
     /// Sets the list of file descriptors of files to expose the new sandbox,
     /// read-only.
+    ///
+    /// Requires version 3 of the interface.
     pub fn sandbox_expose_fd_ro<F: AsRawFd>(mut self, sandbox_expose_fd_ro: &[&F]) -> Self {
         self.sandbox_expose_fd_ro = Some(
             sandbox_expose_fd_ro
@@ -168,6 +207,58 @@ impl SpawnOptions {
         self.sandbox_flags = Some(sandbox_flags);
         self
     }
+
+    /// Sets the directory to mount at `/usr` in the new sandbox, instead of
+    /// the runtime of the caller.
+    ///
+    /// Requires version 6 of the interface.
+    pub fn usr_fd<F: AsRawFd>(mut self, usr_fd: &F) -> Self {
+        self.usr_fd = Some(Fd::from(usr_fd.as_raw_fd()));
+        self
+    }
+
+    /// Sets the directory to mount at `/app` in the new sandbox, instead of
+    /// the application of the caller.
+    ///
+    /// Requires version 6 of the interface.
+    pub fn app_fd<F: AsRawFd>(mut self, app_fd: &F) -> Self {
+        self.app_fd = Some(Fd::from(app_fd.as_raw_fd()));
+        self
+    }
+
+    /// Sets the well-known names the new sandbox is allowed to own on the
+    /// accessibility bus, with
+    /// [`SandboxFlags::AccessibilityBusAccess`].
+    ///
+    /// Requires version 7 of the interface.
+    pub fn sandbox_a11y_own_names<S: AsRef<str>>(mut self, names: &[S]) -> Self {
+        self.sandbox_a11y_own_names = Some(names.iter().map(|s| s.as_ref().to_string()).collect());
+        self
+    }
+
+    /// The version of the interface required by `flags` & the options.
+    fn required_version(&self, flags: BitFlags<SpawnFlags>) -> u32 {
+        let mut version = 1;
+        let mut require = |used: bool, since: u32| {
+            if used {
+                version = version.max(since);
+            }
+        };
+        require(
+            flags.contains(SpawnFlags::Expose)
+                || self.sandbox_expose_fd.is_some()
+                || self.sandbox_expose_fd_ro.is_some(),
+            3,
+        );
+        require(flags.contains(SpawnFlags::Emit), 4);
+        require(flags.contains(SpawnFlags::SharePids), 5);
+        require(
+            flags.contains(SpawnFlags::EmptyApp) || self.usr_fd.is_some() || self.app_fd.is_some(),
+            6,
+        );
+        require(self.sandbox_a11y_own_names.is_some(), 7);
+        version
+    }
 }
 
 #[derive(SerializeDict, DeserializeDict, TypeDict, Debug, Default)]
@@ -183,7 +274,7 @@ struct CreateMonitorOptions {}
 /// Wrapper of the DBus interface: [`org.freedesktop.portal.Flatpak`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-org.freedesktop.portal.Flatpak).
 #[derive(Debug)]
 #[doc(alias = "org.freedesktop.portal.Flatpak")]
-pub struct FlatpakProxy<'a>(zbus::azync::Proxy<'a>);
+pub struct FlatpakProxy<'a>(crate::helpers::Proxy<'a>);
 
 impl<'a> FlatpakProxy<'a> {
     /// Create a new instance of [`FlatpakProxy`].
@@ -194,7 +285,7 @@ impl<'a> FlatpakProxy<'a> {
             .destination(DESTINATION)?
             .build()
             .await?;
-        Ok(Self(proxy))
+        Ok(Self(proxy.into()))
     }
 
     /// Get a reference to the underlying Proxy.
@@ -202,6 +293,10 @@ impl<'a> FlatpakProxy<'a> {
         &self.0
     }
 
+    proxy_set_timeout!();
+
+    proxy_version!(Flatpak);
+
     /// Creates an update monitor object that will emit signals
     /// when an update for the caller becomes available, and can be used to
     /// install it.
@@ -223,6 +318,17 @@ impl<'a> FlatpakProxy<'a> {
         receive_signal(&self.0, "SpawnStarted").await
     }
 
+    /// Emitted when a process starts by [`spawn()`][`FlatpakProxy::spawn`].
+    ///
+    /// Unlike
+    /// [`receive_spawn_started()`][`FlatpakProxy::receive_spawn_started`],
+    /// the returned stream stays subscribed and yields every emitted signal.
+    pub async fn receive_spawn_started_stream(
+        &self,
+    ) -> Result<impl Stream<Item = (u32, u32)> + '_, Error> {
+        receive_signal_stream(&self.0, "SpawnStarted").await
+    }
+
     /// Emitted when a process started by [`spawn()`][`FlatpakProxy::spawn`]
     /// exits.
     ///
@@ -230,10 +336,34 @@ impl<'a> FlatpakProxy<'a> {
     ///
     /// See also [`SpawnExited`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-signal-org-freedesktop-portal-Flatpak.SpawnExited).
     #[doc(alias = "SpawnExited")]
-    pub async fn receive_spawn_existed(&self) -> Result<(u32, u32), Error> {
+    pub async fn receive_spawn_exited(&self) -> Result<(u32, u32), Error> {
         receive_signal(&self.0, "SpawnExited").await
     }
 
+    /// Emitted when a process started by [`spawn()`][`FlatpakProxy::spawn`]
+    /// exits.
+    #[deprecated(note = "Use `receive_spawn_exited()` instead")]
+    pub async fn receive_spawn_existed(&self) -> Result<(u32, u32), Error> {
+        self.receive_spawn_exited().await
+    }
+
+    /// Emitted when a process started by [`spawn()`][`FlatpakProxy::spawn`]
+    /// exits.
+    ///
+    /// Unlike
+    /// [`receive_spawn_exited()`][`FlatpakProxy::receive_spawn_exited`],
+    /// the returned stream stays subscribed and yields every emitted signal.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`SpawnExited`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-signal-org-freedesktop-portal-Flatpak.SpawnExited).
+    #[doc(alias = "SpawnExited")]
+    pub async fn receive_spawn_exited_stream(
+        &self,
+    ) -> Result<impl Stream<Item = (u32, u32)> + '_, Error> {
+        receive_signal_stream(&self.0, "SpawnExited").await
+    }
+
     /// This methods let you start a new instance of your application,
     /// optionally enabling a tighter sandbox.
     ///
@@ -250,7 +380,7 @@ impl<'a> FlatpakProxy<'a> {
     ///
     /// # Returns
     ///
-    /// The PID of the new process.
+    /// A [`SpawnedProcess`] handle to wait for, or signal, the new process.
     ///
     /// # Specifications
     ///
@@ -267,17 +397,59 @@ impl<'a> FlatpakProxy<'a> {
         envs: HashMap<&str, &str>,
         flags: BitFlags<SpawnFlags>,
         options: SpawnOptions,
-    ) -> Result<u32, Error> {
-        let cwd_path = CString::new(cwd_path.as_ref().as_os_str().as_bytes())
-            .expect("The `cwd_path` should not contain a trailing 0 bytes");
+    ) -> Result<SpawnedProcess<'_>, Error> {
+        let argv = argv.iter().map(AsRef::as_ref).collect::<Vec<_>>();
+        self.spawn_paths(cwd_path.as_ref(), &argv, fds, envs, flags, options)
+            .await
+    }
+
+    /// Checks the portal supports spawning with `flags` & `options`, done by
+    /// [`spawn()`][`FlatpakProxy::spawn`] as well.
+    ///
+    /// Fails with [`Error::RequiresVersion`] if the interface is too old, or
+    /// [`Error::RequiresSupport`] if [`SpawnFlags::Expose`] or
+    /// [`SpawnFlags::SharePids`] is used without
+    /// [`SupportsFlags::ExposePids`].
+    pub async fn check_spawn_support(
+        &self,
+        flags: BitFlags<SpawnFlags>,
+        options: &SpawnOptions,
+    ) -> Result<(), Error> {
+        let required = options.required_version(flags);
+        if required > 1 {
+            require_version(&self.0, required).await?;
+        }
+        if flags.intersects(SpawnFlags::Expose | SpawnFlags::SharePids)
+            && !self.supports().await?.contains(SupportsFlags::ExposePids)
+        {
+            return Err(Error::RequiresSupport(SupportsFlags::ExposePids));
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn spawn_paths(
+        &self,
+        cwd_path: &Path,
+        argv: &[&Path],
+        fds: HashMap<u32, Fd>,
+        envs: HashMap<&str, &str>,
+        flags: BitFlags<SpawnFlags>,
+        options: SpawnOptions,
+    ) -> Result<SpawnedProcess<'_>, Error> {
+        let cwd_path = c_path(cwd_path)?;
         let argv = argv
             .iter()
-            .map(|s| {
-                CString::new(s.as_ref().as_os_str().as_bytes())
-                    .expect("The `argv` should not contain a trailing 0 bytes")
-            })
-            .collect::<Vec<_>>();
-        call_method(
+            .map(|arg| c_path(arg))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.check_spawn_support(flags, &options).await?;
+        // Subscribe before spawning so a process exiting right away is seen.
+        let exited = Box::pin(receive_signal_stream(&self.0, "SpawnExited").await?);
+        let started = if flags.contains(SpawnFlags::Emit) {
+            Some(Box::pin(receive_signal_stream(&self.0, "SpawnStarted").await?) as _)
+        } else {
+            None
+        };
+        let pid = call_method(
             &self.0,
             "Spawn",
             &(
@@ -291,7 +463,14 @@ impl<'a> FlatpakProxy<'a> {
                 options,
             ),
         )
-        .await
+        .await?;
+        Ok(SpawnedProcess::new(
+            &self.0,
+            "SpawnSignal",
+            pid,
+            exited,
+            started,
+        ))
     }
 
     /// This methods let you send a Unix signal to a process that was started
@@ -322,13 +501,33 @@ impl<'a> FlatpakProxy<'a> {
     ///
     /// See also [`supports`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-property-org-freedesktop-portal-Flatpak.supports).
     pub async fn supports(&self) -> Result<BitFlags<SupportsFlags>, Error> {
-        self.inner()
-            .get_property::<BitFlags<SupportsFlags>>("supports")
-            .await
-            .map_err(From::from)
+        get_property::<BitFlags<SupportsFlags>>(&self.0, "supports").await
     }
 }
 
+/// The path as a nul terminated byte array, the `ay` type of the spawn methods.
+///
+/// Fails with an [`std::io::ErrorKind::InvalidInput`] error if `path` contains
+/// a nul byte.
+pub(crate) fn c_path(path: &Path) -> Result<CString, Error> {
+    CString::new(path.as_os_str().as_bytes()).map_err(|err| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("{:?} contains a nul byte: {}", path, err),
+        )
+        .into()
+    })
+}
+
+/// Run commands on the host, outside of the sandbox.
+pub mod development;
+
+mod command;
+pub use command::{Child, Command, Output, Stdio};
+
+mod spawned_process;
+pub use spawned_process::{ExitStatus, SpawnedProcess};
+
 /// Monitor if there's an update it and install it.
 mod update_monitor;
 pub use update_monitor::{UpdateInfo, UpdateMonitorProxy, UpdateProgress, UpdateStatus};
@@ -0,0 +1,197 @@
+use std::{fmt::Debug, pin::Pin};
+
+use futures::{Stream, StreamExt};
+
+use crate::{helpers::call_method, Error};
+
+/// The signal sent by [`SpawnedProcess::kill`].
+const SIGKILL: u32 = 9;
+
+type SignalStream<'a> = Pin<Box<dyn Stream<Item = (u32, u32)> + Send + 'a>>;
+
+/// The exit status of a process, as returned by `waitpid`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExitStatus(u32);
+
+impl ExitStatus {
+    /// Create an [`ExitStatus`] from the raw wait status.
+    pub fn from_raw(status: u32) -> Self {
+        Self(status)
+    }
+
+    /// The raw wait status.
+    pub fn into_raw(self) -> u32 {
+        self.0
+    }
+
+    /// Whether the process exited with the code `0`.
+    pub fn success(&self) -> bool {
+        self.code() == Some(0)
+    }
+
+    /// The exit code of the process, if it exited normally.
+    pub fn code(&self) -> Option<i32> {
+        if self.0 & 0x7f == 0 {
+            Some(((self.0 >> 8) & 0xff) as i32)
+        } else {
+            None
+        }
+    }
+
+    /// The signal that terminated the process, if it was killed.
+    pub fn signal(&self) -> Option<i32> {
+        let signal = self.0 & 0x7f;
+        if signal != 0 && signal != 0x7f {
+            Some(signal as i32)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the process dumped its core when killed.
+    pub fn core_dumped(&self) -> bool {
+        self.signal().is_some() && self.0 & 0x80 != 0
+    }
+}
+
+impl std::fmt::Display for ExitStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.code(), self.signal()) {
+            (Some(code), _) => write!(f, "exit code: {}", code),
+            (None, Some(signal)) => write!(f, "signal: {}", signal),
+            (None, None) => write!(f, "unrecognized wait status: {}", self.0),
+        }
+    }
+}
+
+/// A process started with [`FlatpakProxy::spawn`](super::FlatpakProxy::spawn)
+/// or, on the host, with
+/// [`DevelopmentProxy::host_command`](super::development::DevelopmentProxy::host_command).
+///
+/// The signals telling the process started & exited are listened to before
+/// the process is spawned, so none is missed.
+pub struct SpawnedProcess<'a> {
+    proxy: &'a zbus::azync::Proxy<'a>,
+    signal_method: &'static str,
+    pid: u32,
+    exited: SignalStream<'a>,
+    started: Option<SignalStream<'a>>,
+    exit_status: Option<ExitStatus>,
+}
+
+impl<'a> SpawnedProcess<'a> {
+    pub(crate) fn new(
+        proxy: &'a zbus::azync::Proxy<'a>,
+        signal_method: &'static str,
+        pid: u32,
+        exited: SignalStream<'a>,
+        started: Option<SignalStream<'a>>,
+    ) -> Self {
+        Self {
+            proxy,
+            signal_method,
+            pid,
+            exited,
+            started,
+            exit_status: None,
+        }
+    }
+
+    /// The PID of the process, as seen from the caller.
+    pub fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    /// Wait for the process to be fully started and return its PID relative
+    /// to the new sandbox.
+    ///
+    /// Returns `None` if the process wasn't spawned with
+    /// [`SpawnFlags::Emit`](super::SpawnFlags::Emit), as the portal doesn't
+    /// emit the `SpawnStarted` signal then, or if it was spawned on the host.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`SpawnStarted`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-signal-org-freedesktop-portal-Flatpak.SpawnStarted).
+    #[doc(alias = "SpawnStarted")]
+    pub async fn started(&mut self) -> Result<Option<u32>, Error> {
+        let pid = self.pid;
+        let started = match self.started {
+            Some(ref mut started) => started,
+            None => return Ok(None),
+        };
+        while let Some((started_pid, relative_pid)) = started.next().await {
+            if started_pid == pid {
+                return Ok(Some(relative_pid));
+            }
+        }
+        Err(Error::SignalStreamEnded)
+    }
+
+    /// Wait for the process to exit and return its exit status.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`SpawnExited`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-signal-org-freedesktop-portal-Flatpak.SpawnExited).
+    #[doc(alias = "SpawnExited")]
+    #[doc(alias = "HostCommandExited")]
+    pub async fn wait(&mut self) -> Result<ExitStatus, Error> {
+        if let Some(status) = self.exit_status {
+            return Ok(status);
+        }
+        while let Some((pid, status)) = self.exited.next().await {
+            if pid == self.pid {
+                let status = ExitStatus::from_raw(status);
+                self.exit_status = Some(status);
+                return Ok(status);
+            }
+        }
+        Err(Error::SignalStreamEnded)
+    }
+
+    /// Send the Unix signal `signal` to the process, or to its process group.
+    ///
+    /// See also [`FlatpakProxy::spawn_signal`](super::FlatpakProxy::spawn_signal).
+    #[doc(alias = "SpawnSignal")]
+    #[doc(alias = "HostCommandSignal")]
+    pub async fn signal(&self, signal: u32, to_process_group: bool) -> Result<(), Error> {
+        call_method(
+            self.proxy,
+            self.signal_method,
+            &(self.pid, signal, to_process_group),
+        )
+        .await
+    }
+
+    /// Kill the process, or its process group, with `SIGKILL`.
+    pub async fn kill(&self, to_process_group: bool) -> Result<(), Error> {
+        self.signal(SIGKILL, to_process_group).await
+    }
+}
+
+impl<'a> Debug for SpawnedProcess<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SpawnedProcess")
+            .field("pid", &self.pid)
+            .field("exit_status", &self.exit_status)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ExitStatus;
+
+    #[test]
+    fn exit_status() {
+        let exited = ExitStatus::from_raw(3 << 8);
+        assert_eq!(exited.code(), Some(3));
+        assert_eq!(exited.signal(), None);
+        assert!(!exited.success());
+        assert!(ExitStatus::from_raw(0).success());
+
+        let killed = ExitStatus::from_raw(9 | 0x80);
+        assert_eq!(killed.code(), None);
+        assert_eq!(killed.signal(), Some(9));
+        assert!(killed.core_dumped());
+    }
+}
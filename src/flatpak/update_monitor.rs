@@ -22,13 +22,14 @@
 //! }
 //! ```
 
+use futures::Stream;
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use zvariant::ObjectPath;
 use zvariant_derive::{DeserializeDict, SerializeDict, Type, TypeDict};
 
 use super::DESTINATION;
 use crate::{
-    helpers::{call_method, receive_signal},
+    helpers::{call_method, receive_signal, receive_signal_stream},
     Error, WindowIdentifier,
 };
 
@@ -126,6 +127,22 @@ impl<'a> UpdateMonitorProxy<'a> {
         receive_signal(&self.0, "Progress").await
     }
 
+    /// A signal received when there's progress during the application update.
+    ///
+    /// Unlike
+    /// [`receive_progress()`][`UpdateMonitorProxy::receive_progress`],
+    /// the returned stream stays subscribed and yields every emitted signal.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`Progress`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-signal-org-freedesktop-portal-Flatpak-UpdateMonitor.Progress).
+    #[doc(alias = "Progress")]
+    pub async fn receive_progress_stream(
+        &self,
+    ) -> Result<impl Stream<Item = UpdateProgress> + '_, Error> {
+        receive_signal_stream(&self.0, "Progress").await
+    }
+
     /// A signal received when there's an application update.
     ///
     /// # Specifications
@@ -136,6 +153,22 @@ impl<'a> UpdateMonitorProxy<'a> {
         receive_signal(&self.0, "UpdateAvailable").await
     }
 
+    /// A signal received when there's an application update.
+    ///
+    /// Unlike
+    /// [`receive_update_available()`][`UpdateMonitorProxy::receive_update_available`],
+    /// the returned stream stays subscribed and yields every emitted signal.
+    ///
+    /// # Specifications
+    ///
+    /// See also [`UpdateAvailable`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-signal-org-freedesktop-portal-Flatpak-UpdateMonitor.UpdateAvailable).
+    #[doc(alias = "UpdateAvailable")]
+    pub async fn receive_update_available_stream(
+        &self,
+    ) -> Result<impl Stream<Item = UpdateInfo> + '_, Error> {
+        receive_signal_stream(&self.0, "UpdateAvailable").await
+    }
+
     /// Asks to install an update of the calling app.
     ///
     /// **Note** updates are only allowed if the new version has the same
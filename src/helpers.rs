@@ -1,21 +1,85 @@
 use std::{
+    convert::TryFrom,
     ffi::OsStr,
     fmt::Debug,
+    future::Future,
+    ops::Deref,
     os::unix::prelude::OsStrExt,
     path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::Duration,
 };
 
-use futures::StreamExt;
+use async_io::Timer;
+use futures::{channel::oneshot, future::Either, FutureExt, Stream, StreamExt};
 use serde::Deserialize;
+use zbus::{Message, MessageType};
+use zvariant::{ObjectPath, OwnedObjectPath, OwnedValue};
 
 use crate::desktop::{
-    request::{BasicResponse, RequestProxy, Response},
+    request::{close_request, AbortToken, BasicResponse, CallOptions, RequestProxy, Response},
     HandleToken,
 };
 use crate::Error;
 
+/// The proxy wrapped by the portal proxies, holding the default timeout
+/// applied to their requests & property reads.
+#[derive(Debug)]
+pub(crate) struct Proxy<'a> {
+    inner: zbus::azync::Proxy<'a>,
+    timeout: Option<Duration>,
+}
+
+impl<'a> Proxy<'a> {
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+}
+
+impl<'a> Deref for Proxy<'a> {
+    type Target = zbus::azync::Proxy<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<'a> From<zbus::azync::Proxy<'a>> for Proxy<'a> {
+    fn from(inner: zbus::azync::Proxy<'a>) -> Self {
+        Self {
+            inner,
+            timeout: None,
+        }
+    }
+}
+
+/// The match rule of the `Response` signal of the request objects.
+const RESPONSE_MATCH_RULE: &str = "type='signal',interface='org.freedesktop.portal.Request',\
+    member='Response',path_namespace='/org/freedesktop/portal/desktop/request'";
+
+/// Run `future`, failing with [`Error::Timeout`] once `timeout` expired.
+async fn timed<T, F>(timeout: Option<Duration>, future: F) -> Result<T, Error>
+where
+    F: Future<Output = Result<T, Error>>,
+{
+    let timeout = match timeout {
+        Some(timeout) => timeout,
+        None => return future.await,
+    };
+    futures::pin_mut!(future);
+    match futures::future::select(future, Timer::after(timeout)).await {
+        Either::Left((result, _)) => result,
+        Either::Right(_) => Err(Error::Timeout),
+    }
+}
+
 pub(crate) async fn call_request_method<R, B>(
-    proxy: &zbus::azync::Proxy<'_>,
+    proxy: &Proxy<'_>,
+    call: &CallOptions,
     handle_token: &HandleToken,
     method_name: &str,
     body: &B,
@@ -30,43 +94,187 @@ where
         method_name
     );
     tracing::debug!("The body is: {:#?}", body);
-    let request = RequestProxy::from_unique_name(proxy.connection(), handle_token).await?;
-    // We don't use receive_response because we want to create the stream in advance
+    let token = call.abort_token.as_ref();
+    let timeout = call.timeout.unwrap_or_else(|| proxy.timeout());
+    if token.map_or(false, AbortToken::is_aborted) {
+        return Err(Error::RequestClosed);
+    }
+    let connection = proxy.connection();
+    let request = RequestProxy::from_unique_name(connection, handle_token).await?;
+    // Portals older than 0.9 ignore the handle token & create the request at
+    // another path, the responses of all the requests are subscribed to before
+    // calling the method and filtered by path, so the response isn't missed if
+    // it is emitted before the path is known.
     tracing::info!(
         "Listening to signal 'Response' on '{}'",
         request.inner().interface()
     );
-    let mut stream = request.inner().receive_signal("Response").await?;
-
-    let (response, path) = futures::try_join!(
-        async {
-            let message = stream.next().await.ok_or(Error::NoResponse)?;
-            tracing::info!(
-                "Received signal 'Response' on '{}'",
-                request.inner().interface()
-            );
-            let response = match message.body::<Response<R>>()? {
-                Response::Err(e) => Err(e.into()),
-                Response::Ok(r) => Ok(r),
-            };
-
-            tracing::debug!("Received response {:#?}", response);
-            response as Result<_, Error>
-        },
-        async {
-            let msg = proxy.call_method(method_name, body).await?;
-            let path = msg.body::<zvariant::OwnedObjectPath>()?.into_inner();
-
-            tracing::debug!("Received request path {}", path.as_str());
-            Ok(path) as Result<zvariant::ObjectPath<'_>, Error>
-        },
-    )?;
-    assert_eq!(&path, request.inner().path());
+    call_dbus_method::<()>(connection, "AddMatch", RESPONSE_MATCH_RULE).await?;
+    let messages = connection.clone();
+    let (path_sender, path_receiver) = oneshot::channel();
+
+    let (response, abort_handle) = futures::future::abortable(async {
+        let message = receive_response(messages, request.inner().path(), path_receiver).await?;
+        tracing::info!(
+            "Received signal 'Response' on '{}'",
+            request.inner().interface()
+        );
+        let response = match message.body::<Response<R>>()? {
+            Response::Err(e) => Err(e.into()),
+            Response::Ok(r) => Ok(r),
+        };
+
+        tracing::debug!("Received response {:#?}", response);
+        response as Result<_, Error>
+    });
+    if let Some(token) = token {
+        token.register(connection, request.inner().path(), abort_handle);
+    }
+
+    let actual_path = Mutex::new(None);
+    let method = async {
+        let msg = proxy.call_method(method_name, body).await?;
+        let path = msg.body::<OwnedObjectPath>()?;
+
+        tracing::debug!("Received request path {}", path.as_str());
+        *actual_path.lock().unwrap() = Some(path.clone());
+        if let Some(token) = token {
+            if !token.resolve(request.inner().path(), &path) {
+                // The token was aborted before the request path was known.
+                if let Err(err) = close_request(connection, path).await {
+                    tracing::warn!("Failed to close the request: {}", err);
+                }
+                return Err(Error::RequestClosed);
+            }
+        }
+        // The receiver is gone if the response was already received.
+        let _ = path_sender.send(path.clone());
+        Ok(path) as Result<OwnedObjectPath, Error>
+    };
+    let response = async { response.await.map_err(|_| Error::RequestClosed)? };
+    let call = async {
+        futures::pin_mut!(response, method);
+        match futures::future::select(response, method).await {
+            Either::Left((Ok(response), method)) => Ok((response, method.await?)),
+            Either::Left((Err(err), method)) => {
+                // An aborted request is only closed once its path is known.
+                if token.map_or(false, AbortToken::is_aborted) {
+                    let _ = method.await;
+                }
+                Err(err)
+            }
+            Either::Right((path, response)) => {
+                let path = path?;
+                Ok((response.await?, path))
+            }
+        }
+    };
+    let result = timed(timeout, call).await;
+    if let Err(err) = call_dbus_method::<()>(connection, "RemoveMatch", RESPONSE_MATCH_RULE).await {
+        tracing::warn!("Failed to remove the match rule of the responses: {}", err);
+    }
+    let actual_path = actual_path.into_inner().unwrap();
+    if let Some(token) = token {
+        token.unregister(request.inner().path());
+    }
+    if let Err(Error::Timeout) = result {
+        let path = actual_path.unwrap_or_else(|| request.inner().path().clone().into());
+        tracing::warn!("The request {} timed out, closing it", path.as_str());
+        if let Err(err) = close_request(connection, path).await {
+            tracing::warn!("Failed to close the request: {}", err);
+        }
+    }
+    let (response, _path) = result?;
     Ok(response)
 }
 
+/// Wait for the `Response` signal of the request object at `expected`, or at
+/// the path received from `path` if the portal returned a different one.
+async fn receive_response(
+    messages: zbus::azync::Connection,
+    expected: &ObjectPath<'_>,
+    path: oneshot::Receiver<OwnedObjectPath>,
+) -> Result<Arc<Message>, Error> {
+    let responses = messages
+        .filter_map(|message| futures::future::ready(message.ok()))
+        .filter(|message| futures::future::ready(is_response(message)))
+        .fuse();
+    futures::pin_mut!(responses);
+    let mut path = path.fuse();
+    let mut expected = OwnedObjectPath::from(expected.clone());
+    // The responses to requests at other paths, received before the actual
+    // path of the request is known.
+    let mut early = Vec::new();
+    let mut resolved = false;
+
+    loop {
+        futures::select! {
+            actual = path => {
+                resolved = true;
+                let actual = match actual {
+                    Ok(actual) if actual != expected => actual,
+                    _ => continue,
+                };
+                tracing::warn!(
+                    "Expected the request at {}, the portal returned {}",
+                    expected.as_str(),
+                    actual.as_str()
+                );
+                expected = actual;
+                if let Some(message) = early
+                    .drain(..)
+                    .find(|message| message_path_is(message, &expected))
+                {
+                    return Ok(message);
+                }
+            }
+            message = responses.next() => match message {
+                Some(message) if message_path_is(&message, &expected) => return Ok(message),
+                Some(message) if !resolved => early.push(message),
+                Some(_) => (),
+                None => return Err(Error::ConnectionClosed),
+            },
+        }
+    }
+}
+
+/// Whether `message` is a `Response` signal of a request object.
+fn is_response(message: &Message) -> bool {
+    message.message_type() == MessageType::Signal
+        && message
+            .interface()
+            .map_or(false, |i| i.to_string() == "org.freedesktop.portal.Request")
+        && message
+            .member()
+            .map_or(false, |m| m.to_string() == "Response")
+}
+
+fn message_path_is(message: &Message, path: &ObjectPath<'_>) -> bool {
+    message
+        .path()
+        .map_or(false, |p| p.as_str() == path.as_str())
+}
+
+async fn call_dbus_method<R>(
+    connection: &zbus::azync::Connection,
+    method_name: &str,
+    rule: &str,
+) -> Result<R, Error>
+where
+    R: for<'de> Deserialize<'de> + zvariant::Type,
+{
+    let proxy = zbus::azync::ProxyBuilder::new_bare(connection)
+        .interface("org.freedesktop.DBus")?
+        .path("/org/freedesktop/DBus")?
+        .destination("org.freedesktop.DBus")?
+        .build()
+        .await?;
+    call_method(&proxy, method_name, &(rule)).await
+}
+
 pub(crate) async fn call_basic_response_method<B>(
-    proxy: &zbus::azync::Proxy<'_>,
+    proxy: &Proxy<'_>,
+    call: &CallOptions,
     handle_token: &HandleToken,
     method_name: &str,
     body: &B,
@@ -74,10 +282,12 @@ pub(crate) async fn call_basic_response_method<B>(
 where
     B: serde::ser::Serialize + zvariant::Type + Debug,
 {
-    call_request_method::<BasicResponse, B>(proxy, handle_token, method_name, body).await?;
+    call_request_method::<BasicResponse, B>(proxy, call, handle_token, method_name, body).await?;
     Ok(())
 }
 
+/// Wait for the next `signal_name` signal and deserialize it, unlike
+/// [`receive_signal_stream`] a signal with an unexpected body is an error.
 pub(crate) async fn receive_signal<R>(
     proxy: &zbus::azync::Proxy<'_>,
     signal_name: &'static str,
@@ -91,7 +301,45 @@ where
         proxy.interface()
     );
     let mut stream = proxy.receive_signal(signal_name).await?;
-    let message = stream.next().await.ok_or(Error::NoResponse)?;
+    let message = stream.next().await.ok_or(Error::SignalStreamEnded)?;
+    signal_body(proxy, signal_name, &message).map_err(From::from)
+}
+
+/// The stream of the `signal_name` signals, the signals with an unexpected
+/// body are logged and skipped.
+pub(crate) async fn receive_signal_stream<'a, R>(
+    proxy: &'a zbus::azync::Proxy<'_>,
+    signal_name: &'static str,
+) -> Result<impl Stream<Item = R> + 'a, Error>
+where
+    R: for<'de> Deserialize<'de> + zvariant::Type + Debug,
+{
+    tracing::info!(
+        "Listening to signal '{}' on '{}'",
+        signal_name,
+        proxy.interface()
+    );
+    let stream = proxy.receive_signal(signal_name).await?;
+    Ok(stream.filter_map(move |message| {
+        let content = match signal_body(proxy, signal_name, &message) {
+            Ok(content) => Some(content),
+            Err(err) => {
+                tracing::warn!("Failed to deserialize signal '{}': {}", signal_name, err);
+                None
+            }
+        };
+        futures::future::ready(content)
+    }))
+}
+
+fn signal_body<R>(
+    proxy: &zbus::azync::Proxy<'_>,
+    signal_name: &str,
+    message: &Message,
+) -> Result<R, zbus::Error>
+where
+    R: for<'de> Deserialize<'de> + zvariant::Type + Debug,
+{
     tracing::info!(
         "Received signal '{}' on '{}'",
         signal_name,
@@ -120,8 +368,58 @@ where
     Ok(reply)
 }
 
+/// Read the property `name` of the interface of `proxy`.
+pub(crate) async fn get_property<T>(proxy: &Proxy<'_>, name: &str) -> Result<T, Error>
+where
+    T: TryFrom<OwnedValue>,
+{
+    timed(proxy.timeout(), async {
+        proxy.get_property::<T>(name).await.map_err(From::from)
+    })
+    .await
+}
+
+/// Fails with [`Error::RequiresVersion`] if the interface of `proxy` is older
+/// than `required`.
+pub(crate) async fn require_version(proxy: &Proxy<'_>, required: u32) -> Result<(), Error> {
+    let found = get_property::<u32>(proxy, "version").await?;
+    if found < required {
+        return Err(Error::RequiresVersion {
+            interface: proxy.interface().to_string(),
+            required,
+            found,
+        });
+    }
+    Ok(())
+}
+
 // Some portals returns paths which are bytes and not a typical string
 // as those might be null terminated. This might make sense to provide in form of a helper in zvariant
 pub(crate) fn path_from_null_terminated(bytes: Vec<u8>) -> PathBuf {
-    Path::new(OsStr::from_bytes(bytes.split_last().unwrap().1)).to_path_buf()
+    let bytes = match bytes.split_last() {
+        Some((0, path)) => path,
+        _ => &bytes,
+    };
+    Path::new(OsStr::from_bytes(bytes)).to_path_buf()
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use super::path_from_null_terminated;
+
+    #[test]
+    fn null_terminated_path() {
+        assert_eq!(
+            path_from_null_terminated(b"/tmp\0".to_vec()),
+            Path::new("/tmp")
+        );
+        assert_eq!(
+            path_from_null_terminated(b"/tmp".to_vec()),
+            Path::new("/tmp")
+        );
+        assert_eq!(path_from_null_terminated(vec![0]), Path::new(""));
+        assert_eq!(path_from_null_terminated(Vec::new()), Path::new(""));
+    }
 }
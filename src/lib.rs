@@ -42,16 +42,37 @@
 //! }
 //! ```
 //!
+//! Share a single connection & a single instance of the proxies across the
+//! application
+//! ```rust,no_run
+//! use ashpd::{Portal, WindowIdentifier};
+//!
+//! async fn run() -> ashpd::Result<()> {
+//!     let portal = Portal::new();
+//!
+//!     let color = portal
+//!         .screenshot()
+//!         .await?
+//!         .pick_color(&WindowIdentifier::default())
+//!         .await?;
+//!     println!("({}, {}, {})", color.red(), color.green(), color.blue());
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
 //! For a tour of the various portals, see the ASHPD demo application.
 //!
 //! # Optional features
 //!
 //! | Feature | Description |
 //! | ---     | ----------- |
+//! | feature_blocking | Provides `ashpd::blocking`, synchronous variants of the proxies built on top of [`zbus::Connection`] |
 //! | feature_gtk3 | Implement From<[Color](desktop::screenshot::Color)> for [`gdk3::RGBA`](https://gtk-rs.org/gtk3-rs/stable/latest/docs/gdk/struct.RGBA.html) |
 //! |  | Provides `WindowIdentifier::from_window` that takes a [`IsA<gdk3::Window>`](https://gtk-rs.org/gtk3-rs/stable/latest/docs/gdk/struct.Window.html) |
 //! | feature_gtk4 | Implement From<[Color](desktop::screenshot::Color)> for [`gdk4::RGBA`](https://gtk-rs.org/gtk4-rs/stable/latest/docs/gdk4/struct.RGBA.html) |
 //! |  | Provides `WindowIdentifier::from_native` that takes a [`IsA<gtk4::Native>`](https://gtk-rs.org/gtk4-rs/stable/latest/docs/gtk4/struct.Native.html) |
+//! | feature_mock | Provides `ashpd::mock` to test applications against in-process mocked portals |
 //! | feature_pipewire  | Provides `ashpd::desktop::camera::pipewire_node_id` that helps you retrieve the PipeWire Node ID to use with the file descriptor returned by the camera portal |
 #[cfg(all(all(feature = "feature_gtk3", feature = "feature_gtk4"), not(doc)))]
 compile_error!("You can't enable both GTK 3 & GTK 4 features at once");
@@ -59,6 +80,53 @@ compile_error!("You can't enable both GTK 3 & GTK 4 features at once");
 /// Alias for a [`Result`] with the error type `ashpd::Error`.
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Implements `set_timeout()` on a portal proxy, a newtype over the crate's
+/// proxy wrapper.
+macro_rules! proxy_set_timeout {
+    () => {
+        /// Sets the default timeout of the requests & property reads made
+        /// with the proxy, `None`, the default, waits forever. The timeout of a
+        /// single request is set with
+        /// [`Request::timeout`](crate::desktop::Request::timeout).
+        ///
+        /// Calls failing to complete in time return
+        /// [`Error::Timeout`](crate::Error::Timeout) and close the pending
+        /// request.
+        pub fn set_timeout(&mut self, timeout: Option<std::time::Duration>) {
+            self.0.set_timeout(timeout);
+        }
+    };
+}
+
+/// Implements `version()` on a portal proxy, reading the `version` property of
+/// the `org.freedesktop.portal.$name` interface, or of `$prefix$name` where
+/// `$prefix` is the dashed interface prefix used by the specifications.
+macro_rules! proxy_version {
+    ($name:ident) => {
+        proxy_version!("org-freedesktop-portal-", $name);
+    };
+    ($prefix:literal, $name:ident) => {
+        /// The version of the interface implemented by the portal.
+        ///
+        /// # Specifications
+        ///
+        #[doc = concat!(
+            "See also [`version`](https://flatpak.github.io/xdg-desktop-portal/portal-docs.html#gdbus-property-",
+            $prefix, stringify!($name), ".version)."
+        )]
+        #[doc(alias = "version")]
+        pub async fn version(&self) -> Result<u32, crate::Error> {
+            crate::helpers::get_property::<u32>(&self.0, "version").await
+        }
+    };
+}
+
+/// Implement the `org.freedesktop.impl.portal.*` interfaces to write a portal
+/// backend.
+pub mod backend;
+/// Blocking variants of the portal proxies.
+#[cfg(feature = "feature_blocking")]
+pub mod blocking;
 /// Interact with the user's desktop such as taking a screenshot, setting a
 /// background or querying the user's location.
 pub mod desktop;
@@ -71,15 +139,30 @@ pub use self::window_identifier::WindowIdentifier;
 /// received an update & install it.
 pub mod flatpak;
 mod helpers;
+mod portal;
+pub use self::portal::Portal;
+mod portal_info;
+/// Detect the sandbox the application runs in and read the permissions
+/// granted to a Flatpak application.
+pub mod sandbox;
+pub use self::portal_info::{available_portals, PortalInfo};
+/// Serve mocked portals on a private connection, to test applications without
+/// a running `xdg-desktop-portal`.
+#[cfg(feature = "feature_mock")]
+pub mod mock;
 pub use enumflags2;
 pub use zbus;
 pub use zvariant;
 
-/// Check whether the application is running inside a sandbox.
+/// Check whether the application is running inside a Flatpak or a Snap, the
+/// sandboxes restricting the access to the user session to the portals.
 ///
-/// **Note** The check is very stupid as is for now.
+/// See [`sandbox::detect`] for the details.
 pub fn is_sandboxed() -> bool {
-    std::path::Path::new("/.flatpak-info").exists()
+    matches!(
+        sandbox::detect(),
+        Some(sandbox::Sandbox::Flatpak) | Some(sandbox::Sandbox::Snap)
+    )
 }
 
 pub use self::error::{Error, PortalError};
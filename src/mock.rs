@@ -0,0 +1,480 @@
+//! # Examples
+//!
+//! Answer a file chooser request without a running xdg-desktop-portal.
+//!
+//! ```rust,no_run
+//! use ashpd::desktop::file_chooser::{FileChooserProxy, OpenFileOptions};
+//! use ashpd::mock::{MockPortal, MockResponse};
+//! use ashpd::WindowIdentifier;
+//! use zvariant::Value;
+//!
+//! async fn run() -> ashpd::Result<()> {
+//!     let mock = MockPortal::default();
+//!     mock.respond(
+//!         "org.freedesktop.portal.FileChooser",
+//!         "OpenFile",
+//!         MockResponse::success().result("uris", Value::from(vec!["file:///tmp/a.txt"])),
+//!     );
+//!     let connection = mock.connect().await?;
+//!
+//!     let proxy = FileChooserProxy::new(&connection).await?;
+//!     let files = proxy
+//!         .open_file(&WindowIdentifier::default(), "open", OpenFileOptions::default())
+//!         .await?;
+//!     assert_eq!(files.uris(), &["file:///tmp/a.txt"]);
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    fmt::{self, Debug},
+    os::unix::net::UnixStream,
+    sync::{Arc, Mutex},
+};
+
+use futures::StreamExt;
+use serde::Serialize;
+use zbus::Message;
+use zvariant::{Fd, ObjectPath, OwnedObjectPath, OwnedValue, Value};
+
+use crate::Error;
+
+/// The unique name the mocked portal uses as the sender of its messages.
+const PORTAL_UNIQUE_NAME: &str = ":1.0";
+/// The unique name assigned to the connection returned by
+/// [`MockPortal::connect`].
+const CLIENT_UNIQUE_NAME: &str = ":1.1";
+
+type Options = HashMap<String, OwnedValue>;
+type ReplyFn = dyn Fn(&Message) -> zbus::Result<Message> + Send + Sync;
+
+/// A scripted answer to a method call received by the [`MockPortal`].
+#[derive(Clone)]
+pub enum MockResponse {
+    /// Emit a successful `Response` on the request object with the given
+    /// results.
+    Success(HashMap<String, OwnedValue>),
+    /// Emit a `Response` on the request object telling the user cancelled the
+    /// interaction.
+    Cancelled,
+    /// Emit a `Response` on the request object telling the interaction was
+    /// ended in some other way.
+    Other,
+    /// Reply with the session object path built from the `session_handle_token`
+    /// option, like `org.freedesktop.portal.Location.CreateSession` does.
+    Session,
+    /// Reply with a DBus error, composed of its name and message.
+    Error(String, String),
+    /// Reply with a custom body.
+    Reply(Arc<ReplyFn>),
+}
+
+impl MockResponse {
+    /// A successful response without any results.
+    pub fn success() -> Self {
+        Self::Success(HashMap::new())
+    }
+
+    /// Adds a `key = value` to the results of a [`MockResponse::Success`].
+    pub fn result<V: Into<Value<'static>>>(mut self, key: &str, value: V) -> Self {
+        if let Self::Success(ref mut results) = self {
+            results.insert(key.to_string(), value.into().into());
+        }
+        self
+    }
+
+    /// Reply to the method call with `body`.
+    pub fn reply<B>(body: B) -> Self
+    where
+        B: Serialize + zvariant::Type + Send + Sync + 'static,
+    {
+        Self::Reply(Arc::new(move |call| {
+            Message::method_reply(Some(PORTAL_UNIQUE_NAME), call, &body)
+        }))
+    }
+
+    /// Reply to the method call with the body returned by `f`, which gets the
+    /// method call to look at its arguments or file descriptors.
+    pub fn reply_with<F, B>(f: F) -> Self
+    where
+        F: Fn(&Message) -> zbus::Result<B> + Send + Sync + 'static,
+        B: Serialize + zvariant::Type,
+    {
+        Self::Reply(Arc::new(move |call| {
+            Message::method_reply(Some(PORTAL_UNIQUE_NAME), call, &f(call)?)
+        }))
+    }
+}
+
+impl Debug for MockResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Success(results) => f.debug_tuple("Success").field(results).finish(),
+            Self::Cancelled => f.write_str("Cancelled"),
+            Self::Other => f.write_str("Other"),
+            Self::Session => f.write_str("Session"),
+            Self::Error(name, message) => {
+                f.debug_tuple("Error").field(name).field(message).finish()
+            }
+            Self::Reply(_) => f.write_str("Reply"),
+        }
+    }
+}
+
+/// A method call received by the [`MockPortal`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockCall {
+    interface: String,
+    member: String,
+    path: String,
+}
+
+impl MockCall {
+    /// The DBus interface the method belongs to.
+    pub fn interface(&self) -> &str {
+        &self.interface
+    }
+
+    /// The method name.
+    pub fn member(&self) -> &str {
+        &self.member
+    }
+
+    /// The object path the method was called on.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+#[derive(Default)]
+struct MockState {
+    responses: HashMap<(String, String), MockResponse>,
+    properties: HashMap<(String, String), OwnedValue>,
+    calls: Vec<MockCall>,
+    connection: Option<zbus::azync::Connection>,
+    ignore_handle_tokens: bool,
+    objects: u32,
+}
+
+/// An in-process replacement of `xdg-desktop-portal`.
+///
+/// The mock serves `org.freedesktop.portal.Desktop`,
+/// `org.freedesktop.portal.Documents` and `org.freedesktop.portal.Flatpak` on
+/// a private peer-to-peer connection. It also answers the few
+/// `org.freedesktop.DBus` calls zbus makes, so the connection returned by
+/// [`MockPortal::connect`] can be passed to any proxy of the crate.
+///
+/// Request based methods (e.g.
+/// [`FileChooserProxy::open_file`](crate::desktop::file_chooser::FileChooserProxy::open_file))
+/// reply with the request object path and emit the scripted `Response` on it.
+/// Methods without a scripted response fail with
+/// `org.freedesktop.DBus.Error.UnknownMethod`.
+#[derive(Clone, Default)]
+pub struct MockPortal(Arc<Mutex<MockState>>);
+
+impl MockPortal {
+    /// Script the answer to every call of `interface.method`.
+    pub fn respond(&self, interface: &str, method: &str, response: MockResponse) -> &Self {
+        self.0
+            .lock()
+            .unwrap()
+            .responses
+            .insert((interface.to_string(), method.to_string()), response);
+        self
+    }
+
+    /// Sets the value returned when reading `interface.name`.
+    pub fn set_property<V: Into<Value<'static>>>(
+        &self,
+        interface: &str,
+        name: &str,
+        value: V,
+    ) -> &Self {
+        self.0.lock().unwrap().properties.insert(
+            (interface.to_string(), name.to_string()),
+            value.into().into(),
+        );
+        self
+    }
+
+    /// Behave like the portals older than 0.9, which ignore the
+    /// `handle_token` & `session_handle_token` options and create the request
+    /// & session objects at paths of their own.
+    pub fn ignore_handle_tokens(&self, ignore: bool) -> &Self {
+        self.0.lock().unwrap().ignore_handle_tokens = ignore;
+        self
+    }
+
+    /// The method calls received so far, in order.
+    pub fn calls(&self) -> Vec<MockCall> {
+        self.0.lock().unwrap().calls.clone()
+    }
+
+    /// Start serving the mocked portals on a new thread and return the
+    /// connection to use with the proxies.
+    pub async fn connect(&self) -> Result<zbus::azync::Connection, Error> {
+        let (server, client) = UnixStream::pair().map_err(zbus::Error::from)?;
+        let mock = self.clone();
+        std::thread::spawn(move || {
+            futures::executor::block_on(async move {
+                if let Err(err) = mock.serve(server).await {
+                    tracing::warn!("The mock portal stopped: {}", err);
+                }
+            })
+        });
+        let connection = zbus::azync::Connection::new_unix_client(client, true).await?;
+        Ok(connection)
+    }
+
+    /// Emit a signal from the mocked portal.
+    ///
+    /// **Note** The signal is only received if [`MockPortal::connect`] was
+    /// called before.
+    pub async fn emit_signal<B>(
+        &self,
+        path: &str,
+        interface: &str,
+        signal_name: &str,
+        body: &B,
+    ) -> Result<(), Error>
+    where
+        B: Serialize + zvariant::Type,
+    {
+        let connection = self.0.lock().unwrap().connection.clone();
+        let connection = connection.ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::NotConnected,
+                "MockPortal::connect wasn't called",
+            )
+        })?;
+        let message = Message::signal(
+            Some(PORTAL_UNIQUE_NAME),
+            Some(CLIENT_UNIQUE_NAME),
+            path,
+            interface,
+            signal_name,
+            body,
+        )?;
+        tracing::info!("Mock emitting signal '{}.{}'", interface, signal_name);
+        connection.send_message(message).await?;
+        Ok(())
+    }
+
+    async fn serve(&self, stream: UnixStream) -> Result<(), Error> {
+        let guid = zbus::Guid::generate();
+        let connection = zbus::azync::Connection::new_unix_server(stream, &guid).await?;
+        self.0.lock().unwrap().connection = Some(connection.clone());
+
+        let mut messages = connection.clone();
+        while let Some(message) = messages.next().await {
+            let message = message?;
+            if message.message_type() != zbus::MessageType::MethodCall {
+                continue;
+            }
+            // A malformed call gets an error reply, the mock keeps serving.
+            let replies = match self.handle_method_call(&message) {
+                Ok(replies) => replies,
+                Err(err) => {
+                    tracing::warn!("The mock portal failed to handle a call: {}", err);
+                    let name = match err {
+                        zbus::Error::Variant(_) => "org.freedesktop.DBus.Error.InvalidArgs",
+                        _ => "org.freedesktop.DBus.Error.Failed",
+                    };
+                    vec![Message::method_error(
+                        Some(PORTAL_UNIQUE_NAME),
+                        &message,
+                        name,
+                        &err.to_string(),
+                    )?]
+                }
+            };
+            for reply in replies {
+                connection.send_message(reply).await?;
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_method_call(&self, call: &Message) -> zbus::Result<Vec<Message>> {
+        let interface = call.interface().map(|i| i.to_string()).unwrap_or_default();
+        let member = call.member().map(|m| m.to_string()).unwrap_or_default();
+        let path = call.path().map(|p| p.to_string()).unwrap_or_default();
+        tracing::debug!("Mock received a call to '{}.{}'", interface, member);
+
+        match (interface.as_str(), member.as_str()) {
+            ("org.freedesktop.DBus", "Hello") => {
+                return Ok(vec![Message::method_reply(
+                    Some(PORTAL_UNIQUE_NAME),
+                    call,
+                    &CLIENT_UNIQUE_NAME,
+                )?]);
+            }
+            ("org.freedesktop.DBus", "GetNameOwner") => {
+                return Ok(vec![Message::method_reply(
+                    Some(PORTAL_UNIQUE_NAME),
+                    call,
+                    &PORTAL_UNIQUE_NAME,
+                )?]);
+            }
+            ("org.freedesktop.DBus", _) => {
+                return Ok(vec![Message::method_reply(
+                    Some(PORTAL_UNIQUE_NAME),
+                    call,
+                    &(),
+                )?]);
+            }
+            ("org.freedesktop.DBus.Properties", "Get") => {
+                let (property_interface, name) = call.body::<(String, String)>()?;
+                let value = self
+                    .0
+                    .lock()
+                    .unwrap()
+                    .properties
+                    .get(&(property_interface, name))
+                    .cloned();
+                return Ok(vec![match value {
+                    Some(value) => {
+                        Message::method_reply(Some(PORTAL_UNIQUE_NAME), call, &Value::from(value))?
+                    }
+                    None => unknown_method(call, "Unknown property")?,
+                }]);
+            }
+            _ => (),
+        }
+
+        let response = {
+            let mut state = self.0.lock().unwrap();
+            state.calls.push(MockCall {
+                interface: interface.clone(),
+                member: member.clone(),
+                path,
+            });
+            state.responses.get(&(interface, member)).cloned()
+        };
+
+        let options = call_options(call);
+        let response = match response {
+            Some(response) => response,
+            None => return Ok(vec![unknown_method(call, "No mocked response")?]),
+        };
+        let (code, mut results) = match response {
+            MockResponse::Reply(reply) => return Ok(vec![reply(call)?]),
+            MockResponse::Error(name, message) => {
+                return Ok(vec![Message::method_error(
+                    Some(PORTAL_UNIQUE_NAME),
+                    call,
+                    name.as_str(),
+                    &message,
+                )?])
+            }
+            MockResponse::Session => {
+                let path = self.object_path(&options, "session_handle_token", "session")?;
+                return Ok(vec![Message::method_reply(
+                    Some(PORTAL_UNIQUE_NAME),
+                    call,
+                    &path,
+                )?]);
+            }
+            MockResponse::Success(results) => (0u32, results),
+            MockResponse::Cancelled => (1u32, HashMap::new()),
+            MockResponse::Other => (2u32, HashMap::new()),
+        };
+
+        let request_path = self.object_path(&options, "handle_token", "request")?;
+        if code == 0 && options.contains_key("session_handle_token") {
+            let session_path = self.object_path(&options, "session_handle_token", "session")?;
+            results
+                .entry("session_handle".to_string())
+                .or_insert_with(|| Value::from(session_path.as_str().to_string()).into());
+        }
+        Ok(vec![
+            Message::method_reply(Some(PORTAL_UNIQUE_NAME), call, &request_path)?,
+            Message::signal(
+                Some(PORTAL_UNIQUE_NAME),
+                Some(CLIENT_UNIQUE_NAME),
+                request_path.as_str(),
+                "org.freedesktop.portal.Request",
+                "Response",
+                &(code, results),
+            )?,
+        ])
+    }
+
+    /// The path of a new request or session object, built from the token
+    /// stored in `options[key]` unless the handle tokens are ignored.
+    fn object_path(
+        &self,
+        options: &Options,
+        key: &str,
+        kind: &str,
+    ) -> zbus::Result<OwnedObjectPath> {
+        let mut state = self.0.lock().unwrap();
+        if state.ignore_handle_tokens {
+            state.objects += 1;
+            return object_path(kind, &format!("mock{}", state.objects));
+        }
+        let token = options
+            .get(key)
+            .and_then(|token| token.downcast_ref::<str>())
+            .unwrap_or_default();
+        object_path(kind, token)
+    }
+}
+
+impl Debug for MockPortal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MockPortal")
+            .field("calls", &self.calls())
+            .finish()
+    }
+}
+
+fn unknown_method(call: &Message, message: &str) -> zbus::Result<Message> {
+    Message::method_error(
+        Some(PORTAL_UNIQUE_NAME),
+        call,
+        "org.freedesktop.DBus.Error.UnknownMethod",
+        &message,
+    )
+}
+
+/// Build the `/org/freedesktop/portal/desktop/{kind}/SENDER/TOKEN` path from
+/// `token`.
+fn object_path(kind: &str, token: &str) -> zbus::Result<OwnedObjectPath> {
+    let sender = CLIENT_UNIQUE_NAME.trim_start_matches(':').replace('.', "_");
+    let path = ObjectPath::try_from(format!(
+        "/org/freedesktop/portal/desktop/{}/{}/{}",
+        kind, sender, token
+    ))?;
+    Ok(path.into())
+}
+
+/// The portals always pass the options vardict as the last argument, extract
+/// it from the shapes of method calls they use.
+fn call_options(call: &Message) -> Options {
+    let signature = match call.body_signature() {
+        Ok(signature) => signature.as_str().to_string(),
+        Err(_) => return Options::new(),
+    };
+    let options = match signature.as_str() {
+        "a{sv}" => call.body::<Options>(),
+        "sa{sv}" => call.body::<(String, Options)>().map(|b| b.1),
+        "oa{sv}" => call.body::<(OwnedObjectPath, Options)>().map(|b| b.1),
+        "ssa{sv}" => call.body::<(String, String, Options)>().map(|b| b.2),
+        "osa{sv}" => call
+            .body::<(OwnedObjectPath, String, Options)>()
+            .map(|b| b.2),
+        "sua{sv}" => call.body::<(String, u32, Options)>().map(|b| b.2),
+        "sha{sv}" => call.body::<(String, Fd, Options)>().map(|b| b.2),
+        "uasa{sv}" => call.body::<(u32, Vec<String>, Options)>().map(|b| b.2),
+        "ssha{sv}" => call.body::<(String, String, Fd, Options)>().map(|b| b.3),
+        "ssa{sv}a{sv}a{sv}" => call
+            .body::<(String, String, Options, Options, Options)>()
+            .map(|b| b.4),
+        _ => return Options::new(),
+    };
+    options.unwrap_or_default()
+}
@@ -0,0 +1,198 @@
+use std::{
+    fmt::{self, Debug},
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    desktop::{
+        account::AccountProxy, background::BackgroundProxy, camera::CameraProxy,
+        device::DeviceProxy, email::EmailProxy, file_chooser::FileChooserProxy,
+        game_mode::GameModeProxy, global_shortcuts::GlobalShortcutsProxy, inhibit::InhibitProxy,
+        location::LocationProxy, memory_monitor::MemoryMonitorProxy,
+        network_monitor::NetworkMonitorProxy, notification::NotificationProxy,
+        open_uri::OpenURIProxy, print::PrintProxy, proxy_resolver::ProxyResolverProxy,
+        realtime::RealtimeProxy, remote_desktop::RemoteDesktopProxy, screencast::ScreenCastProxy,
+        screenshot::ScreenshotProxy, secret::SecretProxy, settings::SettingsProxy,
+        trash::TrashProxy, wallpaper::WallpaperProxy,
+    },
+    documents::{DocumentsProxy, FileTransferProxy, PermissionStoreProxy},
+    flatpak::{development::DevelopmentProxy, FlatpakProxy},
+    Error,
+};
+
+/// The entry point to the portals, sharing a single connection & a single
+/// instance of each proxy.
+///
+/// The session bus connection is opened on first use, use
+/// [`Portal::with_connection`] to talk to the portals on another connection,
+/// for example the one returned by `MockPortal::connect`. Cloning a
+/// [`Portal`] is cheap, the clones share the connection & the proxies.
+///
+/// The shared proxies can't be reconfigured, the settings of a single call,
+/// like its timeout or its [`AbortToken`](crate::desktop::AbortToken), are set
+/// on the returned [`Request`](crate::desktop::Request) instead.
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+///
+/// use ashpd::desktop::file_chooser::OpenFileOptions;
+/// use ashpd::{Portal, WindowIdentifier};
+///
+/// async fn run() -> ashpd::Result<()> {
+///     let portal = Portal::new();
+///
+///     let files = portal
+///         .file_chooser()
+///         .await?
+///         .open_file(
+///             &WindowIdentifier::default(),
+///             "open a file to read",
+///             OpenFileOptions::default(),
+///         )
+///         .timeout(Some(Duration::from_secs(60)))
+///         .await?;
+///     let color = portal
+///         .screenshot()
+///         .await?
+///         .pick_color(&WindowIdentifier::default())
+///         .await?;
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct Portal(Arc<PortalInner>);
+
+#[derive(Default)]
+struct PortalInner {
+    connection: futures::lock::Mutex<Option<zbus::azync::Connection>>,
+    proxies: Mutex<Proxies>,
+}
+
+impl Portal {
+    /// Create a new [`Portal`] using the session bus.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new [`Portal`] using `connection`, for example a connection
+    /// to a private bus or a peer-to-peer connection.
+    pub fn with_connection(connection: zbus::azync::Connection) -> Self {
+        let inner = PortalInner {
+            connection: futures::lock::Mutex::new(Some(connection)),
+            proxies: Default::default(),
+        };
+        Self(Arc::new(inner))
+    }
+
+    /// The connection used by the proxies, the session bus is connected to
+    /// the first time it is called unless a connection was provided with
+    /// [`Portal::with_connection`].
+    pub async fn connection(&self) -> Result<zbus::azync::Connection, Error> {
+        let mut connection = self.0.connection.lock().await;
+        if let Some(ref connection) = *connection {
+            return Ok(connection.clone());
+        }
+        tracing::info!("Connecting to the session bus");
+        let session = zbus::azync::Connection::session().await?;
+        *connection = Some(session.clone());
+        Ok(session)
+    }
+
+    /// The proxy stored in the `slot` of the cache, created with `new` unless
+    /// it is already there.
+    async fn proxy<P, F, Fut>(
+        &self,
+        slot: fn(&mut Proxies) -> &mut Option<Arc<P>>,
+        new: F,
+    ) -> Result<Arc<P>, Error>
+    where
+        F: FnOnce(zbus::azync::Connection) -> Fut,
+        Fut: Future<Output = Result<P, Error>>,
+    {
+        if let Some(proxy) = slot(&mut self.0.proxies.lock().unwrap()) {
+            return Ok(proxy.clone());
+        }
+
+        let proxy = Arc::new(new(self.connection().await?).await?);
+        // Another task may have created the proxy in the meantime, keep a
+        // single instance.
+        let proxy = slot(&mut self.0.proxies.lock().unwrap())
+            .get_or_insert(proxy)
+            .clone();
+        Ok(proxy)
+    }
+}
+
+impl Debug for Portal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Portal")
+            .field("proxies", &self.0.proxies.lock().unwrap().count())
+            .finish()
+    }
+}
+
+macro_rules! portal_proxies {
+    ($($method:ident => $proxy:ident;)*) => {
+        /// The proxies created so far, one slot per proxy type.
+        #[derive(Default)]
+        struct Proxies {
+            $($method: Option<Arc<$proxy<'static>>>,)*
+        }
+
+        impl Proxies {
+            fn count(&self) -> usize {
+                [$(self.$method.is_some()),*]
+                    .iter()
+                    .filter(|created| **created)
+                    .count()
+            }
+        }
+
+        impl Portal {
+            $(
+                #[doc = concat!("The shared [`", stringify!($proxy), "`].")]
+                pub async fn $method(&self) -> Result<Arc<$proxy<'static>>, Error> {
+                    self.proxy(
+                        |proxies| &mut proxies.$method,
+                        |connection| async move { $proxy::new(&connection).await },
+                    )
+                    .await
+                }
+            )*
+        }
+    };
+}
+
+portal_proxies! {
+    account => AccountProxy;
+    background => BackgroundProxy;
+    camera => CameraProxy;
+    device => DeviceProxy;
+    email => EmailProxy;
+    file_chooser => FileChooserProxy;
+    game_mode => GameModeProxy;
+    global_shortcuts => GlobalShortcutsProxy;
+    inhibit => InhibitProxy;
+    location => LocationProxy;
+    memory_monitor => MemoryMonitorProxy;
+    network_monitor => NetworkMonitorProxy;
+    notification => NotificationProxy;
+    open_uri => OpenURIProxy;
+    print => PrintProxy;
+    proxy_resolver => ProxyResolverProxy;
+    realtime => RealtimeProxy;
+    remote_desktop => RemoteDesktopProxy;
+    screencast => ScreenCastProxy;
+    screenshot => ScreenshotProxy;
+    secret => SecretProxy;
+    settings => SettingsProxy;
+    trash => TrashProxy;
+    wallpaper => WallpaperProxy;
+    documents => DocumentsProxy;
+    file_transfer => FileTransferProxy;
+    permission_store => PermissionStoreProxy;
+    flatpak => FlatpakProxy;
+    development => DevelopmentProxy;
+}
@@ -0,0 +1,99 @@
+use std::convert::TryFrom;
+
+use zbus::xml::Node;
+use zvariant::OwnedValue;
+
+use crate::{
+    desktop::{DESTINATION, PATH},
+    helpers::call_method,
+    Error,
+};
+
+/// An interface implemented by the running `xdg-desktop-portal`, as returned
+/// by [`available_portals`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortalInfo {
+    interface: String,
+    version: Option<u32>,
+}
+
+impl PortalInfo {
+    /// The name of the interface, e.g. `org.freedesktop.portal.ScreenCast`.
+    pub fn interface(&self) -> &str {
+        &self.interface
+    }
+
+    /// The version of the interface, `None` if it couldn't be read.
+    pub fn version(&self) -> Option<u32> {
+        self.version
+    }
+}
+
+/// List the portal interfaces exported on `/org/freedesktop/portal/desktop`
+/// along with their version.
+///
+/// Calling a method of a portal missing from the list fails, as does using an
+/// option requiring a newer version.
+///
+/// ```rust,no_run
+/// async fn run() -> ashpd::Result<()> {
+///     let connection = zbus::azync::Connection::session().await?;
+///
+///     let portals = ashpd::available_portals(&connection).await?;
+///     let screencast = portals
+///         .iter()
+///         .find(|portal| portal.interface() == "org.freedesktop.portal.ScreenCast");
+///     match screencast {
+///         Some(portal) => println!("ScreenCast version {:?}", portal.version()),
+///         None => println!("ScreenCast is not available"),
+///     }
+///     Ok(())
+/// }
+/// ```
+pub async fn available_portals(
+    connection: &zbus::azync::Connection,
+) -> Result<Vec<PortalInfo>, Error> {
+    let introspectable = zbus::azync::ProxyBuilder::new_bare(connection)
+        .interface("org.freedesktop.DBus.Introspectable")?
+        .path(PATH)?
+        .destination(DESTINATION)?
+        .build()
+        .await?;
+    let properties = zbus::azync::ProxyBuilder::new_bare(connection)
+        .interface("org.freedesktop.DBus.Properties")?
+        .path(PATH)?
+        .destination(DESTINATION)?
+        .build()
+        .await?;
+
+    let xml: String = call_method(&introspectable, "Introspect", &()).await?;
+    let node = xml.parse::<Node>()?;
+    let mut portals = Vec::new();
+    for interface in node.interfaces() {
+        let interface = interface.name();
+        if !interface.starts_with("org.freedesktop.portal.") {
+            continue;
+        }
+        // A portal failing to tell its version is still listed.
+        let version = match portal_version(&properties, interface).await {
+            Ok(version) => Some(version),
+            Err(err) => {
+                tracing::warn!("Failed to read the version of {}: {}", interface, err);
+                None
+            }
+        };
+        portals.push(PortalInfo {
+            interface: interface.to_string(),
+            version,
+        });
+    }
+    Ok(portals)
+}
+
+async fn portal_version(
+    properties: &zbus::azync::Proxy<'_>,
+    interface: &str,
+) -> Result<u32, Error> {
+    let value: OwnedValue = call_method(properties, "Get", &(interface, "version")).await?;
+    Ok(u32::try_from(value)?)
+}
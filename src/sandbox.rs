@@ -0,0 +1,480 @@
+//! # Examples
+//!
+//! Adapt the UI to the permissions granted to a Flatpak application.
+//!
+//! ```rust,no_run
+//! use ashpd::sandbox::{self, FlatpakInfo, Sandbox, Shared};
+//!
+//! fn run() -> ashpd::Result<()> {
+//!     if sandbox::detect() == Some(Sandbox::Flatpak) {
+//!         let info = FlatpakInfo::load()?;
+//!         println!("Running {} on {:?}", info.app_id, info.runtime);
+//!         if !info.shared.contains(&Shared::Network) {
+//!             println!("Offline mode");
+//!         }
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use std::{collections::HashMap, io, path::Path, str::FromStr};
+
+use strum_macros::EnumString;
+
+use crate::Error;
+
+/// The kind of sandbox, or container, the application runs in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Sandbox {
+    /// A Flatpak, see [`FlatpakInfo`].
+    Flatpak,
+    /// A Snap.
+    Snap,
+    /// A generic container, with the name of its manager if known, such as
+    /// `docker` or `podman`.
+    ///
+    /// Containers don't restrict the access to the user session, the portals
+    /// aren't needed.
+    Container(Option<String>),
+}
+
+/// Detect the sandbox, or container, the application runs in, if any.
+pub fn detect() -> Option<Sandbox> {
+    if Path::new("/.flatpak-info").exists() {
+        return Some(Sandbox::Flatpak);
+    }
+    if std::env::var_os("SNAP").is_some() || in_snap_cgroup() {
+        return Some(Sandbox::Snap);
+    }
+    if let Some(manager) = std::env::var_os("container") {
+        return Some(Sandbox::Container(Some(
+            manager.to_string_lossy().into_owned(),
+        )));
+    }
+    if Path::new("/run/.containerenv").exists() {
+        return Some(Sandbox::Container(Some("podman".to_owned())));
+    }
+    if Path::new("/.dockerenv").exists() {
+        return Some(Sandbox::Container(Some("docker".to_owned())));
+    }
+    None
+}
+
+/// Whether the process is in the cgroup of a Snap, named `snap.<name>.<app>`.
+fn in_snap_cgroup() -> bool {
+    std::fs::read_to_string("/proc/self/cgroup")
+        .map(|cgroups| {
+            cgroups
+                .lines()
+                .filter_map(|line| line.rsplit(':').next())
+                .any(|path| path.split('/').any(|name| name.starts_with("snap.")))
+        })
+        .unwrap_or(false)
+}
+
+/// A resource shared with the host, the `shared` permission.
+#[derive(Debug, Clone, PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum Shared {
+    /// The network.
+    Network,
+    /// The IPC namespace.
+    Ipc,
+    /// A resource unknown to ashpd.
+    #[strum(default)]
+    Other(String),
+}
+
+/// A socket exposed to the sandbox, the `sockets` permission.
+#[derive(Debug, Clone, PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum Socket {
+    /// The X11 display server.
+    X11,
+    /// The Wayland compositor.
+    Wayland,
+    /// The X11 display server, only if Wayland isn't available.
+    FallbackX11,
+    /// The PulseAudio sound server.
+    #[strum(serialize = "pulseaudio")]
+    PulseAudio,
+    /// The whole session bus.
+    SessionBus,
+    /// The whole system bus.
+    SystemBus,
+    /// The SSH agent.
+    SshAuth,
+    /// The smart card daemon.
+    Pcsc,
+    /// The CUPS printing server.
+    Cups,
+    /// The GnuPG agent.
+    GpgAgent,
+    /// The Wayland socket inherited from the caller.
+    InheritWaylandSocket,
+    /// A socket unknown to ashpd.
+    #[strum(default)]
+    Other(String),
+}
+
+/// A device exposed to the sandbox, the `devices` permission.
+#[derive(Debug, Clone, PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum Device {
+    /// The GPU.
+    Dri,
+    /// The input devices.
+    Input,
+    /// The USB devices.
+    Usb,
+    /// The virtualization device.
+    Kvm,
+    /// The shared memory.
+    Shm,
+    /// Every device.
+    All,
+    /// A device unknown to ashpd.
+    #[strum(default)]
+    Other(String),
+}
+
+/// How a [`Filesystem`] is exposed to the sandbox.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilesystemMode {
+    /// For reading & writing.
+    ReadWrite,
+    /// Read-only.
+    ReadOnly,
+    /// For reading & writing, created if missing.
+    Create,
+}
+
+/// A location of the host exposed to the sandbox, the `filesystems`
+/// permission.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Filesystem {
+    /// The location, either a path or a name such as `home` or
+    /// `xdg-download`, with an optional sub path.
+    pub location: String,
+    /// The access mode.
+    pub mode: FilesystemMode,
+}
+
+impl FromStr for Filesystem {
+    type Err = std::convert::Infallible;
+
+    fn from_str(filesystem: &str) -> Result<Self, Self::Err> {
+        let (location, mode) = match filesystem.rsplit_once(':') {
+            Some((location, "ro")) => (location, FilesystemMode::ReadOnly),
+            Some((location, "rw")) => (location, FilesystemMode::ReadWrite),
+            Some((location, "create")) => (location, FilesystemMode::Create),
+            _ => (filesystem, FilesystemMode::ReadWrite),
+        };
+        Ok(Self {
+            location: location.to_owned(),
+            mode,
+        })
+    }
+}
+
+/// The access granted to a bus name, from the least to the most permissive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum BusPolicy {
+    /// The name is hidden.
+    None,
+    /// The name is visible.
+    See,
+    /// The name can be called.
+    Talk,
+    /// The name can be owned.
+    Own,
+}
+
+/// The metadata of a Flatpak application, read from `/.flatpak-info`.
+///
+/// The `!` prefixed, revoked, permissions are left out.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FlatpakInfo {
+    /// The application ID, or the runtime ID when a runtime is run directly.
+    pub app_id: String,
+    /// The ID of the running instance.
+    pub instance_id: Option<String>,
+    /// The runtime ref, such as `runtime/org.gnome.Platform/x86_64/41`.
+    pub runtime: Option<String>,
+    /// The branch of the application.
+    pub branch: Option<String>,
+    /// The architecture of the application.
+    pub arch: Option<String>,
+    /// The version of Flatpak running the application.
+    pub flatpak_version: Option<String>,
+    /// Whether the application runs with the development permissions.
+    pub devel: bool,
+    /// The IDs of the extensions of the application.
+    pub app_extensions: Vec<String>,
+    /// The IDs of the extensions of the runtime.
+    pub runtime_extensions: Vec<String>,
+    /// The resources shared with the host.
+    pub shared: Vec<Shared>,
+    /// The sockets exposed to the sandbox.
+    pub sockets: Vec<Socket>,
+    /// The devices exposed to the sandbox.
+    pub devices: Vec<Device>,
+    /// The locations of the host exposed to the sandbox.
+    pub filesystems: Vec<Filesystem>,
+    /// The home directory paths persisted in the application data.
+    pub persistent: Vec<String>,
+    /// The access to the names of the session bus.
+    pub session_bus_policy: HashMap<String, BusPolicy>,
+    /// The access to the names of the system bus.
+    pub system_bus_policy: HashMap<String, BusPolicy>,
+    /// The environment variables set for the application.
+    pub environment: HashMap<String, String>,
+}
+
+impl FlatpakInfo {
+    /// Read the metadata of the running application from `/.flatpak-info`.
+    pub fn load() -> Result<Self, Error> {
+        std::fs::read_to_string("/.flatpak-info")?.parse()
+    }
+}
+
+impl FromStr for FlatpakInfo {
+    type Err = Error;
+
+    fn from_str(content: &str) -> Result<Self, Self::Err> {
+        let keyfile = parse_keyfile(content)?;
+        let group = |name: &str| keyfile.get(name);
+        let value = |group_name: &str, key: &str| {
+            group(group_name)
+                .and_then(|group| group.get(key))
+                .map(|value| unescape(value))
+        };
+        let list = |group_name: &str, key: &str| {
+            group(group_name)
+                .and_then(|group| group.get(key))
+                .map(|value| split_list(value))
+                .unwrap_or_default()
+        };
+        let permissions = |key: &str| {
+            list("Context", key)
+                .into_iter()
+                .filter(|permission| !permission.starts_with('!'))
+                .collect::<Vec<_>>()
+        };
+        let extensions = |key: &str| {
+            list("Instance", key)
+                .into_iter()
+                .map(|extension| match extension.split_once('=') {
+                    Some((id, _commit)) => id.to_owned(),
+                    None => extension,
+                })
+                .collect()
+        };
+        let policy = |group_name: &str| {
+            group(group_name)
+                .map(|group| {
+                    group
+                        .iter()
+                        .filter_map(|(name, policy)| Some((name.to_string(), policy.parse().ok()?)))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        let app_id = value("Application", "name")
+            .or_else(|| value("Runtime", "name"))
+            .ok_or_else(|| invalid_data("missing the application name"))?;
+        Ok(Self {
+            app_id,
+            instance_id: value("Instance", "instance-id"),
+            runtime: value("Application", "runtime"),
+            branch: value("Instance", "branch"),
+            arch: value("Instance", "arch"),
+            flatpak_version: value("Instance", "flatpak-version"),
+            devel: value("Instance", "devel").as_deref() == Some("true"),
+            app_extensions: extensions("app-extensions"),
+            runtime_extensions: extensions("runtime-extensions"),
+            shared: parse_all(permissions("shared")),
+            sockets: parse_all(permissions("sockets")),
+            devices: parse_all(permissions("devices")),
+            filesystems: parse_all(permissions("filesystems")),
+            persistent: permissions("persistent"),
+            session_bus_policy: policy("Session Bus Policy"),
+            system_bus_policy: policy("System Bus Policy"),
+            environment: group("Environment")
+                .map(|group| {
+                    group
+                        .iter()
+                        .map(|(key, value)| (key.to_string(), unescape(value)))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/// Parse the permissions, the unknown ones parse to the `Other` variants.
+fn parse_all<T: FromStr>(permissions: Vec<String>) -> Vec<T> {
+    permissions
+        .iter()
+        .filter_map(|permission| permission.parse().ok())
+        .collect()
+}
+
+fn invalid_data(message: &str) -> Error {
+    Error::IO(io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("Invalid /.flatpak-info: {}", message),
+    ))
+}
+
+/// The groups of a GLib key file, with their raw values.
+fn parse_keyfile(content: &str) -> Result<HashMap<&str, HashMap<&str, &str>>, Error> {
+    let mut groups = HashMap::<_, HashMap<_, _>>::new();
+    let mut current = None;
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            groups.entry(name).or_default();
+            current = Some(name);
+            continue;
+        }
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| invalid_data(&format!("malformed line '{}'", line)))?;
+        let group = current.ok_or_else(|| invalid_data("key outside of a group"))?;
+        groups
+            .entry(group)
+            .or_default()
+            .insert(key.trim_end(), value.trim_start());
+    }
+    Ok(groups)
+}
+
+/// Split a `;` separated list value, the separators can be escaped.
+fn split_list(value: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut item = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(escaped) = chars.next() {
+                    item.push('\\');
+                    item.push(escaped);
+                }
+            }
+            ';' => items.push(unescape(&std::mem::take(&mut item))),
+            c => item.push(c),
+        }
+    }
+    if !item.is_empty() {
+        items.push(unescape(&item));
+    }
+    items
+}
+
+/// Replace the escape sequences of a key file value.
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('s') => unescaped.push(' '),
+            Some('n') => unescaped.push('\n'),
+            Some('t') => unescaped.push('\t'),
+            Some('r') => unescaped.push('\r'),
+            Some(c) => unescaped.push(c),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const FLATPAK_INFO: &str = r#"
+[Application]
+name=org.gnome.Builder
+runtime=runtime/org.gnome.Sdk/x86_64/41
+
+[Instance]
+instance-id=3480135731
+branch=stable
+arch=x86_64
+flatpak-version=1.12.2
+app-extensions=org.gnome.Builder.Locale=a1b2;
+runtime-extensions=org.gnome.Sdk.Locale=c3d4;org.freedesktop.Platform.GL.default=e5f6;
+devel=true
+
+[Context]
+shared=network;ipc;
+sockets=x11;wayland;pulseaudio;!fallback-x11;
+devices=dri;all;
+filesystems=host;xdg-run/gvfsd;~/.local/share/fonts:ro;/var/lib/x\;y:create;
+
+[Session Bus Policy]
+org.freedesktop.Flatpak=talk
+org.gnome.Builder.*=own
+
+[Environment]
+RUST_LOG=debug\sinfo
+"#;
+
+    #[test]
+    fn flatpak_info() {
+        let info = FLATPAK_INFO.parse::<FlatpakInfo>().unwrap();
+        assert_eq!(info.app_id, "org.gnome.Builder");
+        assert_eq!(
+            info.runtime.as_deref(),
+            Some("runtime/org.gnome.Sdk/x86_64/41")
+        );
+        assert_eq!(info.instance_id.as_deref(), Some("3480135731"));
+        assert!(info.devel);
+        assert_eq!(info.app_extensions, vec!["org.gnome.Builder.Locale"]);
+        assert_eq!(info.runtime_extensions.len(), 2);
+        assert_eq!(info.shared, vec![Shared::Network, Shared::Ipc]);
+        assert_eq!(
+            info.sockets,
+            vec![Socket::X11, Socket::Wayland, Socket::PulseAudio]
+        );
+        assert_eq!(info.devices, vec![Device::Dri, Device::All]);
+        assert_eq!(
+            info.filesystems[2],
+            Filesystem {
+                location: "~/.local/share/fonts".to_owned(),
+                mode: FilesystemMode::ReadOnly,
+            }
+        );
+        assert_eq!(info.filesystems[3].location, "/var/lib/x;y");
+        assert_eq!(info.filesystems[3].mode, FilesystemMode::Create);
+        assert_eq!(
+            info.session_bus_policy.get("org.freedesktop.Flatpak"),
+            Some(&BusPolicy::Talk)
+        );
+        assert!(info.session_bus_policy["org.gnome.Builder.*"] > BusPolicy::Talk);
+        assert_eq!(info.environment["RUST_LOG"], "debug info");
+    }
+
+    #[test]
+    fn flatpak_info_malformed() {
+        assert!("[Context]\nshared=network;".parse::<FlatpakInfo>().is_err());
+        assert!("name=org.gnome.Builder".parse::<FlatpakInfo>().is_err());
+        assert_eq!(
+            "[Runtime]\nname=org.gnome.Sdk"
+                .parse::<FlatpakInfo>()
+                .unwrap()
+                .app_id,
+            "org.gnome.Sdk"
+        );
+    }
+}
@@ -0,0 +1,370 @@
+use std::{
+    convert::TryFrom,
+    os::unix::net::UnixStream,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use ashpd::{
+    backend::{
+        file_chooser::FileChooserImpl, screencast::ScreenCastImpl, Options, Response, Server,
+        ServerBuilder,
+    },
+    desktop::{
+        file_chooser::{OpenFileOptions, SaveFileOptions, SaveFilesOptions, SelectedFiles},
+        screencast::{
+            CursorMode, SelectSourcesOptions, SourceType, StartCastOptions, Stream, Streams,
+        },
+        ResponseError,
+    },
+    Error,
+};
+use enumflags2::BitFlags;
+use futures::{executor::block_on, future::BoxFuture};
+use serde::{de::DeserializeOwned, Serialize};
+use zvariant::{OwnedObjectPath, Value};
+
+const PATH: &str = "/org/freedesktop/portal/desktop";
+const FILE_CHOOSER: &str = "org.freedesktop.impl.portal.FileChooser";
+const SCREENCAST: &str = "org.freedesktop.impl.portal.ScreenCast";
+
+/// Serve the backend on a new thread and return the peer connection to call
+/// it with.
+fn connect<F>(build: F) -> zbus::azync::Connection
+where
+    F: FnOnce(ServerBuilder) -> ServerBuilder + Send + 'static,
+{
+    let (server, client) = UnixStream::pair().unwrap();
+    std::thread::spawn(move || {
+        block_on(async move {
+            let guid = zbus::Guid::generate();
+            let connection = zbus::azync::Connection::new_unix_server(server, &guid).await?;
+            build(Server::builder(&connection)).build().run().await
+        })
+    });
+    block_on(zbus::azync::Connection::new_unix_client(client, false)).unwrap()
+}
+
+async fn call<B, R>(
+    connection: &zbus::azync::Connection,
+    interface: &str,
+    method: &str,
+    body: &B,
+) -> Result<R, Error>
+where
+    B: Serialize + zvariant::Type,
+    R: DeserializeOwned + zvariant::Type,
+{
+    let reply = connection
+        .call_method(None::<&str>, PATH, Some(interface), method, body)
+        .await?;
+    Ok(reply.body::<R>()?)
+}
+
+fn handle(path: &str) -> OwnedObjectPath {
+    OwnedObjectPath::try_from(path).unwrap()
+}
+
+#[derive(Default)]
+struct FileChooser {
+    /// Set once the `SaveFile` dialog is shown, it is never answered.
+    save_file_shown: Arc<AtomicBool>,
+}
+
+impl FileChooserImpl for FileChooser {
+    fn open_file(
+        &self,
+        _app_id: String,
+        _parent_window: String,
+        _title: String,
+        options: OpenFileOptions,
+    ) -> BoxFuture<'_, Response<SelectedFiles>> {
+        let mut files = SelectedFiles::default().uri("file:///tmp/a.txt");
+        if options.get_multiple() == Some(true) {
+            files = files.uri("file:///tmp/b.txt");
+        }
+        Box::pin(async move { Response::Ok(files) })
+    }
+
+    fn save_file(
+        &self,
+        _app_id: String,
+        _parent_window: String,
+        _title: String,
+        _options: SaveFileOptions,
+    ) -> BoxFuture<'_, Response<SelectedFiles>> {
+        let shown = self.save_file_shown.clone();
+        Box::pin(async move {
+            shown.store(true, Ordering::SeqCst);
+            futures::future::pending().await
+        })
+    }
+
+    fn save_files(
+        &self,
+        _app_id: String,
+        _parent_window: String,
+        _title: String,
+        _options: SaveFilesOptions,
+    ) -> BoxFuture<'_, Response<SelectedFiles>> {
+        Box::pin(async { Response::Err(ResponseError::Cancelled) })
+    }
+}
+
+#[test]
+fn file_chooser() {
+    let connection = connect(|builder| builder.file_chooser(FileChooser::default()));
+
+    block_on(async {
+        let mut options = Options::new();
+        options.insert("multiple".to_string(), Value::from(true).into());
+        let (response, files) = call::<_, (u32, SelectedFiles)>(
+            &connection,
+            FILE_CHOOSER,
+            "OpenFile",
+            &(
+                handle("/org/freedesktop/portal/desktop/request/1_1/open"),
+                "org.example.App",
+                "",
+                "Open",
+                options,
+            ),
+        )
+        .await?;
+        assert_eq!(response, 0);
+        assert_eq!(files.uris(), &["file:///tmp/a.txt", "file:///tmp/b.txt"]);
+
+        let (response, _) = call::<_, (u32, Options)>(
+            &connection,
+            FILE_CHOOSER,
+            "SaveFiles",
+            &(
+                handle("/org/freedesktop/portal/desktop/request/1_1/save"),
+                "org.example.App",
+                "",
+                "Save",
+                Options::new(),
+            ),
+        )
+        .await?;
+        assert_eq!(response, 1);
+        Ok::<_, Error>(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn invalid_call() {
+    let connection = connect(|builder| builder.file_chooser(FileChooser::default()));
+
+    block_on(async {
+        let result = connection
+            .call_method(None::<&str>, PATH, Some(FILE_CHOOSER), "OpenFile", &42u32)
+            .await;
+        assert!(matches!(result, Err(zbus::Error::MethodError(_, _, _))));
+
+        // The interfaces that aren't implemented are unknown.
+        let result = connection
+            .call_method(
+                None::<&str>,
+                PATH,
+                Some("org.freedesktop.impl.portal.Screenshot"),
+                "Screenshot",
+                &(),
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(zbus::Error::MethodError(ref name, _, _))
+                if name.as_str() == "org.freedesktop.DBus.Error.UnknownInterface"
+        ));
+
+        // The server keeps serving after a malformed call.
+        let (response, _) = call::<_, (u32, SelectedFiles)>(
+            &connection,
+            FILE_CHOOSER,
+            "OpenFile",
+            &(
+                handle("/org/freedesktop/portal/desktop/request/1_1/open"),
+                "org.example.App",
+                "",
+                "Open",
+                Options::new(),
+            ),
+        )
+        .await?;
+        assert_eq!(response, 0);
+        Ok::<_, Error>(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn request_closed() {
+    let shown = Arc::new(AtomicBool::default());
+    let file_chooser = FileChooser {
+        save_file_shown: shown.clone(),
+    };
+    let connection = connect(move |builder| builder.file_chooser(file_chooser));
+
+    block_on(async {
+        let request = handle("/org/freedesktop/portal/desktop/request/1_1/pending");
+        let save = call::<_, (u32, Options)>(
+            &connection,
+            FILE_CHOOSER,
+            "SaveFile",
+            &(&request, "org.example.App", "", "Save", Options::new()),
+        );
+        let close = async {
+            while !shown.load(Ordering::SeqCst) {
+                async_io::Timer::after(Duration::from_millis(10)).await;
+            }
+            connection
+                .call_method(
+                    None::<&str>,
+                    request.as_str(),
+                    Some("org.freedesktop.impl.portal.Request"),
+                    "Close",
+                    &(),
+                )
+                .await
+        };
+        let (save, close) = futures::join!(save, close);
+        close?;
+        // The pending call is answered as cancelled.
+        assert_eq!(save?.0, 1);
+        Ok::<_, Error>(())
+    })
+    .unwrap();
+}
+
+#[derive(Default)]
+struct ScreenCast {
+    closed: Arc<Mutex<Vec<OwnedObjectPath>>>,
+}
+
+impl ScreenCastImpl for ScreenCast {
+    fn available_source_types(&self) -> BitFlags<SourceType> {
+        SourceType::Monitor | SourceType::Window
+    }
+
+    fn available_cursor_modes(&self) -> BitFlags<CursorMode> {
+        CursorMode::Hidden.into()
+    }
+
+    fn create_session(
+        &self,
+        _session_handle: OwnedObjectPath,
+        _app_id: String,
+        _options: Options,
+    ) -> BoxFuture<'_, Response<Options>> {
+        Box::pin(async { Response::Ok(Options::new()) })
+    }
+
+    fn select_sources(
+        &self,
+        _session_handle: OwnedObjectPath,
+        _app_id: String,
+        options: SelectSourcesOptions,
+    ) -> BoxFuture<'_, Response<Options>> {
+        let response = if options.get_types() == Some(SourceType::Window.into()) {
+            Response::Ok(Options::new())
+        } else {
+            Response::Err(ResponseError::Cancelled)
+        };
+        Box::pin(async move { response })
+    }
+
+    fn start(
+        &self,
+        _session_handle: OwnedObjectPath,
+        _app_id: String,
+        _parent_window: String,
+        _options: StartCastOptions,
+    ) -> BoxFuture<'_, Response<Streams>> {
+        let stream = Stream::new(42, Some((0, 0)), Some((1920, 1080)));
+        Box::pin(async move { Response::Ok(Streams::new(vec![stream])) })
+    }
+
+    fn close_session(&self, session_handle: OwnedObjectPath) -> BoxFuture<'_, ()> {
+        self.closed.lock().unwrap().push(session_handle);
+        Box::pin(async {})
+    }
+}
+
+#[test]
+fn screencast_session() {
+    let closed = Arc::new(Mutex::new(Vec::new()));
+    let screencast = ScreenCast {
+        closed: closed.clone(),
+    };
+    let connection = connect(move |builder| builder.screencast(screencast));
+
+    block_on(async {
+        let session = handle("/org/freedesktop/portal/desktop/session/1_1/cast");
+        let (response, _) = call::<_, (u32, Options)>(
+            &connection,
+            SCREENCAST,
+            "CreateSession",
+            &(
+                handle("/org/freedesktop/portal/desktop/request/1_1/create"),
+                &session,
+                "org.example.App",
+                Options::new(),
+            ),
+        )
+        .await?;
+        assert_eq!(response, 0);
+
+        let mut options = Options::new();
+        options.insert(
+            "types".to_string(),
+            Value::U32(SourceType::Window as u32).into(),
+        );
+        let (response, _) = call::<_, (u32, Options)>(
+            &connection,
+            SCREENCAST,
+            "SelectSources",
+            &(
+                handle("/org/freedesktop/portal/desktop/request/1_1/select"),
+                &session,
+                "org.example.App",
+                options,
+            ),
+        )
+        .await?;
+        assert_eq!(response, 0);
+
+        let (response, streams) = call::<_, (u32, Streams)>(
+            &connection,
+            SCREENCAST,
+            "Start",
+            &(
+                handle("/org/freedesktop/portal/desktop/request/1_1/start"),
+                &session,
+                "org.example.App",
+                "",
+                Options::new(),
+            ),
+        )
+        .await?;
+        assert_eq!(response, 0);
+        assert_eq!(streams.streams()[0].pipe_wire_node_id(), 42);
+
+        connection
+            .call_method(
+                None::<&str>,
+                session.as_str(),
+                Some("org.freedesktop.impl.portal.Session"),
+                "Close",
+                &(),
+            )
+            .await?;
+        assert_eq!(closed.lock().unwrap().as_slice(), &[session]);
+        Ok::<_, Error>(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,102 @@
+use std::convert::TryFrom;
+
+use ashpd::{
+    blocking::desktop::{
+        file_chooser::{FileChooserProxy, OpenFileOptions},
+        inhibit::{InhibitFlags, InhibitProxy},
+        settings::SettingsProxy,
+    },
+    mock::{MockPortal, MockResponse},
+    WindowIdentifier,
+};
+use futures::executor::block_on;
+use zvariant::{ObjectPath, OwnedObjectPath, Value};
+
+#[test]
+fn file_chooser() {
+    let mock = MockPortal::default();
+    mock.respond(
+        "org.freedesktop.portal.FileChooser",
+        "OpenFile",
+        MockResponse::success().result("uris", Value::from(vec!["file:///tmp/a.txt"])),
+    );
+
+    let connection = zbus::Connection::from(block_on(mock.connect()).unwrap());
+    let proxy = FileChooserProxy::new(&connection).unwrap();
+    let files = proxy
+        .open_file(
+            &WindowIdentifier::default(),
+            "open",
+            OpenFileOptions::default(),
+        )
+        .unwrap();
+    assert_eq!(files.uris(), &["file:///tmp/a.txt"]);
+}
+
+#[test]
+fn settings_iter() {
+    let mock = MockPortal::default();
+
+    let connection = zbus::Connection::from(block_on(mock.connect()).unwrap());
+    let proxy = SettingsProxy::new(&connection).unwrap();
+    let mut changes = proxy.receive_setting_changed_iter().unwrap();
+
+    block_on(mock.emit_signal(
+        "/org/freedesktop/portal/desktop",
+        "org.freedesktop.portal.Settings",
+        "SettingChanged",
+        &(
+            "org.gnome.desktop.interface",
+            "clock-format",
+            Value::from("24h"),
+        ),
+    ))
+    .unwrap();
+
+    let setting = changes.next().unwrap();
+    assert_eq!(setting.key(), "clock-format");
+}
+
+#[test]
+fn inhibit_guard() {
+    let mock = MockPortal::default();
+    let path = ObjectPath::try_from("/org/freedesktop/portal/desktop/request/1_1/idle").unwrap();
+    mock.respond(
+        "org.freedesktop.portal.Inhibit",
+        "Inhibit",
+        MockResponse::reply(OwnedObjectPath::from(path)),
+    );
+
+    let connection = zbus::Connection::from(block_on(mock.connect()).unwrap());
+    let proxy = InhibitProxy::new(&connection).unwrap();
+    // Closing the request isn't mocked, the output is kept anyway.
+    let output = proxy
+        .inhibit_while(
+            &WindowIdentifier::default(),
+            InhibitFlags::Idle.into(),
+            "playing a video",
+            || 42,
+        )
+        .unwrap();
+    assert_eq!(output, 42);
+
+    mock.respond(
+        "org.freedesktop.portal.Request",
+        "Close",
+        MockResponse::reply(()),
+    );
+    let guard = proxy
+        .inhibit(
+            &WindowIdentifier::default(),
+            InhibitFlags::Idle.into(),
+            "playing a video",
+        )
+        .unwrap();
+    assert_eq!(
+        guard.path().as_str(),
+        "/org/freedesktop/portal/desktop/request/1_1/idle"
+    );
+    guard.release().unwrap();
+    let calls = mock.calls();
+    assert_eq!(calls.last().unwrap().member(), "Close");
+}
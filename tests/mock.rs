@@ -0,0 +1,988 @@
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    fs::File,
+    io::{Read, Write},
+    mem::ManuallyDrop,
+    os::unix::{
+        fs::FileTypeExt,
+        io::{AsRawFd, FromRawFd},
+        net::UnixStream,
+    },
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use ashpd::{
+    desktop::{
+        file_chooser::{FileChooserProxy, OpenFileOptions},
+        global_shortcuts::{GlobalShortcutsProxy, NewShortcut},
+        inhibit::{InhibitFlags, InhibitProxy, SessionEndEvent, SessionEndMonitor},
+        remote_desktop::{KeyState, Keysym, RemoteDesktopProxy, VirtualInput},
+        screencast::{CursorMode, ScreenCastProxy, SelectSourcesOptions, SourceType},
+        settings::SettingsProxy,
+        AbortToken, ResponseError,
+    },
+    flatpak::{
+        development::{DevelopmentProxy, HostCommandFlags},
+        Command, FlatpakProxy, SpawnFlags, SpawnOptions, SupportsFlags,
+    },
+    mock::{MockPortal, MockResponse},
+    Error, Portal, WindowIdentifier,
+};
+use futures::{executor::block_on, StreamExt};
+use zvariant::{Fd, ObjectPath, OwnedObjectPath, OwnedValue, Value};
+
+#[test]
+fn file_chooser_success() {
+    let mock = MockPortal::default();
+    mock.respond(
+        "org.freedesktop.portal.FileChooser",
+        "OpenFile",
+        MockResponse::success().result("uris", Value::from(vec!["file:///tmp/a.txt"])),
+    );
+
+    block_on(async {
+        let connection = mock.connect().await?;
+        let proxy = FileChooserProxy::new(&connection).await?;
+        let files = proxy
+            .open_file(
+                &WindowIdentifier::default(),
+                "open",
+                OpenFileOptions::default(),
+            )
+            .await?;
+        assert_eq!(files.uris(), &["file:///tmp/a.txt"]);
+        Ok::<_, Error>(())
+    })
+    .unwrap();
+
+    let calls = mock.calls();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].member(), "OpenFile");
+}
+
+#[test]
+fn portal_shared_proxies() {
+    let mock = MockPortal::default();
+    mock.respond(
+        "org.freedesktop.portal.FileChooser",
+        "OpenFile",
+        MockResponse::success().result("uris", Value::from(vec!["file:///tmp/a.txt"])),
+    );
+
+    block_on(async {
+        let portal = Portal::with_connection(mock.connect().await?);
+        let files = portal
+            .clone()
+            .file_chooser()
+            .await?
+            .open_file(
+                &WindowIdentifier::default(),
+                "open",
+                OpenFileOptions::default(),
+            )
+            // The settings of a call don't touch the shared proxy.
+            .timeout(Some(Duration::from_secs(5)))
+            .await?;
+        assert_eq!(files.uris(), &["file:///tmp/a.txt"]);
+        assert!(Arc::ptr_eq(
+            &portal.file_chooser().await?,
+            &portal.clone().file_chooser().await?
+        ));
+        Ok::<_, Error>(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn mock_invalid_call() {
+    let mock = MockPortal::default();
+    mock.set_property("org.freedesktop.portal.Settings", "version", 1u32);
+
+    block_on(async {
+        let connection = mock.connect().await?;
+        let result = connection
+            .call_method(
+                Some("org.freedesktop.portal.Desktop"),
+                "/org/freedesktop/portal/desktop",
+                Some("org.freedesktop.DBus.Properties"),
+                "Get",
+                &42u32,
+            )
+            .await;
+        assert!(result.is_err());
+
+        // The mock keeps serving after a malformed call.
+        let proxy = SettingsProxy::new(&connection).await?;
+        assert_eq!(proxy.version().await?, 1);
+        Ok::<_, Error>(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn file_chooser_cancelled() {
+    let mock = MockPortal::default();
+    mock.respond(
+        "org.freedesktop.portal.FileChooser",
+        "OpenFile",
+        MockResponse::Cancelled,
+    );
+
+    let result = block_on(async {
+        let connection = mock.connect().await?;
+        let proxy = FileChooserProxy::new(&connection).await?;
+        proxy
+            .open_file(
+                &WindowIdentifier::default(),
+                "open",
+                OpenFileOptions::default(),
+            )
+            .await
+    });
+    assert!(matches!(
+        result,
+        Err(Error::Response(ResponseError::Cancelled))
+    ));
+}
+
+#[test]
+fn legacy_request_path() {
+    let mock = MockPortal::default();
+    mock.ignore_handle_tokens(true)
+        .respond(
+            "org.freedesktop.portal.FileChooser",
+            "OpenFile",
+            MockResponse::success().result("uris", Value::from(vec!["file:///tmp/a.txt"])),
+        )
+        .respond(
+            "org.freedesktop.portal.ScreenCast",
+            "CreateSession",
+            MockResponse::success(),
+        );
+
+    block_on(async {
+        let connection = mock.connect().await?;
+        // The response is emitted right after the reply, before the actual
+        // path of the request is known.
+        let proxy = FileChooserProxy::new(&connection).await?;
+        let files = proxy
+            .open_file(
+                &WindowIdentifier::default(),
+                "open",
+                OpenFileOptions::default(),
+            )
+            .await?;
+        assert_eq!(files.uris(), &["file:///tmp/a.txt"]);
+
+        let proxy = ScreenCastProxy::new(&connection).await?;
+        let session = proxy.create_session().await?;
+        assert_eq!(
+            session.inner().path().as_str(),
+            "/org/freedesktop/portal/desktop/session/1_1/mock3"
+        );
+        Ok::<_, Error>(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn request_timeout() {
+    let mock = MockPortal::default();
+    let path = ObjectPath::try_from("/org/freedesktop/portal/desktop/request/1_1/pending").unwrap();
+    mock.respond(
+        "org.freedesktop.portal.FileChooser",
+        "OpenFile",
+        MockResponse::reply(OwnedObjectPath::from(path)),
+    )
+    .respond(
+        "org.freedesktop.portal.Request",
+        "Close",
+        MockResponse::reply(()),
+    );
+
+    let result = block_on(async {
+        let connection = mock.connect().await?;
+        let mut proxy = FileChooserProxy::new(&connection).await?;
+        proxy.set_timeout(Some(Duration::from_secs(60)));
+        // The timeout of the request overrides the default one.
+        proxy
+            .open_file(
+                &WindowIdentifier::default(),
+                "open",
+                OpenFileOptions::default(),
+            )
+            .timeout(Some(Duration::from_millis(100)))
+            .await
+    });
+    assert!(matches!(result, Err(Error::Timeout)));
+
+    let calls = mock.calls();
+    let close = calls.last().unwrap();
+    assert_eq!(close.member(), "Close");
+    assert_eq!(
+        close.path(),
+        "/org/freedesktop/portal/desktop/request/1_1/pending"
+    );
+}
+
+#[test]
+fn request_aborted() {
+    let mock = MockPortal::default();
+    let path = ObjectPath::try_from("/org/freedesktop/portal/desktop/request/1_1/pending").unwrap();
+    mock.respond(
+        "org.freedesktop.portal.FileChooser",
+        "OpenFile",
+        MockResponse::reply(OwnedObjectPath::from(path)),
+    )
+    .respond(
+        "org.freedesktop.portal.Request",
+        "Close",
+        MockResponse::reply(()),
+    );
+
+    let result = block_on(async {
+        let connection = mock.connect().await?;
+        let proxy = FileChooserProxy::new(&connection).await?;
+        let token = AbortToken::default();
+
+        let files = proxy
+            .open_file(
+                &WindowIdentifier::default(),
+                "open",
+                OpenFileOptions::default(),
+            )
+            .abort_token(&token);
+        let abort = async {
+            while mock.calls().iter().all(|call| call.member() != "OpenFile") {
+                async_io::Timer::after(Duration::from_millis(10)).await;
+            }
+            token.abort().await
+        };
+        let (files, aborted) = futures::join!(files, abort);
+        aborted?;
+        files.map(|_| ())
+    });
+    assert!(matches!(result, Err(Error::RequestClosed)));
+
+    let calls = mock.calls();
+    let close = calls.last().unwrap();
+    assert_eq!(close.member(), "Close");
+    assert_eq!(
+        close.path(),
+        "/org/freedesktop/portal/desktop/request/1_1/pending"
+    );
+}
+
+#[test]
+fn screencast_session() {
+    let mock = MockPortal::default();
+    mock.respond(
+        "org.freedesktop.portal.ScreenCast",
+        "CreateSession",
+        MockResponse::success(),
+    )
+    .respond(
+        "org.freedesktop.portal.ScreenCast",
+        "SelectSources",
+        MockResponse::success(),
+    )
+    .respond(
+        "org.freedesktop.portal.ScreenCast",
+        "Start",
+        MockResponse::Other,
+    )
+    .set_property(
+        "org.freedesktop.portal.ScreenCast",
+        "AvailableSourceTypes",
+        Value::U32(3),
+    );
+
+    block_on(async {
+        let connection = mock.connect().await?;
+        let proxy = ScreenCastProxy::new(&connection).await?;
+        assert_eq!(
+            proxy.available_source_types().await?,
+            SourceType::Monitor | SourceType::Window
+        );
+
+        let session = proxy.create_session().await?;
+        proxy
+            .select_sources(
+                &session,
+                SelectSourcesOptions::default()
+                    .cursor_mode(CursorMode::Hidden.into())
+                    .types(SourceType::Monitor.into())
+                    .multiple(false),
+            )
+            .await?;
+        let result = proxy.start(&session, &WindowIdentifier::default()).await;
+        assert!(matches!(
+            result,
+            Err(Error::Response(ResponseError::Other(_)))
+        ));
+        Ok::<_, Error>(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn session_closed_on_drop() {
+    let mock = MockPortal::default();
+    mock.respond(
+        "org.freedesktop.portal.ScreenCast",
+        "CreateSession",
+        MockResponse::success(),
+    )
+    .respond(
+        "org.freedesktop.portal.Session",
+        "Close",
+        MockResponse::reply(()),
+    );
+
+    let path = block_on(async {
+        let connection = mock.connect().await?;
+        let proxy = ScreenCastProxy::new(&connection).await?;
+        let session = proxy.create_session().await?;
+        let path = session.inner().path().to_string();
+        drop(session);
+        Ok::<_, Error>(path)
+    })
+    .unwrap();
+
+    // The session is closed in the background.
+    let closed = |mock: &MockPortal| {
+        mock.calls()
+            .iter()
+            .any(|call| call.member() == "Close" && call.path() == path)
+    };
+    for _ in 0..50 {
+        if closed(&mock) {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    assert!(closed(&mock));
+}
+
+#[test]
+fn inhibit_release() {
+    let mock = MockPortal::default();
+    let path = ObjectPath::try_from("/org/freedesktop/portal/desktop/request/1_1/idle").unwrap();
+    mock.respond(
+        "org.freedesktop.portal.Inhibit",
+        "Inhibit",
+        MockResponse::reply(OwnedObjectPath::from(path)),
+    )
+    .respond(
+        "org.freedesktop.portal.Request",
+        "Close",
+        MockResponse::reply(()),
+    );
+
+    block_on(async {
+        let connection = mock.connect().await?;
+        let proxy = InhibitProxy::new(&connection).await?;
+        let output = proxy
+            .inhibit_while(
+                &WindowIdentifier::default(),
+                InhibitFlags::Idle.into(),
+                "playing a video",
+                async { 42 },
+            )
+            .await?;
+        assert_eq!(output, 42);
+        Ok::<_, Error>(())
+    })
+    .unwrap();
+
+    let calls = mock.calls();
+    let close = calls.last().unwrap();
+    assert_eq!(close.member(), "Close");
+    assert_eq!(
+        close.path(),
+        "/org/freedesktop/portal/desktop/request/1_1/idle"
+    );
+}
+
+#[test]
+fn session_end_monitor() {
+    let mock = MockPortal::default();
+    mock.respond(
+        "org.freedesktop.portal.Inhibit",
+        "CreateMonitor",
+        MockResponse::success(),
+    )
+    .respond(
+        "org.freedesktop.portal.Inhibit",
+        "QueryEndResponse",
+        MockResponse::reply(()),
+    )
+    .respond(
+        "org.freedesktop.portal.Session",
+        "Close",
+        MockResponse::reply(()),
+    );
+
+    block_on(async {
+        let connection = mock.connect().await?;
+        let monitor = SessionEndMonitor::new(&connection, &WindowIdentifier::default()).await?;
+        let session = OwnedObjectPath::from(monitor.session().inner().path().clone());
+        let events = monitor.receive_events().await?;
+        futures::pin_mut!(events);
+
+        for state in &[2u32, 3] {
+            let mut details = HashMap::new();
+            details.insert("screensaver-active", Value::from(false));
+            details.insert("session-state", Value::from(*state));
+            mock.emit_signal(
+                "/org/freedesktop/portal/desktop",
+                "org.freedesktop.portal.Inhibit",
+                "StateChanged",
+                &(&session, details),
+            )
+            .await?;
+        }
+
+        match events.next().await.unwrap() {
+            SessionEndEvent::QueryEnd(responder) => responder.respond().await?,
+            event => panic!("unexpected event {:?}", event),
+        }
+        assert!(matches!(
+            events.next().await.unwrap(),
+            SessionEndEvent::Ending
+        ));
+        Ok::<_, Error>(())
+    })
+    .unwrap();
+
+    assert!(mock
+        .calls()
+        .iter()
+        .any(|call| call.member() == "QueryEndResponse"));
+}
+
+#[test]
+fn query_end_deadline() {
+    let mock = MockPortal::default();
+    mock.respond(
+        "org.freedesktop.portal.Inhibit",
+        "CreateMonitor",
+        MockResponse::success(),
+    )
+    .respond(
+        "org.freedesktop.portal.Inhibit",
+        "QueryEndResponse",
+        MockResponse::reply(()),
+    )
+    .respond(
+        "org.freedesktop.portal.Session",
+        "Close",
+        MockResponse::reply(()),
+    );
+
+    block_on(async {
+        let connection = mock.connect().await?;
+        let monitor = SessionEndMonitor::new(&connection, &WindowIdentifier::default()).await?;
+        let session = OwnedObjectPath::from(monitor.session().inner().path().clone());
+        let events = monitor.receive_events().await?;
+        futures::pin_mut!(events);
+
+        let mut details = HashMap::new();
+        details.insert("screensaver-active", Value::from(false));
+        details.insert("session-state", Value::from(2u32));
+        let emitted_at = Instant::now();
+        mock.emit_signal(
+            "/org/freedesktop/portal/desktop",
+            "org.freedesktop.portal.Inhibit",
+            "StateChanged",
+            &(&session, details),
+        )
+        .await?;
+
+        // The deadline runs from the reception of the signal, not from the
+        // moment the event is polled.
+        async_io::Timer::after(Duration::from_millis(300)).await;
+        let responder = match events.next().await.unwrap() {
+            SessionEndEvent::QueryEnd(responder) => responder,
+            event => panic!("unexpected event {:?}", event),
+        };
+        assert!(responder.deadline() < emitted_at + Duration::from_millis(900));
+
+        // An unused responder responds by itself ahead of the portal's 1s
+        // deadline.
+        async_io::Timer::at(responder.deadline() + Duration::from_millis(50)).await;
+        let responses = |mock: &MockPortal| {
+            mock.calls()
+                .iter()
+                .filter(|call| call.member() == "QueryEndResponse")
+                .count()
+        };
+        assert_eq!(responses(&mock), 1);
+        assert!(Instant::now() < emitted_at + Duration::from_secs(1));
+
+        // Using it afterwards doesn't respond twice.
+        responder.respond().await?;
+        assert_eq!(responses(&mock), 1);
+        Ok::<_, Error>(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn spawned_process_wait() {
+    let mock = MockPortal::default();
+    mock.respond(
+        "org.freedesktop.portal.Flatpak",
+        "Spawn",
+        MockResponse::reply(42u32),
+    );
+
+    block_on(async {
+        let connection = mock.connect().await?;
+        let proxy = FlatpakProxy::new(&connection).await?;
+        let mut process = proxy
+            .spawn(
+                "/",
+                &["true"],
+                HashMap::new(),
+                HashMap::new(),
+                SpawnFlags::ClearEnv.into(),
+                SpawnOptions::default(),
+            )
+            .await?;
+        assert_eq!(process.pid(), 42);
+        assert_eq!(process.started().await?, None);
+
+        // Another process exiting is ignored.
+        for (pid, status) in &[(7u32, 0u32), (42, 1 << 8)] {
+            mock.emit_signal(
+                "/org/freedesktop/portal/Flatpak",
+                "org.freedesktop.portal.Flatpak",
+                "SpawnExited",
+                &(pid, status),
+            )
+            .await?;
+        }
+        let status = process.wait().await?;
+        assert_eq!(status.code(), Some(1));
+        Ok::<_, Error>(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn host_command_wait() {
+    let mock = MockPortal::default();
+    let envs = Arc::new(Mutex::new(HashMap::new()));
+    let received = envs.clone();
+    mock.respond(
+        "org.freedesktop.Flatpak.Development",
+        "HostCommand",
+        MockResponse::reply_with(move |call| {
+            let (_, _, _, envs, _) = call.body::<(
+                Vec<u8>,
+                Vec<Vec<u8>>,
+                HashMap<u32, Fd>,
+                HashMap<String, String>,
+                u32,
+            )>()?;
+            *received.lock().unwrap() = envs;
+            Ok(42u32)
+        }),
+    )
+    .respond(
+        "org.freedesktop.Flatpak.Development",
+        "HostCommandSignal",
+        MockResponse::reply(()),
+    );
+    std::env::set_var("ASHPD_TEST_FORWARDED", "caller");
+
+    block_on(async {
+        let connection = mock.connect().await?;
+        let proxy = DevelopmentProxy::new(&connection).await?;
+        let mut process = proxy
+            .host_command(
+                "/",
+                &["sleep", "60"],
+                HashMap::new(),
+                HashMap::new(),
+                HostCommandFlags::WatchBus.into(),
+            )
+            .await?;
+        assert_eq!(process.pid(), 42);
+        // The session helper doesn't emit a started signal.
+        assert_eq!(process.started().await?, None);
+        process.kill(false).await?;
+
+        // Another process exiting is ignored.
+        for (pid, status) in &[(7u32, 0u32), (42, 9)] {
+            mock.emit_signal(
+                "/org/freedesktop/Flatpak/Development",
+                "org.freedesktop.Flatpak.Development",
+                "HostCommandExited",
+                &(pid, status),
+            )
+            .await?;
+        }
+        let status = process.wait().await?;
+        assert_eq!(status.signal(), Some(9));
+        assert!(envs.lock().unwrap().is_empty());
+
+        let mut child = Command::new("env")
+            .forward_env()
+            .env("ASHPD_TEST_SET", "command")
+            .spawn_on_host(&proxy)
+            .await?;
+        assert_eq!(child.pid(), 42);
+        child.process().kill(true).await?;
+        Ok::<_, Error>(())
+    })
+    .unwrap();
+
+    let envs = envs.lock().unwrap();
+    assert_eq!(envs["ASHPD_TEST_FORWARDED"], "caller");
+    assert_eq!(envs["ASHPD_TEST_SET"], "command");
+    let calls = mock.calls();
+    let signals = calls
+        .iter()
+        .filter(|call| call.member() == "HostCommandSignal")
+        .collect::<Vec<_>>();
+    assert_eq!(signals.len(), 2);
+    assert_eq!(signals[0].path(), "/org/freedesktop/Flatpak/Development");
+}
+
+#[test]
+fn command_output() {
+    let mock = MockPortal::default();
+    let stdin_null = Arc::new(AtomicBool::default());
+    let spawned = stdin_null.clone();
+    mock.respond(
+        "org.freedesktop.portal.Flatpak",
+        "Spawn",
+        MockResponse::reply_with(move |call| {
+            let (_, _, fds, _, _, _) = call.body::<(
+                Vec<u8>,
+                Vec<Vec<u8>>,
+                HashMap<u32, Fd>,
+                HashMap<String, String>,
+                u32,
+                HashMap<String, OwnedValue>,
+            )>()?;
+            // The file descriptors are closed with the message.
+            let fd = |target: u32| {
+                ManuallyDrop::new(unsafe { File::from_raw_fd(fds[&target].as_raw_fd()) })
+            };
+            let stdin = fd(0).metadata()?;
+            spawned.store(stdin.file_type().is_char_device(), Ordering::SeqCst);
+            fd(1).write_all(b"hello")?;
+            fd(2).write_all(b"oops")?;
+            Ok(42u32)
+        }),
+    );
+
+    let (mut stderr, remote_stderr) = UnixStream::pair().unwrap();
+    let mut command = Command::new("sh");
+    command
+        .args(&["-c", "echo hello; echo oops >&2"])
+        .fd(2, remote_stderr);
+
+    block_on(async {
+        let connection = mock.connect().await?;
+        let proxy = FlatpakProxy::new(&connection).await?;
+        let output = command.output(&proxy);
+        let exit = async {
+            while mock.calls().iter().all(|call| call.member() != "Spawn") {
+                async_io::Timer::after(Duration::from_millis(10)).await;
+            }
+            mock.emit_signal(
+                "/org/freedesktop/portal/Flatpak",
+                "org.freedesktop.portal.Flatpak",
+                "SpawnExited",
+                &(42u32, 0u32),
+            )
+            .await
+        };
+        let (output, exited) = futures::join!(output, exit);
+        exited?;
+        let output = output?;
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"hello");
+        // The file descriptor 2 overrides the piped standard error.
+        assert!(output.stderr.is_empty());
+        Ok::<_, Error>(())
+    })
+    .unwrap();
+
+    // The standard input of `output()` is `/dev/null`.
+    assert!(stdin_null.load(Ordering::SeqCst));
+    drop(command);
+    let mut content = String::new();
+    stderr.read_to_string(&mut content).unwrap();
+    assert_eq!(content, "oops");
+}
+
+#[test]
+fn command_nul_byte() {
+    let mock = MockPortal::default();
+
+    let result = block_on(async {
+        let connection = mock.connect().await?;
+        let proxy = FlatpakProxy::new(&connection).await?;
+        Command::new("echo")
+            .arg("nul\0byte")
+            .spawn(&proxy)
+            .await
+            .map(|child| child.pid())
+    });
+    assert!(matches!(
+        result,
+        Err(Error::IO(ref err)) if err.kind() == std::io::ErrorKind::InvalidInput
+    ));
+    assert!(mock.calls().iter().all(|call| call.member() != "Spawn"));
+}
+
+#[test]
+fn spawn_requires_support() {
+    let mock = MockPortal::default();
+    mock.set_property("org.freedesktop.portal.Flatpak", "version", Value::U32(5))
+        .set_property("org.freedesktop.portal.Flatpak", "supports", Value::U32(0));
+
+    let result = block_on(async {
+        let connection = mock.connect().await?;
+        let proxy = FlatpakProxy::new(&connection).await?;
+        proxy
+            .spawn(
+                "/",
+                &["true"],
+                HashMap::new(),
+                HashMap::new(),
+                SpawnFlags::SharePids.into(),
+                SpawnOptions::default(),
+            )
+            .await
+            .map(|process| process.pid())
+    });
+    assert!(matches!(
+        result,
+        Err(Error::RequiresSupport(SupportsFlags::ExposePids))
+    ));
+}
+
+#[test]
+fn remote_desktop_eis_requires_version() {
+    let mock = MockPortal::default();
+    mock.respond(
+        "org.freedesktop.portal.RemoteDesktop",
+        "CreateSession",
+        MockResponse::success(),
+    )
+    .set_property("org.freedesktop.portal.RemoteDesktop", "version", 1u32);
+
+    block_on(async {
+        let connection = mock.connect().await?;
+        let proxy = RemoteDesktopProxy::new(&connection).await?;
+        let session = proxy.create_session().await?;
+        let result = proxy.connect_to_eis(&session).await;
+        assert!(matches!(
+            result,
+            Err(Error::RequiresVersion {
+                required: 2,
+                found: 1,
+                ..
+            })
+        ));
+        assert!(mock.calls().iter().all(|c| c.member() != "ConnectToEIS"));
+        Ok::<_, Error>(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn virtual_input_releases_on_error() {
+    let mock = MockPortal::default();
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let received = events.clone();
+    mock.respond(
+        "org.freedesktop.portal.RemoteDesktop",
+        "CreateSession",
+        MockResponse::success(),
+    )
+    .respond(
+        "org.freedesktop.portal.RemoteDesktop",
+        "NotifyKeyboardKeysym",
+        MockResponse::reply_with(move |call| {
+            let (_, _, keysym, state) =
+                call.body::<(OwnedObjectPath, HashMap<String, OwnedValue>, i32, u32)>()?;
+            received.lock().unwrap().push((keysym, state));
+            // Only Shift can be pressed.
+            if keysym != Keysym::SHIFT.raw() {
+                return Err(std::io::Error::new(std::io::ErrorKind::Other, "no such key").into());
+            }
+            Ok(())
+        }),
+    );
+
+    block_on(async {
+        let connection = mock.connect().await?;
+        let proxy = RemoteDesktopProxy::new(&connection).await?;
+        let session = proxy.create_session().await?;
+        let input = VirtualInput::new(&proxy, &session).delay(Duration::from_millis(0));
+
+        assert!(input.type_text("A").await.is_err());
+        assert!(input
+            .chord(&[Keysym::SHIFT, Keysym::from_char('a')])
+            .await
+            .is_err());
+        Ok::<_, Error>(())
+    })
+    .unwrap();
+
+    let shift = Keysym::SHIFT.raw();
+    let pressed = KeyState::Pressed as u32;
+    let released = KeyState::Released as u32;
+    // Shift is released after failing to press the second key.
+    assert_eq!(
+        events.lock().unwrap().as_slice(),
+        &[
+            (shift, pressed),
+            (Keysym::from_char('A').raw(), pressed),
+            (shift, released),
+            (shift, pressed),
+            (Keysym::from_char('a').raw(), pressed),
+            (shift, released),
+        ]
+    );
+}
+
+#[test]
+fn global_shortcuts() {
+    let shortcut = |id: &str, trigger: Option<&str>| {
+        let mut info = HashMap::new();
+        info.insert(
+            "description".to_string(),
+            Value::from(format!("{} the tab", id)),
+        );
+        if let Some(trigger) = trigger {
+            info.insert(
+                "trigger_description".to_string(),
+                Value::from(trigger.to_string()),
+            );
+        }
+        (id.to_string(), info)
+    };
+    let mock = MockPortal::default();
+    mock.respond(
+        "org.freedesktop.portal.GlobalShortcuts",
+        "CreateSession",
+        MockResponse::success(),
+    )
+    .respond(
+        "org.freedesktop.portal.GlobalShortcuts",
+        "BindShortcuts",
+        MockResponse::success().result(
+            "shortcuts",
+            vec![shortcut("close", Some("Ctrl+W")), shortcut("reload", None)],
+        ),
+    )
+    .respond(
+        "org.freedesktop.portal.GlobalShortcuts",
+        "ListShortcuts",
+        MockResponse::success().result("shortcuts", vec![shortcut("reload", None)]),
+    );
+
+    block_on(async {
+        let connection = mock.connect().await?;
+        let proxy = GlobalShortcutsProxy::new(&connection).await?;
+        let session = proxy.create_session().await?;
+        let shortcuts = proxy
+            .bind_shortcuts(
+                &session,
+                &[
+                    NewShortcut::new("close", "Close the tab").preferred_trigger("CTRL+W"),
+                    NewShortcut::new("reload", "Reload the tab"),
+                ],
+                &WindowIdentifier::default(),
+            )
+            .await?;
+        assert_eq!(shortcuts.len(), 2);
+        assert_eq!(shortcuts[0].id(), "close");
+        assert_eq!(shortcuts[0].trigger_description(), Some("Ctrl+W"));
+        // The portal may not describe the trigger.
+        assert_eq!(shortcuts[1].trigger_description(), None);
+
+        let shortcuts = proxy.list_shortcuts(&session).await?;
+        assert_eq!(shortcuts.len(), 1);
+        assert_eq!(shortcuts[0].id(), "reload");
+        assert_eq!(shortcuts[0].description(), "reload the tab");
+        Ok::<_, Error>(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn settings_stream() {
+    let mock = MockPortal::default();
+
+    block_on(async {
+        let connection = mock.connect().await?;
+        let proxy = SettingsProxy::new(&connection).await?;
+        let changes = proxy.receive_setting_changed_stream().await?;
+        futures::pin_mut!(changes);
+
+        for value in &["12h", "24h"] {
+            mock.emit_signal(
+                "/org/freedesktop/portal/desktop",
+                "org.freedesktop.portal.Settings",
+                "SettingChanged",
+                &(
+                    "org.gnome.desktop.interface",
+                    "clock-format",
+                    Value::from(*value),
+                ),
+            )
+            .await?;
+        }
+
+        let first = changes.next().await.unwrap();
+        let second = changes.next().await.unwrap();
+        assert_eq!(first.key(), "clock-format");
+        assert_eq!(second.value().downcast_ref::<str>(), Some("24h"));
+        Ok::<_, Error>(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn signal_invalid_body() {
+    let mock = MockPortal::default();
+
+    let result = block_on(async {
+        let connection = mock.connect().await?;
+        let proxy = SettingsProxy::new(&connection).await?;
+        // Emit until the one-shot receive is subscribed & gives up.
+        let emit = async {
+            loop {
+                mock.emit_signal(
+                    "/org/freedesktop/portal/desktop",
+                    "org.freedesktop.portal.Settings",
+                    "SettingChanged",
+                    &("org.gnome.desktop.interface", 42u32),
+                )
+                .await?;
+                async_io::Timer::after(Duration::from_millis(10)).await;
+            }
+        };
+        let receive = proxy.receive_setting_changed();
+        futures::pin_mut!(receive, emit);
+        match futures::future::select(receive, emit).await {
+            futures::future::Either::Left((result, _)) => result.map(|_| ()),
+            futures::future::Either::Right((result, _)) => result,
+        }
+    });
+    assert!(matches!(result, Err(Error::ParseError(_))));
+}